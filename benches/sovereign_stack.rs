@@ -4,6 +4,8 @@
 //! Run with: `cargo bench`
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rayon::prelude::*;
+use trueno::Vector;
 
 /// SIMD-style vector operations benchmark (Chapter 3/6 claims)
 fn bench_vector_operations(c: &mut Criterion) {
@@ -49,6 +51,52 @@ fn bench_vector_operations(c: &mut Criterion) {
     group.finish();
 }
 
+/// Scalar iterator dot product, the baseline for the SIMD comparison
+fn scalar_dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+/// trueno SIMD dot product vs scalar iterator (Chapter 3/6 SIMD claims)
+fn bench_simd_dot_product(c: &mut Criterion) {
+    let mut group = c.benchmark_group("simd_dot_product");
+
+    for size in [64, 256, 1024, 4096, 16384].iter() {
+        let a: Vec<f32> = (0..*size).map(|i| i as f32 * 0.01).collect();
+        let b: Vec<f32> = (0..*size).map(|i| (size - i) as f32 * 0.01).collect();
+        let vec_a = Vector::from_slice(&a);
+        let vec_b = Vector::from_slice(&b);
+
+        // Only time implementations that agree. SIMD lanes sum in a different
+        // order, so the tolerance is relative to the result's magnitude.
+        let scalar = scalar_dot(&a, &b);
+        let simd = vec_a.dot(&vec_b).expect("vectors have equal length");
+        assert!(
+            (scalar - simd).abs() <= 1e-4 * scalar.abs().max(1.0),
+            "size {}: scalar {} vs SIMD {}",
+            size,
+            scalar,
+            simd
+        );
+
+        group.throughput(Throughput::Elements(*size as u64));
+
+        group.bench_with_input(BenchmarkId::new("scalar", size), size, |bench, _| {
+            bench.iter(|| black_box(scalar_dot(black_box(&a), black_box(&b))))
+        });
+
+        group.bench_with_input(BenchmarkId::new("trueno_simd", size), size, |bench, _| {
+            bench.iter(|| {
+                let result = black_box(&vec_a)
+                    .dot(black_box(&vec_b))
+                    .expect("vectors have equal length");
+                black_box(result)
+            })
+        });
+    }
+
+    group.finish();
+}
+
 /// Matrix multiplication benchmark (Chapter 6/7 claims)
 fn bench_matrix_operations(c: &mut Criterion) {
     let mut group = c.benchmark_group("matrix_ops");
@@ -291,13 +339,73 @@ fn bench_determinism(c: &mut Criterion) {
     group.finish();
 }
 
+/// Elements per parallel chunk; fixed so partial sums never depend on thread count
+const REDUCTION_CHUNK: usize = 4096;
+
+/// Chunked parallel sum: each chunk is summed sequentially, then the partial
+/// sums are combined in chunk order. Float addition is not associative, so
+/// letting rayon pick split points would make the last bits vary run to run.
+fn chunked_parallel_sum(data: &[f64]) -> f64 {
+    let partials: Vec<f64> = data
+        .par_chunks(REDUCTION_CHUNK)
+        .map(|chunk| chunk.iter().sum::<f64>())
+        .collect();
+    partials.iter().sum()
+}
+
+/// Sequential vs chunked parallel reduction: where does threading pay off?
+fn bench_parallel_reduction(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel_reduction");
+
+    for size in [10_000, 100_000, 1_000_000].iter() {
+        let data: Vec<f64> = (0..*size).map(|i| (i as f64) * 0.0001).collect();
+
+        // The parallel result differs from the sequential one (different
+        // association order) but must be bit-identical across runs.
+        let reference = chunked_parallel_sum(&data);
+        for _ in 0..10 {
+            assert_eq!(
+                chunked_parallel_sum(&data).to_bits(),
+                reference.to_bits(),
+                "size {}: chunked parallel sum is not reproducible",
+                size
+            );
+        }
+        let sequential: f64 = data.iter().sum();
+        assert!(
+            (sequential - reference).abs() <= 1e-9 * sequential.abs(),
+            "size {}: sequential {} vs parallel {}",
+            size,
+            sequential,
+            reference
+        );
+
+        group.throughput(Throughput::Elements(*size as u64));
+
+        group.bench_with_input(BenchmarkId::new("sequential", size), size, |bench, _| {
+            bench.iter(|| {
+                let sum: f64 = black_box(&data).iter().sum();
+                black_box(sum)
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("rayon_chunked", size), size, |bench, _| {
+            bench.iter(|| black_box(chunked_parallel_sum(black_box(&data))))
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_vector_operations,
+    bench_simd_dot_product,
     bench_matrix_operations,
     bench_similarity_search,
     bench_ml_training,
     bench_determinism,
+    bench_parallel_reduction,
 );
 
 criterion_main!(benches);
@@ -9,6 +9,8 @@
 /// - Shows compiler catching bugs at compile-time
 /// - Demonstrates type system as quality gate
 /// - Proves "Jidoka" (automation with human touch) principle
+/// - `tests/andon_cord.rs` feeds each rejected example to `rustc` and checks
+///   the error code (`cargo test -p ch02-crisis --test andon_cord`)
 ///
 /// **KEY PRINCIPLE:** Toyota Way - Jidoka
 /// - Andon Cord: Workers can stop production line when defect detected
@@ -16,6 +18,118 @@
 /// - Prevention > Detection: Fix bugs before they run, not after
 use anyhow::Result;
 
+/// One Toyota Production System principle and its Rust counterpart
+#[derive(Debug, Clone, PartialEq)]
+struct PrincipleMapping {
+    principle: &'static str,
+    rust_feature: &'static str,
+    prevents: &'static str,
+}
+
+/// The TPS ↔ Rust mapping used throughout the chapter
+struct ToyotaMapping {
+    entries: Vec<PrincipleMapping>,
+}
+
+impl ToyotaMapping {
+    fn new() -> Self {
+        let entry = |principle, rust_feature, prevents| PrincipleMapping {
+            principle,
+            rust_feature,
+            prevents,
+        };
+        Self {
+            entries: vec![
+                entry(
+                    "Andon Cord",
+                    "Compilation failure",
+                    "Defective code reaching production",
+                ),
+                entry("Jidoka", "Borrow checker", "Use-after-free and data races"),
+                entry(
+                    "Poka-Yoke",
+                    "Type system",
+                    "Unhandled None and type mix-ups",
+                ),
+                entry(
+                    "Kaizen",
+                    "Cargo clippy warnings",
+                    "Accumulating code smells",
+                ),
+            ],
+        }
+    }
+
+    /// Find a principle by name, ignoring case
+    fn lookup(&self, principle: &str) -> Option<&PrincipleMapping> {
+        self.entries
+            .iter()
+            .find(|m| m.principle.eq_ignore_ascii_case(principle))
+    }
+}
+
+/// Category of a reported vulnerability
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BugClass {
+    UseAfterFree,
+    BufferOverflow,
+    DataRace,
+    NullDereference,
+    Injection,
+    Logic,
+}
+
+impl BugClass {
+    /// Bug classes that safe Rust rules out at compile time
+    fn is_memory_safety(self) -> bool {
+        matches!(
+            self,
+            BugClass::UseAfterFree
+                | BugClass::BufferOverflow
+                | BugClass::DataRace
+                | BugClass::NullDereference
+        )
+    }
+}
+
+/// Categorized vulnerability reports
+struct BugDataset {
+    entries: Vec<BugClass>,
+}
+
+impl BugDataset {
+    /// Ten hand-labelled reports that illustrate the classification
+    ///
+    /// Made up for this example, not drawn from any published study, so the
+    /// fraction it yields says nothing about real CVE populations.
+    fn illustrative_sample() -> Self {
+        use BugClass::*;
+        Self {
+            entries: vec![
+                UseAfterFree,
+                UseAfterFree,
+                BufferOverflow,
+                BufferOverflow,
+                BufferOverflow,
+                DataRace,
+                NullDereference,
+                Injection,
+                Logic,
+                Logic,
+            ],
+        }
+    }
+
+    /// Share of entries that are memory-safety bugs (0.0 when empty)
+    fn memory_safety_fraction(&self) -> f64 {
+        if self.entries.is_empty() {
+            return 0.0;
+        }
+        let memory = self.entries.iter().filter(|c| c.is_memory_safety()).count();
+        memory as f64 / self.entries.len() as f64
+    }
+}
+
 /// Example 1: Memory safety violations caught by compiler
 /// This code WOULD NOT COMPILE if uncommented (by design!)
 fn demonstrate_memory_safety() {
@@ -33,14 +147,14 @@ fn demonstrate_memory_safety() {
     println!("   ✅ Compiler BLOCKS this bug");
     println!();
 
-    // CASE 2: Data race (prevented by Send/Sync traits)
+    // CASE 2: Data race (prevented by exclusive &mut borrows)
     println!("   Case 2: Data race PREVENTED");
     println!("   ```rust");
     println!("   let mut data = vec![1, 2, 3];");
-    println!("   let handle = thread::spawn(|| {{");
-    println!("       data.push(4);     // ❌ ERROR: cannot capture mutable reference");
+    println!("   thread::scope(|s| {{");
+    println!("       s.spawn(|| data.push(4));");
+    println!("       s.spawn(|| data.push(5));  // ❌ ERROR: `data` borrowed mutably twice");
     println!("   }});");
-    println!("   data.push(5);         // Concurrent modification!");
     println!("   ```");
     println!("   ✅ Compiler BLOCKS this bug");
     println!();
@@ -158,15 +272,23 @@ fn main() -> Result<()> {
     // Key statistics
     println!("📊 Real-world impact (documented):");
     println!();
-    println!("   Microsoft Security Study (2019):");
-    println!("   - 70% of CVEs are memory safety bugs");
-    println!("   - Rust eliminates entire class of vulnerabilities");
-    println!();
     println!("   Google Chrome Security (2021):");
     println!("   - 70% of serious bugs are memory safety issues");
     println!("   - Rust components have 0 memory safety bugs");
     println!();
-    println!("   Source: Microsoft MSRC, Google Project Zero");
+    println!("   Source: Google Project Zero");
+    println!();
+
+    let sample = BugDataset::illustrative_sample();
+    println!(
+        "🧮 Classifying reports (illustrative sample of {}, not study data):",
+        sample.entries.len()
+    );
+    println!(
+        "   - {:.0}% classified as memory safety bugs",
+        sample.memory_safety_fraction() * 100.0
+    );
+    println!("   - Rust eliminates entire class of vulnerabilities");
     println!();
 
     // EU AI Act compliance
@@ -179,12 +301,23 @@ fn main() -> Result<()> {
     // Toyota Way principles
     println!("🏭 Toyota Way Mapping:");
     println!();
-    println!("   | TPS Principle | Rust Compiler Equivalent |");
-    println!("   |---------------|--------------------------|");
-    println!("   | Andon Cord    | Compilation failure      |");
-    println!("   | Jidoka        | Borrow checker           |");
-    println!("   | Poka-Yoke     | Type system              |");
-    println!("   | Kaizen        | Cargo clippy warnings    |");
+    let mapping = ToyotaMapping::new();
+    println!(
+        "   | TPS Principle | Rust Compiler Equivalent | Prevents                           |"
+    );
+    println!(
+        "   |---------------|--------------------------|------------------------------------|"
+    );
+    for m in &mapping.entries {
+        println!(
+            "   | {:<13} | {:<24} | {:<34} |",
+            m.principle, m.rust_feature, m.prevents
+        );
+    }
+    if let Some(andon) = mapping.lookup("andon cord") {
+        println!();
+        println!("   Pulling the Andon Cord = {}", andon.rust_feature);
+    }
     println!();
 
     // Key takeaway
@@ -226,6 +359,37 @@ mod tests {
         // Compilation success itself proves memory safety
     }
 
+    #[test]
+    fn test_principle_mapping() {
+        let mapping = ToyotaMapping::new();
+
+        let andon = mapping.lookup("Andon Cord").expect("Andon Cord is mapped");
+        assert_eq!(andon.rust_feature, "Compilation failure");
+
+        for principle in ["Andon Cord", "Jidoka", "Poka-Yoke", "Kaizen"] {
+            assert!(mapping.lookup(principle).is_some(), "{} missing", principle);
+        }
+        assert_eq!(mapping.entries.len(), 4);
+        assert!(mapping.lookup("Muda").is_none());
+    }
+
+    #[test]
+    fn test_memory_safety_fraction() {
+        let sample = BugDataset::illustrative_sample();
+        assert_eq!(sample.entries.len(), 10);
+        assert!((sample.memory_safety_fraction() - 0.7).abs() < 1e-12);
+
+        let empty = BugDataset {
+            entries: Vec::new(),
+        };
+        assert_eq!(empty.memory_safety_fraction(), 0.0);
+
+        let none = BugDataset {
+            entries: vec![BugClass::Logic, BugClass::Injection],
+        };
+        assert_eq!(none.memory_safety_fraction(), 0.0);
+    }
+
     #[test]
     fn test_wrapping_arithmetic() -> Result<()> {
         // Explicit overflow handling
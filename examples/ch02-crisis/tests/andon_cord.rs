@@ -0,0 +1,81 @@
+//! Compile-fail harness for the Andon Cord example.
+//!
+//! `toyota_andon` prints code the compiler is claimed to reject. These tests
+//! hand each of those programs to `rustc` and check that the build stops
+//! with the expected error code, so the claim is executed, not just printed.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn fixture(dir: &str, name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join(dir)
+        .join(name)
+}
+
+/// Type-check `path` as a 2021-edition binary, returning rustc's stderr on failure
+fn compile(path: &Path) -> Result<(), String> {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let out_dir = std::env::temp_dir().join("ch02-andon-cord");
+    let output = Command::new(rustc)
+        .args([
+            "--edition",
+            "2021",
+            "--emit=metadata",
+            "--crate-type",
+            "bin",
+        ])
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .arg(path)
+        .output()
+        .expect("rustc runs");
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).into_owned())
+    }
+}
+
+/// Assert that the fixture is rejected with error `code`
+fn assert_rejected(name: &str, code: &str) {
+    let path = fixture("compile_fail", name);
+    match compile(&path) {
+        Ok(()) => panic!("{} compiled, but the compiler should stop it", name),
+        Err(stderr) => assert!(
+            stderr.contains(&format!("error[{}]", code)),
+            "{} should fail with {}, got:\n{}",
+            name,
+            code,
+            stderr
+        ),
+    }
+}
+
+#[test]
+fn test_use_after_free_is_rejected() {
+    // E0505: cannot move out of `data` because it is borrowed
+    assert_rejected("use_after_free.rs", "E0505");
+}
+
+#[test]
+fn test_data_race_is_rejected() {
+    // E0499: cannot borrow `data` as mutable more than once at a time
+    assert_rejected("data_race.rs", "E0499");
+}
+
+#[test]
+fn test_unhandled_option_is_rejected() {
+    // E0277: `Option<i32>` doesn't implement `std::fmt::Display`
+    assert_rejected("unhandled_option.rs", "E0277");
+}
+
+#[test]
+fn test_handled_option_compiles() {
+    // Control case: the harness itself must accept correct code
+    let path = fixture("compile_pass", "handled_option.rs");
+    if let Err(stderr) = compile(&path) {
+        panic!("handled_option.rs should compile:\n{}", stderr);
+    }
+}
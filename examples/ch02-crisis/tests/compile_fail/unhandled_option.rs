@@ -0,0 +1,5 @@
+// Andon cord: using a possibly-missing value as if it were always present.
+fn main() {
+    let value: Option<i32> = None;
+    println!("{}", value);
+}
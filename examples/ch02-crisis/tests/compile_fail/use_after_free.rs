@@ -0,0 +1,7 @@
+// Andon cord: dropping data while a reference to it is still alive.
+fn main() {
+    let data = vec![1, 2, 3];
+    let reference = &data[0];
+    drop(data);
+    println!("{}", reference);
+}
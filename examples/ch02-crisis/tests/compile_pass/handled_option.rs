@@ -0,0 +1,8 @@
+// The fix for unhandled_option.rs: the None case is handled explicitly.
+fn main() {
+    let value: Option<i32> = None;
+    match value {
+        Some(v) => println!("{}", v),
+        None => println!("no value"),
+    }
+}
@@ -0,0 +1,80 @@
+//! pmat grade bands, shared by the TDG and coverage examples
+//!
+//! A+ = 95-100, A = 90-94, A- = 85-89, ... D = 50-54, F below 50.
+
+use std::fmt;
+
+/// Letter grade on the pmat score bands (matching pmat spec)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Grade {
+    F,      // < 50
+    D,      // 50-54
+    CMinus, // 55-59
+    C,      // 60-64
+    CPlus,  // 65-69
+    BMinus, // 70-74
+    B,      // 75-79
+    BPlus,  // 80-84
+    AMinus, // 85-89
+    A,      // 90-94
+    APlus,  // 95-100
+}
+
+impl Grade {
+    /// All grades from best to worst
+    pub(crate) const ALL: [Grade; 11] = [
+        Grade::APlus,
+        Grade::A,
+        Grade::AMinus,
+        Grade::BPlus,
+        Grade::B,
+        Grade::BMinus,
+        Grade::CPlus,
+        Grade::C,
+        Grade::CMinus,
+        Grade::D,
+        Grade::F,
+    ];
+
+    /// Lowest score that earns this grade
+    pub(crate) fn min_score(self) -> f64 {
+        match self {
+            Grade::APlus => 95.0,
+            Grade::A => 90.0,
+            Grade::AMinus => 85.0,
+            Grade::BPlus => 80.0,
+            Grade::B => 75.0,
+            Grade::BMinus => 70.0,
+            Grade::CPlus => 65.0,
+            Grade::C => 60.0,
+            Grade::CMinus => 55.0,
+            Grade::D => 50.0,
+            Grade::F => 0.0,
+        }
+    }
+
+    pub(crate) fn from_score(score: f64) -> Self {
+        Grade::ALL
+            .into_iter()
+            .find(|g| score >= g.min_score())
+            .unwrap_or(Grade::F)
+    }
+}
+
+impl fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Grade::APlus => write!(f, "A+"),
+            Grade::A => write!(f, "A"),
+            Grade::AMinus => write!(f, "A-"),
+            Grade::BPlus => write!(f, "B+"),
+            Grade::B => write!(f, "B"),
+            Grade::BMinus => write!(f, "B-"),
+            Grade::CPlus => write!(f, "C+"),
+            Grade::C => write!(f, "C"),
+            Grade::CMinus => write!(f, "C-"),
+            Grade::D => write!(f, "D"),
+            Grade::F => write!(f, "F"),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! TDG scoring, shared by the TDG and coverage examples
+//!
+//! Coverage 40%, mutation 30%, complexity 15%, quality 10%, documentation 5%.
+
+use serde::Serialize;
+
+/// Cyclomatic complexity ceiling (from pmat config)
+const MAX_COMPLEXITY: f64 = 15.0;
+
+/// Complexity points lost per unit the worst function exceeds the ceiling
+const MAX_COMPLEXITY_EXCESS_PENALTY: f64 = 2.0;
+
+/// Quality metrics from various tools
+#[derive(Debug, Clone, Default, Serialize)]
+#[allow(dead_code)]
+pub(crate) struct QualityMetrics {
+    // Coverage metrics
+    pub(crate) line_coverage_pct: f64,   // From cargo-tarpaulin
+    pub(crate) branch_coverage_pct: f64, // From cargo-tarpaulin
+
+    // Mutation testing
+    pub(crate) mutation_score_pct: f64, // From cargo-mutants
+
+    // Code complexity
+    pub(crate) avg_cyclomatic_complexity: f64, // From cargo-complexity or pmat
+    pub(crate) max_cyclomatic_complexity: u32, // Worst function
+
+    // Code quality
+    pub(crate) clippy_warnings: u32, // From cargo clippy
+    pub(crate) clippy_errors: u32,   // From cargo clippy -D warnings
+
+    // Documentation
+    pub(crate) doc_coverage_pct: f64, // Public API documentation
+}
+
+/// Relative weight of each TDG component (must sum to 1.0)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct TdgWeights {
+    pub(crate) coverage: f64,
+    pub(crate) mutation: f64,
+    pub(crate) complexity: f64,
+    pub(crate) quality: f64,
+    pub(crate) documentation: f64,
+    /// Share of the coverage component taken from branch (vs line) coverage
+    pub(crate) branch_share: f64,
+}
+
+impl Default for TdgWeights {
+    /// pmat's standard 40/30/15/10/5 split
+    fn default() -> Self {
+        Self {
+            coverage: 0.40,
+            mutation: 0.30,
+            complexity: 0.15,
+            quality: 0.10,
+            documentation: 0.05,
+            branch_share: 0.5,
+        }
+    }
+}
+
+impl QualityMetrics {
+    /// Coverage score (0-100): line and branch coverage blended by `branch_share`
+    pub(crate) fn coverage_score(&self, weights: &TdgWeights) -> f64 {
+        self.line_coverage_pct * (1.0 - weights.branch_share)
+            + self.branch_coverage_pct * weights.branch_share
+    }
+
+    /// Complexity score (0-100, inverted: lower complexity = better score)
+    ///
+    /// Max acceptable complexity: 15 (from pmat config). Any function above
+    /// the ceiling docks points even when the average looks healthy.
+    pub(crate) fn complexity_score(&self) -> f64 {
+        let complexity_penalty = (self.avg_cyclomatic_complexity / MAX_COMPLEXITY).min(1.0);
+        let excess = self
+            .max_cyclomatic_complexity
+            .saturating_sub(MAX_COMPLEXITY as u32);
+        ((1.0 - complexity_penalty) * 100.0 - excess as f64 * MAX_COMPLEXITY_EXCESS_PENALTY)
+            .max(0.0)
+    }
+
+    /// Quality score (0-100): zero warnings/errors = 100%, any warnings = penalty
+    pub(crate) fn quality_score(&self) -> f64 {
+        let total_issues = self.clippy_warnings + self.clippy_errors;
+        if total_issues == 0 {
+            100.0
+        } else {
+            // Harsh penalty: each issue costs points
+            (100.0 - (total_issues as f64 * 2.0)).max(0.0)
+        }
+    }
+
+    /// Calculate TDG score (0-100) from quality metrics
+    ///
+    /// Formula (matching pmat implementation, default weights):
+    /// - Coverage: 40% weight (line + branch) / 2, split set by `branch_share`
+    /// - Mutation: 30% weight
+    /// - Complexity: 15% weight (inverted, lower = better; max > 15 penalized)
+    /// - Quality: 10% weight (zero warnings = 100%)
+    /// - Documentation: 5% weight (public API doc coverage)
+    pub(crate) fn calculate_tdg_score(&self, weights: &TdgWeights) -> f64 {
+        self.coverage_score(weights) * weights.coverage
+            + self.mutation_score_pct * weights.mutation
+            + self.complexity_score() * weights.complexity
+            + self.quality_score() * weights.quality
+            + self.doc_coverage_pct * weights.documentation
+    }
+}
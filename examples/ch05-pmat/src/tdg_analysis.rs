@@ -7,117 +7,315 @@
 ///
 /// **VALIDATION:** `make run-ch05-tdg`
 /// - Calculates TDG score from quality metrics
-/// - Shows grade mapping (A+ = 95-100, A = 90-94, etc.)
-/// - Demonstrates threshold enforcement (≥ A- = 90)
+/// - Shows grade mapping (A+ = 95-100, A = 90-94, A- = 85-89, etc.)
+/// - Demonstrates threshold enforcement (≥ A- = 85)
 ///
 /// **KEY PRINCIPLE:** METRICS OVER ADJECTIVES
-/// - Not "good quality" → "TDG grade: A- (91.2)"
+/// - Not "good quality" → "TDG grade: B+ (83.9)"
 /// - Not "well-tested" → "95.3% coverage, 82% mutation score"
 /// - Not "maintainable" → "Cyclomatic complexity: 8.3 avg"
-use anyhow::Result;
-use std::fmt;
-
-/// TDG grade levels (matching pmat spec)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-enum Grade {
-    F,      // < 60
-    D,      // 60-69
-    C,      // 70-79
-    B,      // 80-84
-    BMinus, // 85-89
-    A,      // 90-94
-    APlus,  // 95-100
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+mod grade;
+mod tdg;
+use grade::Grade;
+use tdg::{QualityMetrics, TdgWeights};
+
+/// Points contributed by each TDG component, archived per build by CI
+#[derive(Debug, Clone, Serialize)]
+struct TdgBreakdown {
+    coverage: f64,
+    mutation: f64,
+    complexity: f64,
+    quality: f64,
+    documentation: f64,
+    total: f64,
+    grade: String,
 }
 
-impl Grade {
-    fn from_score(score: f64) -> Self {
-        match score {
-            s if s >= 95.0 => Grade::APlus,
-            s if s >= 90.0 => Grade::A,
-            s if s >= 85.0 => Grade::BMinus,
-            s if s >= 80.0 => Grade::B,
-            s if s >= 70.0 => Grade::C,
-            s if s >= 60.0 => Grade::D,
-            _ => Grade::F,
-        }
-    }
+/// Quality artifact: raw metrics plus the scored breakdown
+#[derive(Serialize)]
+struct QualityArtifact<'a> {
+    metrics: &'a QualityMetrics,
+    breakdown: TdgBreakdown,
 }
 
-impl fmt::Display for Grade {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Grade::APlus => write!(f, "A+"),
-            Grade::A => write!(f, "A"),
-            Grade::BMinus => write!(f, "B+"),
-            Grade::B => write!(f, "B"),
-            Grade::C => write!(f, "C"),
-            Grade::D => write!(f, "D"),
-            Grade::F => write!(f, "F"),
-        }
-    }
+/// Minimal view of a `cargo tarpaulin --out Json` report
+#[derive(Debug, Deserialize)]
+struct TarpaulinReport {
+    files: Vec<TarpaulinFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TarpaulinFile {
+    #[serde(default)]
+    traces: Vec<TarpaulinTrace>,
+    covered: usize,
+    coverable: usize,
 }
 
-/// Quality metrics from various tools
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
-struct QualityMetrics {
-    // Coverage metrics
-    line_coverage_pct: f64,   // From cargo-tarpaulin
-    branch_coverage_pct: f64, // From cargo-tarpaulin
+#[derive(Debug, Deserialize)]
+struct TarpaulinTrace {
+    stats: TraceStat,
+}
+
+/// Per-trace coverage statistic (tarpaulin's `CoverageStat`)
+#[derive(Debug, Deserialize)]
+enum TraceStat {
+    /// Hit count; line totals come from the per-file `covered` field instead
+    #[allow(dead_code)]
+    Line(u64),
+    Branch(LogicState),
+    Condition(Vec<LogicState>),
+}
+
+#[derive(Debug, Deserialize)]
+struct LogicState {
+    been_true: bool,
+    been_false: bool,
+}
 
-    // Mutation testing
-    mutation_score_pct: f64, // From cargo-mutants
+impl LogicState {
+    /// (outcomes taken, outcomes possible)
+    fn outcomes(&self) -> (usize, usize) {
+        (self.been_true as usize + self.been_false as usize, 2)
+    }
+}
 
-    // Code complexity
-    avg_cyclomatic_complexity: f64, // From cargo-complexity or pmat
-    max_cyclomatic_complexity: u32, // Worst function
+impl TdgWeights {
+    /// Create validated weights; rejects negative weights or a sum other than 1.0
+    fn new(
+        coverage: f64,
+        mutation: f64,
+        complexity: f64,
+        quality: f64,
+        documentation: f64,
+    ) -> Result<Self, String> {
+        let weights = Self {
+            coverage,
+            mutation,
+            complexity,
+            quality,
+            documentation,
+            branch_share: 0.5,
+        };
+        weights.validate()?;
+        Ok(weights)
+    }
 
-    // Code quality
-    clippy_warnings: u32, // From cargo clippy
-    clippy_errors: u32,   // From cargo clippy -D warnings
+    /// Weight branch coverage as `share` (0.0-1.0) of the coverage component;
+    /// branch coverage is stronger evidence than line coverage
+    fn with_branch_share(mut self, share: f64) -> Result<Self, String> {
+        if !(0.0..=1.0).contains(&share) {
+            return Err(format!(
+                "branch share must be within 0.0-1.0, got {}",
+                share
+            ));
+        }
+        self.branch_share = share;
+        Ok(self)
+    }
 
-    // Documentation
-    doc_coverage_pct: f64, // Public API documentation
+    fn validate(&self) -> Result<(), String> {
+        let parts = [
+            self.coverage,
+            self.mutation,
+            self.complexity,
+            self.quality,
+            self.documentation,
+        ];
+        if parts.iter().any(|w| *w < 0.0) {
+            return Err("TDG weights must be non-negative".to_string());
+        }
+        let sum: f64 = parts.iter().sum();
+        if (sum - 1.0).abs() > 1e-9 {
+            return Err(format!("TDG weights must sum to 1.0, got {:.3}", sum));
+        }
+        Ok(())
+    }
 }
 
 impl QualityMetrics {
-    /// Calculate TDG score (0-100) from quality metrics
+    /// Populate coverage fields from a cargo-tarpaulin JSON report
     ///
-    /// Formula (matching pmat implementation):
-    /// - Coverage: 40% weight (line + branch) / 2
-    /// - Mutation: 30% weight
-    /// - Complexity: 15% weight (inverted, lower = better)
-    /// - Quality: 15% weight (zero warnings = 100%)
-    fn calculate_tdg_score(&self) -> f64 {
-        // Coverage component (40% weight)
-        let coverage_score = (self.line_coverage_pct + self.branch_coverage_pct) / 2.0;
-        let coverage_component = coverage_score * 0.40;
-
-        // Mutation component (30% weight)
-        let mutation_component = self.mutation_score_pct * 0.30;
-
-        // Complexity component (15% weight, inverted)
-        // Lower complexity = better score
-        // Max acceptable complexity: 15 (from pmat config)
-        let complexity_penalty = (self.avg_cyclomatic_complexity / 15.0).min(1.0);
-        let complexity_component = (1.0 - complexity_penalty) * 100.0 * 0.15;
-
-        // Quality component (15% weight)
-        // Zero warnings/errors = 100%, any warnings = penalty
-        let total_issues = self.clippy_warnings + self.clippy_errors;
-        let quality_score = if total_issues == 0 {
-            100.0
+    /// Line coverage is covered/coverable summed over all files. Branch
+    /// coverage comes from `Branch`/`Condition` traces; tarpaulin only emits
+    /// those with branch instrumentation enabled, so without them branch
+    /// coverage falls back to line coverage. All other fields are defaulted.
+    fn from_tarpaulin_json(json: &str) -> Result<Self> {
+        let report: TarpaulinReport =
+            serde_json::from_str(json).context("invalid tarpaulin JSON report")?;
+
+        let covered: usize = report.files.iter().map(|f| f.covered).sum();
+        let coverable: usize = report.files.iter().map(|f| f.coverable).sum();
+        let line_coverage_pct = if coverable == 0 {
+            0.0
+        } else {
+            covered as f64 / coverable as f64 * 100.0
+        };
+
+        let (taken, possible) = report
+            .files
+            .iter()
+            .flat_map(|f| &f.traces)
+            .flat_map(|t| match &t.stats {
+                TraceStat::Line(_) => Vec::new(),
+                TraceStat::Branch(state) => vec![state.outcomes()],
+                TraceStat::Condition(states) => states.iter().map(LogicState::outcomes).collect(),
+            })
+            .fold((0, 0), |(t, p), (dt, dp)| (t + dt, p + dp));
+        let branch_coverage_pct = if possible == 0 {
+            line_coverage_pct
         } else {
-            // Harsh penalty: each issue costs points
-            (100.0 - (total_issues as f64 * 2.0)).max(0.0)
+            taken as f64 / possible as f64 * 100.0
         };
-        let quality_component = quality_score * 0.15;
 
-        // Total TDG score
-        coverage_component + mutation_component + complexity_component + quality_component
+        Ok(Self {
+            line_coverage_pct,
+            branch_coverage_pct,
+            ..Self::default()
+        })
+    }
+
+    /// Per-component points under `weights`
+    fn breakdown(&self, weights: &TdgWeights) -> TdgBreakdown {
+        let total = self.calculate_tdg_score(weights);
+        TdgBreakdown {
+            coverage: self.coverage_score(weights) * weights.coverage,
+            mutation: self.mutation_score_pct * weights.mutation,
+            complexity: self.complexity_score() * weights.complexity,
+            quality: self.quality_score() * weights.quality,
+            documentation: self.doc_coverage_pct * weights.documentation,
+            total,
+            grade: Grade::from_score(total).to_string(),
+        }
+    }
+
+    /// Metrics and TDG breakdown as pretty-printed JSON
+    fn to_json(&self, weights: &TdgWeights) -> Result<String> {
+        let artifact = QualityArtifact {
+            metrics: self,
+            breakdown: self.breakdown(weights),
+        };
+        Ok(serde_json::to_string_pretty(&artifact)?)
+    }
+
+    /// Ratchet check: the TDG score (default weights) may not drop more than
+    /// `tolerance` points below the stored baseline
+    fn check_ratchet(&self, baseline_score: f64, tolerance: f64) -> Result<(), String> {
+        let score = self.calculate_tdg_score(&TdgWeights::default());
+        if score + tolerance < baseline_score {
+            Err(format!(
+                "TDG regressed: {:.1} < baseline {:.1} (tolerance {:.1})",
+                score, baseline_score, tolerance
+            ))
+        } else {
+            Ok(())
+        }
     }
 }
 
+/// Direction of TDG scores over time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Trend {
+    Improving,
+    Flat,
+    Regressing,
+}
+
+/// TDG scores recorded per commit or sprint, oldest first
+#[derive(Debug, Clone, Default)]
+struct TdgHistory {
+    points: Vec<(String, f64)>,
+}
+
+impl TdgHistory {
+    /// Slopes within ±this many points per step count as flat
+    const FLAT_SLOPE: f64 = 0.1;
+
+    fn push(&mut self, label: impl Into<String>, score: f64) {
+        self.points.push((label.into(), score));
+    }
+
+    /// Least-squares slope of score against point index (0.0 for < 2 points)
+    fn slope(&self) -> f64 {
+        let n = self.points.len() as f64;
+        if self.points.len() < 2 {
+            return 0.0;
+        }
+        let mean_x = (n - 1.0) / 2.0;
+        let mean_y = self.points.iter().map(|(_, s)| s).sum::<f64>() / n;
+        let (mut cov, mut var) = (0.0, 0.0);
+        for (i, (_, score)) in self.points.iter().enumerate() {
+            let dx = i as f64 - mean_x;
+            cov += dx * (score - mean_y);
+            var += dx * dx;
+        }
+        cov / var
+    }
+
+    fn trend(&self) -> Trend {
+        match self.slope() {
+            s if s > Self::FLAT_SLOPE => Trend::Improving,
+            s if s < -Self::FLAT_SLOPE => Trend::Regressing,
+            _ => Trend::Flat,
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = String::from("label,score\n");
+        for (label, score) in &self.points {
+            csv.push_str(&format!("{},{:.1}\n", label, score));
+        }
+        csv
+    }
+}
+
+/// Persist a baseline TDG score (e.g. `.pmat/tdg-baseline`)
+fn save_baseline(path: &Path, score: f64) -> Result<()> {
+    fs::write(path, format!("{:.4}\n", score))
+        .with_context(|| format!("writing baseline {}", path.display()))
+}
+
+/// Load a baseline TDG score written by [`save_baseline`]
+fn load_baseline(path: &Path) -> Result<f64> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("reading baseline {}", path.display()))?;
+    text.trim()
+        .parse()
+        .with_context(|| format!("invalid baseline score in {}", path.display()))
+}
+
+/// Trimmed-down `cargo tarpaulin --out Json` output used when no report path is given
+const SAMPLE_TARPAULIN_JSON: &str = r#"{
+  "files": [
+    {
+      "path": ["src", "vector.rs"],
+      "traces": [
+        { "line": 10, "address": [4096], "length": 1, "stats": { "Line": 3 } },
+        { "line": 11, "address": [4112], "length": 1, "stats": { "Line": 0 } }
+      ],
+      "covered": 1,
+      "coverable": 2
+    },
+    {
+      "path": ["src", "matrix.rs"],
+      "traces": [
+        { "line": 20, "address": [8192], "length": 1, "stats": { "Line": 7 } },
+        { "line": 21, "address": [8208], "length": 1, "stats": { "Line": 2 } }
+      ],
+      "covered": 2,
+      "coverable": 2
+    }
+  ],
+  "coverage": 75.0,
+  "covered": 3,
+  "coverable": 4
+}"#;
+
 fn main() -> Result<()> {
     println!("📊 Chapter 5: pmat TDG (Test-Driven Grade) Analysis");
     println!();
@@ -135,7 +333,8 @@ fn main() -> Result<()> {
         doc_coverage_pct: 98.0,
     };
 
-    print_metrics_analysis(&excellent, "Sovereign AI Stack Book")?;
+    let weights = TdgWeights::default();
+    print_metrics_analysis(&excellent, "Sovereign AI Stack Book", &weights)?;
 
     // Example 2: Good quality (meets minimum standards)
     println!("📈 Example 2: GOOD quality (meets minimum standards)");
@@ -150,7 +349,7 @@ fn main() -> Result<()> {
         doc_coverage_pct: 85.0,
     };
 
-    print_metrics_analysis(&good, "Typical Project")?;
+    print_metrics_analysis(&good, "Typical Project", &weights)?;
 
     // Example 3: Below standards (needs improvement)
     println!("📈 Example 3: BELOW STANDARDS (needs improvement)");
@@ -165,18 +364,83 @@ fn main() -> Result<()> {
         doc_coverage_pct: 45.0,
     };
 
-    print_metrics_analysis(&poor, "Legacy Codebase")?;
+    print_metrics_analysis(&poor, "Legacy Codebase", &weights)?;
+
+    // Example 4: Same project, team that prioritizes mutation testing
+    println!("📈 Example 4: Custom weights (mutation-heavy, branch-focused team)");
+    let mutation_heavy = TdgWeights::new(0.30, 0.40, 0.15, 0.10, 0.05)
+        .and_then(|w| w.with_branch_share(0.7))
+        .map_err(anyhow::Error::msg)?;
+    print_metrics_analysis(&good, "Typical Project", &mutation_heavy)?;
+
+    // Example 5: Coverage from real tool output
+    // Pass a path to tarpaulin-report.json to analyze your own project
+    println!("📈 Example 5: Coverage parsed from cargo-tarpaulin JSON");
+    let json = match std::env::args().nth(1) {
+        Some(path) => {
+            std::fs::read_to_string(&path).with_context(|| format!("reading {}", path))?
+        }
+        None => SAMPLE_TARPAULIN_JSON.to_string(),
+    };
+    let parsed = QualityMetrics::from_tarpaulin_json(&json)?;
+    println!("   Line coverage:   {:.1}%", parsed.line_coverage_pct);
+    println!("   Branch coverage: {:.1}%", parsed.branch_coverage_pct);
+    println!();
+
+    // Example 6: Ratchet against a stored baseline
+    println!("📈 Example 6: Ratchet enforcement (TDG may only improve)");
+    let baseline_path = std::env::temp_dir().join("ch05-tdg-baseline");
+    save_baseline(
+        &baseline_path,
+        excellent.calculate_tdg_score(&TdgWeights::default()),
+    )?;
+    let baseline = load_baseline(&baseline_path)?;
+    println!("   Baseline: {:.1} ({})", baseline, baseline_path.display());
+    for (name, metrics) in [("excellent", &excellent), ("good", &good)] {
+        match metrics.check_ratchet(baseline, 0.5) {
+            Ok(()) => println!("   ✅ {}: ratchet holds", name),
+            Err(e) => println!("   ❌ {}: {}", name, e),
+        }
+    }
+    println!();
+
+    // Example 7: Track progress over sprints
+    println!("📈 Example 7: TDG trend across sprints");
+    let mut history = TdgHistory::default();
+    for (label, metrics) in [
+        ("sprint-1", &poor),
+        ("sprint-2", &good),
+        ("sprint-3", &excellent),
+    ] {
+        history.push(label, metrics.calculate_tdg_score(&TdgWeights::default()));
+    }
+    for line in history.to_csv().lines() {
+        println!("   {}", line);
+    }
+    println!(
+        "   Trend: {:?} ({:+.1} points/sprint)",
+        history.trend(),
+        history.slope()
+    );
+    println!();
+
+    // Example 8: Archive the quality artifact for this build
+    println!("📈 Example 8: JSON quality artifact (for CI archiving)");
+    for line in excellent.to_json(&TdgWeights::default())?.lines() {
+        println!("   {}", line);
+    }
+    println!();
 
     // Key takeaways
     println!("🎯 Key takeaways:");
     println!("   1. TDG converts subjective 'quality' into objective score");
     println!("   2. Weighted formula balances coverage, mutation, complexity, quality");
-    println!("   3. Grade threshold (≥A-/90) enforces minimum standards");
+    println!("   3. Grade threshold (≥A-/85) enforces minimum standards");
     println!("   4. Ratchet effect: TDG should only improve over time");
     println!();
 
     println!("🛡️  pmat.toml configuration:");
-    println!("   min_tdg_grade = 'A-'  # Blocks commits below 90");
+    println!("   min_tdg_grade = 'A-'  # Blocks commits below 85");
     println!("   min_test_coverage = 95.0");
     println!("   max_cyclomatic_complexity = 15");
     println!();
@@ -191,7 +455,11 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn print_metrics_analysis(metrics: &QualityMetrics, project_name: &str) -> Result<()> {
+fn print_metrics_analysis(
+    metrics: &QualityMetrics,
+    project_name: &str,
+    weights: &TdgWeights,
+) -> Result<()> {
     println!("   Project: {}", project_name);
     println!();
 
@@ -216,65 +484,72 @@ fn print_metrics_analysis(metrics: &QualityMetrics, project_name: &str) -> Resul
     );
     println!("      Clippy warnings:   {}", metrics.clippy_warnings);
     println!("      Clippy errors:     {}", metrics.clippy_errors);
+    println!("      Doc coverage:      {:.1}%", metrics.doc_coverage_pct);
     println!();
 
     // Calculate TDG score
-    let tdg_score = metrics.calculate_tdg_score();
+    let tdg_score = metrics.calculate_tdg_score(weights);
     let grade = Grade::from_score(tdg_score);
 
     println!("   🎯 TDG Score: {:.1} (Grade: {})", tdg_score, grade);
     println!();
 
     // Component breakdown
-    let coverage_avg = (metrics.line_coverage_pct + metrics.branch_coverage_pct) / 2.0;
+    let coverage_avg = metrics.coverage_score(weights);
     println!("   📈 Component breakdown:");
     println!(
-        "      Coverage (40%):   {:.1}% → {:.1} points",
+        "      Coverage ({:.0}%):   {:.1}% → {:.1} points",
+        weights.coverage * 100.0,
         coverage_avg,
-        coverage_avg * 0.40
+        coverage_avg * weights.coverage
     );
     println!(
-        "      Mutation (30%):   {:.1}% → {:.1} points",
+        "      Mutation ({:.0}%):   {:.1}% → {:.1} points",
+        weights.mutation * 100.0,
         metrics.mutation_score_pct,
-        metrics.mutation_score_pct * 0.30
+        metrics.mutation_score_pct * weights.mutation
     );
 
-    let complexity_penalty = (metrics.avg_cyclomatic_complexity / 15.0).min(1.0);
-    let complexity_score = (1.0 - complexity_penalty) * 100.0;
+    let complexity_score = metrics.complexity_score();
     println!(
-        "      Complexity (15%): {:.1} → {:.1} points",
+        "      Complexity ({:.0}%): {:.1} → {:.1} points",
+        weights.complexity * 100.0,
         complexity_score,
-        complexity_score * 0.15
+        complexity_score * weights.complexity
     );
 
-    let total_issues = metrics.clippy_warnings + metrics.clippy_errors;
-    let quality_score = if total_issues == 0 {
-        100.0
-    } else {
-        (100.0 - (total_issues as f64 * 2.0)).max(0.0)
-    };
+    let quality_score = metrics.quality_score();
     println!(
-        "      Quality (15%):    {:.1} → {:.1} points",
+        "      Quality ({:.0}%):    {:.1} → {:.1} points",
+        weights.quality * 100.0,
         quality_score,
-        quality_score * 0.15
+        quality_score * weights.quality
+    );
+    println!(
+        "      Docs ({:.0}%):        {:.1}% → {:.1} points",
+        weights.documentation * 100.0,
+        metrics.doc_coverage_pct,
+        metrics.doc_coverage_pct * weights.documentation
     );
     println!();
 
     // Pass/fail analysis
-    let min_grade = 90.0; // A- threshold from pmat config
-    if tdg_score >= min_grade {
+    let min_grade = Grade::AMinus; // from pmat config
+    let min_score = min_grade.min_score();
+    if grade >= min_grade {
         println!(
-            "   ✅ PASS: TDG {:.1} ≥ {:.1} (meets A- standard)",
-            tdg_score, min_grade
+            "   ✅ PASS: TDG {:.1} ≥ {:.1} (meets {} standard)",
+            tdg_score, min_score, min_grade
         );
     } else {
         println!(
-            "   ❌ FAIL: TDG {:.1} < {:.1} (below A- standard)",
-            tdg_score, min_grade
+            "   ❌ FAIL: TDG {:.1} < {:.1} (below {} standard)",
+            tdg_score, min_score, min_grade
         );
         println!(
-            "      Needs {:.1} points to reach A-",
-            min_grade - tdg_score
+            "      Needs {:.1} points to reach {}",
+            min_score - tdg_score,
+            min_grade
         );
     }
     println!();
@@ -290,13 +565,33 @@ mod tests {
     fn test_grade_from_score() {
         assert_eq!(Grade::from_score(100.0), Grade::APlus);
         assert_eq!(Grade::from_score(95.0), Grade::APlus);
-        assert_eq!(Grade::from_score(92.0), Grade::A);
+        assert_eq!(Grade::from_score(94.9), Grade::A);
         assert_eq!(Grade::from_score(90.0), Grade::A);
-        assert_eq!(Grade::from_score(87.0), Grade::BMinus);
-        assert_eq!(Grade::from_score(82.0), Grade::B);
-        assert_eq!(Grade::from_score(75.0), Grade::C);
-        assert_eq!(Grade::from_score(65.0), Grade::D);
-        assert_eq!(Grade::from_score(50.0), Grade::F);
+        assert_eq!(Grade::from_score(89.9), Grade::AMinus);
+        assert_eq!(Grade::from_score(85.0), Grade::AMinus);
+        assert_eq!(Grade::from_score(84.9), Grade::BPlus);
+        assert_eq!(Grade::from_score(80.0), Grade::BPlus);
+        assert_eq!(Grade::from_score(75.0), Grade::B);
+        assert_eq!(Grade::from_score(70.0), Grade::BMinus);
+        assert_eq!(Grade::from_score(65.0), Grade::CPlus);
+        assert_eq!(Grade::from_score(60.0), Grade::C);
+        assert_eq!(Grade::from_score(55.0), Grade::CMinus);
+        assert_eq!(Grade::from_score(50.0), Grade::D);
+        assert_eq!(Grade::from_score(49.9), Grade::F);
+        assert_eq!(Grade::from_score(0.0), Grade::F);
+
+        // Every band starts exactly at its documented threshold
+        for grade in Grade::ALL {
+            assert_eq!(Grade::from_score(grade.min_score()), grade);
+        }
+    }
+
+    #[test]
+    fn test_grade_display() {
+        assert_eq!(Grade::AMinus.to_string(), "A-");
+        assert_eq!(Grade::BPlus.to_string(), "B+");
+        assert_eq!(Grade::BMinus.to_string(), "B-");
+        assert_eq!(Grade::from_score(87.0).to_string(), "A-");
     }
 
     #[test]
@@ -312,7 +607,7 @@ mod tests {
             doc_coverage_pct: 100.0,
         };
 
-        let score = perfect.calculate_tdg_score();
+        let score = perfect.calculate_tdg_score(&TdgWeights::default());
         assert!(
             score >= 95.0,
             "Perfect score should be ≥95 (A+), got {}",
@@ -334,13 +629,14 @@ mod tests {
             doc_coverage_pct: 90.0,
         };
 
-        let score = acceptable.calculate_tdg_score();
+        let score = acceptable.calculate_tdg_score(&TdgWeights::default());
         // With these metrics:
         // - Coverage (40%): 87.5% * 0.40 = 35.0
         // - Mutation (30%): 80.0% * 0.30 = 24.0
         // - Complexity (15%): (1 - 10/15) * 100 * 0.15 = 5.0
-        // - Quality (15%): 100.0 * 0.15 = 15.0
-        // Total: 35.0 + 24.0 + 5.0 + 15.0 = 79.0
+        // - Quality (10%): 100.0 * 0.10 = 10.0
+        // - Documentation (5%): 90.0% * 0.05 = 4.5
+        // Total: 35.0 + 24.0 + 5.0 + 10.0 + 4.5 = 78.5
         assert!(
             score >= 75.0,
             "Acceptable metrics should score ≥75, got {}",
@@ -371,9 +667,234 @@ mod tests {
             ..no_warnings.clone()
         };
 
-        let score_clean = no_warnings.calculate_tdg_score();
-        let score_warnings = with_warnings.calculate_tdg_score();
+        let score_clean = no_warnings.calculate_tdg_score(&TdgWeights::default());
+        let score_warnings = with_warnings.calculate_tdg_score(&TdgWeights::default());
 
         assert!(score_clean > score_warnings, "Warnings should lower score");
     }
+
+    #[test]
+    fn test_custom_weights() {
+        let metrics = QualityMetrics {
+            line_coverage_pct: 95.0,
+            branch_coverage_pct: 95.0,
+            mutation_score_pct: 60.0,
+            avg_cyclomatic_complexity: 7.5,
+            max_cyclomatic_complexity: 10,
+            clippy_warnings: 0,
+            clippy_errors: 0,
+            doc_coverage_pct: 90.0,
+        };
+
+        let default_score = metrics.calculate_tdg_score(&TdgWeights::default());
+        let mutation_heavy = TdgWeights::new(0.30, 0.40, 0.15, 0.10, 0.05).expect("valid weights");
+        let custom_score = metrics.calculate_tdg_score(&mutation_heavy);
+
+        // Weak mutation score costs more when mutation is weighted higher
+        assert!(custom_score < default_score);
+        // 95*0.30 + 60*0.40 + 50*0.15 + 100*0.10 + 90*0.05 = 74.5
+        assert!((custom_score - 74.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_weights_must_sum_to_one() {
+        assert!(TdgWeights::new(0.40, 0.30, 0.15, 0.10, 0.05).is_ok());
+        assert!(TdgWeights::new(0.50, 0.30, 0.15, 0.10, 0.05).is_err());
+        assert!(TdgWeights::new(0.40, 0.30, 0.10, 0.10, 0.0).is_err());
+        assert!(TdgWeights::new(1.20, -0.20, 0.0, 0.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_doc_coverage_affects_score() {
+        let documented = QualityMetrics {
+            line_coverage_pct: 95.0,
+            branch_coverage_pct: 90.0,
+            mutation_score_pct: 85.0,
+            avg_cyclomatic_complexity: 8.0,
+            max_cyclomatic_complexity: 12,
+            clippy_warnings: 0,
+            clippy_errors: 0,
+            doc_coverage_pct: 100.0,
+        };
+        let undocumented = QualityMetrics {
+            doc_coverage_pct: 40.0,
+            ..documented.clone()
+        };
+
+        let weights = TdgWeights::default();
+        let diff =
+            documented.calculate_tdg_score(&weights) - undocumented.calculate_tdg_score(&weights);
+        // 60 points of doc coverage at 5% weight
+        assert!((diff - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_from_tarpaulin_json() {
+        let metrics =
+            QualityMetrics::from_tarpaulin_json(SAMPLE_TARPAULIN_JSON).expect("valid report");
+        assert!((metrics.line_coverage_pct - 75.0).abs() < 1e-9);
+        // No branch traces: falls back to line coverage
+        assert!((metrics.branch_coverage_pct - 75.0).abs() < 1e-9);
+        assert_eq!(metrics.mutation_score_pct, 0.0);
+
+        let with_branches = r#"{"files": [{
+            "traces": [
+                {"stats": {"Line": 1}},
+                {"stats": {"Branch": {"been_true": true, "been_false": false}}}
+            ],
+            "covered": 9, "coverable": 10
+        }]}"#;
+        let metrics = QualityMetrics::from_tarpaulin_json(with_branches).expect("valid report");
+        assert!((metrics.line_coverage_pct - 90.0).abs() < 1e-9);
+        assert!((metrics.branch_coverage_pct - 50.0).abs() < 1e-9);
+
+        assert!(QualityMetrics::from_tarpaulin_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_max_complexity_penalty() {
+        let uniform = QualityMetrics {
+            line_coverage_pct: 95.0,
+            branch_coverage_pct: 90.0,
+            mutation_score_pct: 85.0,
+            avg_cyclomatic_complexity: 6.0,
+            max_cyclomatic_complexity: 12,
+            clippy_warnings: 0,
+            clippy_errors: 0,
+            doc_coverage_pct: 95.0,
+        };
+        let one_monster = QualityMetrics {
+            max_cyclomatic_complexity: 40,
+            ..uniform.clone()
+        };
+
+        // At the ceiling there is no penalty
+        assert!((uniform.complexity_score() - 60.0).abs() < 1e-9);
+        // 25 over the ceiling * 2 points = 50 points lost
+        assert_eq!(one_monster.complexity_score(), 10.0);
+
+        let weights = TdgWeights::default();
+        assert!(
+            one_monster.calculate_tdg_score(&weights) < uniform.calculate_tdg_score(&weights),
+            "A single monstrous function should lower the score"
+        );
+    }
+
+    #[test]
+    fn test_ratchet() {
+        let metrics = QualityMetrics {
+            line_coverage_pct: 95.0,
+            branch_coverage_pct: 90.0,
+            mutation_score_pct: 85.0,
+            avg_cyclomatic_complexity: 8.0,
+            max_cyclomatic_complexity: 12,
+            clippy_warnings: 0,
+            clippy_errors: 0,
+            doc_coverage_pct: 95.0,
+        };
+        let score = metrics.calculate_tdg_score(&TdgWeights::default());
+
+        assert!(metrics.check_ratchet(score, 0.0).is_ok());
+        assert!(metrics.check_ratchet(score - 5.0, 0.0).is_ok());
+        assert!(metrics.check_ratchet(score + 1.0, 0.0).is_err());
+        // Within tolerance
+        assert!(metrics.check_ratchet(score + 1.0, 2.0).is_ok());
+
+        let regressed = QualityMetrics {
+            clippy_warnings: 3,
+            ..metrics.clone()
+        };
+        assert!(regressed.check_ratchet(score, 0.1).is_err());
+    }
+
+    #[test]
+    fn test_baseline_roundtrip() {
+        let path = std::env::temp_dir().join(format!("ch05-baseline-{}", std::process::id()));
+        save_baseline(&path, 87.25).expect("save");
+        assert_eq!(load_baseline(&path).expect("load"), 87.25);
+
+        fs::write(&path, "not a number").expect("write");
+        assert!(load_baseline(&path).is_err());
+        fs::remove_file(&path).expect("cleanup");
+    }
+
+    #[test]
+    fn test_tdg_history_trend() {
+        let mut improving = TdgHistory::default();
+        for (i, score) in [70.0, 74.5, 80.0, 86.0].iter().enumerate() {
+            improving.push(format!("sprint-{}", i + 1), *score);
+        }
+        assert_eq!(improving.trend(), Trend::Improving);
+
+        let mut regressing = TdgHistory::default();
+        for (i, score) in [92.0, 90.0, 85.5, 81.0].iter().enumerate() {
+            regressing.push(format!("sprint-{}", i + 1), *score);
+        }
+        assert_eq!(regressing.trend(), Trend::Regressing);
+
+        let mut flat = TdgHistory::default();
+        flat.push("a", 88.0);
+        flat.push("b", 88.0);
+        assert_eq!(flat.trend(), Trend::Flat);
+        assert_eq!(TdgHistory::default().trend(), Trend::Flat);
+    }
+
+    #[test]
+    fn test_tdg_history_csv() {
+        let mut history = TdgHistory::default();
+        history.push("abc123", 84.31);
+        history.push("def456", 90.0);
+        assert_eq!(history.to_csv(), "label,score\nabc123,84.3\ndef456,90.0\n");
+    }
+
+    #[test]
+    fn test_json_export() {
+        let metrics = QualityMetrics {
+            line_coverage_pct: 95.5,
+            branch_coverage_pct: 93.2,
+            mutation_score_pct: 82.0,
+            avg_cyclomatic_complexity: 8.3,
+            max_cyclomatic_complexity: 12,
+            clippy_warnings: 0,
+            clippy_errors: 0,
+            doc_coverage_pct: 98.0,
+        };
+        let weights = TdgWeights::default();
+        let json = metrics.to_json(&weights).expect("serializable");
+        let value: serde_json::Value = serde_json::from_str(&json).expect("valid JSON");
+
+        let total = value["breakdown"]["total"].as_f64().expect("total");
+        assert_eq!(total, metrics.calculate_tdg_score(&weights));
+        assert_eq!(value["breakdown"]["grade"], "B+");
+        assert_eq!(value["metrics"]["max_cyclomatic_complexity"], 12);
+
+        let b = metrics.breakdown(&weights);
+        let sum = b.coverage + b.mutation + b.complexity + b.quality + b.documentation;
+        assert!((sum - b.total).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_branch_share() {
+        let metrics = QualityMetrics {
+            line_coverage_pct: 96.0,
+            branch_coverage_pct: 80.0,
+            mutation_score_pct: 85.0,
+            avg_cyclomatic_complexity: 8.0,
+            max_cyclomatic_complexity: 12,
+            clippy_warnings: 0,
+            clippy_errors: 0,
+            doc_coverage_pct: 95.0,
+        };
+        let even = TdgWeights::default();
+        let branch_heavy = even.with_branch_share(0.75).expect("valid share");
+
+        assert_eq!(metrics.coverage_score(&even), 88.0);
+        assert_eq!(metrics.coverage_score(&branch_heavy), 84.0);
+        // Weaker branch coverage costs more when it is weighted higher
+        let diff = metrics.calculate_tdg_score(&even) - metrics.calculate_tdg_score(&branch_heavy);
+        assert!((diff - 4.0 * 0.40).abs() < 1e-9);
+
+        assert!(even.with_branch_share(1.5).is_err());
+        assert!(even.with_branch_share(-0.1).is_err());
+    }
 }
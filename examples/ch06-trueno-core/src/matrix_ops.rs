@@ -13,6 +13,239 @@
 use anyhow::Result;
 use trueno::Matrix;
 
+/// Row and column access without hand-written `row * cols + col` arithmetic
+trait MatrixAccess {
+    /// Row `i` as a slice; panics if `i >= rows()`
+    fn row(&self, i: usize) -> &[f32];
+    /// Copy of column `j`; panics if `j >= cols()`
+    fn col(&self, j: usize) -> Vec<f32>;
+}
+
+impl MatrixAccess for Matrix<f32> {
+    fn row(&self, i: usize) -> &[f32] {
+        assert!(
+            i < self.rows(),
+            "row {} out of range for {} rows",
+            i,
+            self.rows()
+        );
+        let cols = self.cols();
+        &self.as_slice()[i * cols..(i + 1) * cols]
+    }
+
+    fn col(&self, j: usize) -> Vec<f32> {
+        assert!(
+            j < self.cols(),
+            "column {} out of range for {} columns",
+            j,
+            self.cols()
+        );
+        self.as_slice()
+            .iter()
+            .skip(j)
+            .step_by(self.cols())
+            .copied()
+            .collect()
+    }
+}
+
+/// New matrix with rows and columns swapped
+fn transpose(m: &Matrix<f32>) -> Matrix<f32> {
+    let (rows, cols) = (m.rows(), m.cols());
+    let slice = m.as_slice();
+    let data: Vec<f32> = (0..cols)
+        .flat_map(|col| (0..rows).map(move |row| slice[row * cols + col]))
+        .collect();
+    Matrix::from_vec(cols, rows, data).expect("transpose preserves element count")
+}
+
+/// Matrix product `a × b`; `a.cols()` must equal `b.rows()`
+fn matmul(a: &Matrix<f32>, b: &Matrix<f32>) -> Result<Matrix<f32>, String> {
+    if a.cols() != b.rows() {
+        return Err(format!(
+            "Dimension mismatch: {}x{} × {}x{}",
+            a.rows(),
+            a.cols(),
+            b.rows(),
+            b.cols()
+        ));
+    }
+
+    let (n, inner, m) = (a.rows(), a.cols(), b.cols());
+    let (a, b) = (a.as_slice(), b.as_slice());
+    let mut c = vec![0.0f32; n * m];
+    for i in 0..n {
+        for j in 0..m {
+            for k in 0..inner {
+                c[i * m + j] += a[i * inner + k] * b[k * m + j];
+            }
+        }
+    }
+    Ok(Matrix::from_vec(n, m, c).expect("product has n × m elements"))
+}
+
+/// Combine two same-shaped matrices element by element
+fn zip_with(
+    a: &Matrix<f32>,
+    b: &Matrix<f32>,
+    op: impl Fn(f32, f32) -> f32,
+) -> Result<Matrix<f32>, String> {
+    if (a.rows(), a.cols()) != (b.rows(), b.cols()) {
+        return Err(format!(
+            "Shape mismatch: {}x{} vs {}x{}",
+            a.rows(),
+            a.cols(),
+            b.rows(),
+            b.cols()
+        ));
+    }
+    let data: Vec<f32> = a
+        .as_slice()
+        .iter()
+        .zip(b.as_slice())
+        .map(|(&x, &y)| op(x, y))
+        .collect();
+    Ok(Matrix::from_vec(a.rows(), a.cols(), data).expect("shape unchanged"))
+}
+
+/// Element-wise sum `a + b`
+fn add(a: &Matrix<f32>, b: &Matrix<f32>) -> Result<Matrix<f32>, String> {
+    zip_with(a, b, |x, y| x + y)
+}
+
+/// Element-wise (Hadamard) product `a ⊙ b`
+fn hadamard(a: &Matrix<f32>, b: &Matrix<f32>) -> Result<Matrix<f32>, String> {
+    zip_with(a, b, |x, y| x * y)
+}
+
+/// Every element multiplied by `factor`
+fn scale(m: &Matrix<f32>, factor: f32) -> Matrix<f32> {
+    let data: Vec<f32> = m.as_slice().iter().map(|&x| x * factor).collect();
+    Matrix::from_vec(m.rows(), m.cols(), data).expect("shape unchanged")
+}
+
+/// ReLU activation `max(0, x)` applied to every element
+fn relu_matrix(m: &Matrix<f32>) -> Matrix<f32> {
+    let data: Vec<f32> = m.as_slice().iter().map(|&x| x.max(0.0)).collect();
+    Matrix::from_vec(m.rows(), m.cols(), data).expect("shape unchanged")
+}
+
+/// Softmax over each row independently
+///
+/// Subtracts the row maximum before exponentiating, so large logits do not
+/// overflow to infinity.
+fn softmax_rows(m: &Matrix<f32>) -> Matrix<f32> {
+    let data: Vec<f32> = m
+        .as_slice()
+        .chunks(m.cols().max(1))
+        .flat_map(|row| {
+            let max_val = row.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+            let exp: Vec<f32> = row.iter().map(|x| (x - max_val).exp()).collect();
+            let exp_sum: f32 = exp.iter().sum();
+            exp.into_iter().map(move |e| e / exp_sum)
+        })
+        .collect();
+    Matrix::from_vec(m.rows(), m.cols(), data).expect("shape unchanged")
+}
+
+fn require_square(m: &Matrix<f32>) -> Result<usize, String> {
+    if m.rows() != m.cols() {
+        return Err(format!(
+            "Matrix is {}x{}, expected square",
+            m.rows(),
+            m.cols()
+        ));
+    }
+    Ok(m.rows())
+}
+
+/// Sum of the diagonal of a square matrix
+fn trace(m: &Matrix<f32>) -> Result<f32, String> {
+    let n = require_square(m)?;
+    Ok((0..n).map(|i| m.as_slice()[i * n + i]).sum())
+}
+
+/// Determinant of a 2x2 matrix: `ad - bc`
+fn determinant_2x2(m: &Matrix<f32>) -> Result<f32, String> {
+    match m.as_slice() {
+        [a, b, c, d] if m.rows() == 2 => Ok(a * d - b * c),
+        _ => Err(format!("Matrix is {}x{}, expected 2x2", m.rows(), m.cols())),
+    }
+}
+
+/// Determinant of a small square matrix by cofactor expansion
+///
+/// O(n!), so only meant for the handful-of-rows matrices in these demos.
+fn determinant(m: &Matrix<f32>) -> Result<f32, String> {
+    fn expand(data: &[f32], n: usize) -> f32 {
+        match n {
+            0 => 1.0,
+            1 => data[0],
+            2 => data[0] * data[3] - data[1] * data[2],
+            _ => (0..n)
+                .map(|j| {
+                    // Minor: drop row 0 and column j
+                    let minor: Vec<f32> = (1..n)
+                        .flat_map(|r| {
+                            (0..n)
+                                .filter(move |&c| c != j)
+                                .map(move |c| data[r * n + c])
+                        })
+                        .collect();
+                    let sign = if j % 2 == 0 { 1.0 } else { -1.0 };
+                    sign * data[j] * expand(&minor, n - 1)
+                })
+                .sum(),
+        }
+    }
+
+    let n = require_square(m)?;
+    Ok(expand(m.as_slice(), n))
+}
+
+/// Sum of all elements with Kahan (compensated) summation
+///
+/// Carries the low-order bits lost by each addition into the next one, so
+/// adding many small values to a large running total does not drift.
+fn sum_kahan(m: &Matrix<f32>) -> f32 {
+    let mut sum = 0.0f32;
+    let mut c = 0.0f32;
+    for &x in m.as_slice() {
+        let y = x - c;
+        let t = sum + y;
+        c = (t - sum) - y;
+        sum = t;
+    }
+    sum
+}
+
+/// Dense layer output `y = Wx + b`
+///
+/// `x` needs one entry per column of `w` and `bias` one per row.
+fn matvec(w: &Matrix<f32>, x: &[f32], bias: &[f32]) -> Result<Vec<f32>, String> {
+    if x.len() != w.cols() {
+        return Err(format!(
+            "Input has {} elements, weights expect {}",
+            x.len(),
+            w.cols()
+        ));
+    }
+    if bias.len() != w.rows() {
+        return Err(format!(
+            "Bias has {} elements, weights have {} rows",
+            bias.len(),
+            w.rows()
+        ));
+    }
+
+    let cols = w.cols();
+    Ok(w.as_slice()
+        .chunks(cols)
+        .zip(bias)
+        .map(|(row, b)| row.iter().zip(x).map(|(w, x)| w * x).sum::<f32>() + b)
+        .collect())
+}
+
 /// Demonstrate matrix creation
 fn matrix_basics() {
     println!("📊 Matrix Basics");
@@ -26,8 +259,8 @@ fn matrix_basics() {
     println!("   Matrix (3x3):");
     for row in 0..3 {
         print!("   ");
-        for col in 0..3 {
-            print!("{:>4.1} ", m.as_slice()[row * 3 + col]);
+        for value in m.row(row) {
+            print!("{:>4.1} ", value);
         }
         println!();
     }
@@ -35,6 +268,13 @@ fn matrix_basics() {
     println!("   rows = {}", m.rows());
     println!("   cols = {}", m.cols());
     println!("   total elements = {}", m.as_slice().len());
+    println!("   row 1 = {:?}", m.row(1));
+    println!("   col 2 = {:?}", m.col(2));
+    println!("   trace = {}", trace(&m).expect("square matrix"));
+    println!(
+        "   det   = {} (rows are linearly dependent)",
+        determinant(&m).expect("square matrix")
+    );
     println!();
 }
 
@@ -50,24 +290,24 @@ fn matrix_transpose() {
     println!("   Original (2x3):");
     for row in 0..2 {
         print!("   ");
-        for col in 0..3 {
-            print!("{:>4.1} ", m.as_slice()[row * 3 + col]);
+        for value in m.row(row) {
+            print!("{:>4.1} ", value);
         }
         println!();
     }
     println!();
 
-    // Manual transpose for demonstration
-    let slice = m.as_slice();
-    let transposed: Vec<f32> = (0..3)
-        .flat_map(|col| (0..2).map(move |row| slice[row * 3 + col]))
-        .collect();
+    let transposed = transpose(&m);
 
-    println!("   Transposed (3x2):");
+    println!(
+        "   Transposed ({}x{}):",
+        transposed.rows(),
+        transposed.cols()
+    );
     for row in 0..3 {
         print!("   ");
-        for col in 0..2 {
-            print!("{:>4.1} ", transposed[row * 2 + col]);
+        for value in transposed.row(row) {
+            print!("{:>4.1} ", value);
         }
         println!();
     }
@@ -90,8 +330,8 @@ fn matrix_multiplication() {
     println!("   A (2x3):");
     for row in 0..2 {
         print!("   ");
-        for col in 0..3 {
-            print!("{:>4.1} ", a.as_slice()[row * 3 + col]);
+        for value in a.row(row) {
+            print!("{:>4.1} ", value);
         }
         println!();
     }
@@ -100,33 +340,38 @@ fn matrix_multiplication() {
     println!("   B (3x2):");
     for row in 0..3 {
         print!("   ");
-        for col in 0..2 {
-            print!("{:>4.1} ", b.as_slice()[row * 2 + col]);
+        for value in b.row(row) {
+            print!("{:>4.1} ", value);
         }
         println!();
     }
     println!();
 
-    // Manual matrix multiplication: C = A × B (2x3 × 3x2 = 2x2)
-    let mut c = [0.0f32; 4];
-    for i in 0..2 {
-        for j in 0..2 {
-            for k in 0..3 {
-                c[i * 2 + j] += a.as_slice()[i * 3 + k] * b.as_slice()[k * 2 + j];
-            }
-        }
-    }
+    // C = A × B (2x3 × 3x2 = 2x2)
+    let c = matmul(&a, &b).expect("inner dimensions match");
 
     println!("   C = A × B (2x2):");
     for row in 0..2 {
         print!("   ");
-        for col in 0..2 {
-            print!("{:>6.1} ", c[row * 2 + col]);
+        for value in c.row(row) {
+            print!("{:>6.1} ", value);
         }
         println!();
     }
     println!();
 
+    println!(
+        "   det(C) = 58×154 − 64×139 = {}",
+        determinant_2x2(&c).expect("C is 2x2")
+    );
+    println!();
+
+    // B × B is undefined: 3x2 × 3x2
+    if let Err(e) = matmul(&b, &b) {
+        println!("   B × B: {}", e);
+        println!();
+    }
+
     // Verify: C[0,0] = 1*7 + 2*9 + 3*11 = 7 + 18 + 33 = 58
     println!(
         "   Verification: C[0,0] = 1×7 + 2×9 + 3×11 = {}",
@@ -135,6 +380,35 @@ fn matrix_multiplication() {
     println!();
 }
 
+/// Demonstrate element-wise operations
+fn elementwise_operations() {
+    println!("➕ Element-wise Operations");
+    println!();
+
+    let a = Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0]).expect("valid matrix A");
+    let b = Matrix::from_vec(2, 2, vec![5.0, 6.0, 7.0, 8.0]).expect("valid matrix B");
+
+    println!("   A = {:?}", a.as_slice());
+    println!("   B = {:?}", b.as_slice());
+    println!();
+    println!(
+        "   A + B   = {:?}",
+        add(&a, &b).expect("same shape").as_slice()
+    );
+    println!(
+        "   A ⊙ B   = {:?}",
+        hadamard(&a, &b).expect("same shape").as_slice()
+    );
+    println!("   0.5 × A = {:?}", scale(&a, 0.5).as_slice());
+    println!();
+
+    let wide = Matrix::from_vec(1, 4, vec![1.0; 4]).expect("valid 1x4 matrix");
+    if let Err(e) = add(&a, &wide) {
+        println!("   A + (1x4): {}", e);
+        println!();
+    }
+}
+
 /// Demonstrate determinism in matrix operations
 fn matrix_determinism() {
     println!("🔁 Matrix Determinism Verification");
@@ -149,19 +423,11 @@ fn matrix_determinism() {
         let a = Matrix::from_vec(2, 2, a_data.clone()).expect("valid 2x2 matrix A");
         let b = Matrix::from_vec(2, 2, b_data.clone()).expect("valid 2x2 matrix B");
 
-        // Manual 2x2 matrix multiplication
-        let mut c = [0.0f32; 4];
-        for i in 0..2 {
-            for j in 0..2 {
-                for k in 0..2 {
-                    c[i * 2 + j] += a.as_slice()[i * 2 + k] * b.as_slice()[k * 2 + j];
-                }
-            }
-        }
+        let product = matmul(&a, &b).expect("2x2 × 2x2");
 
-        let trace = c[0] + c[3]; // sum of diagonal
-        results.push(trace);
-        println!("   Run {}: trace(A×B) = {:.10}", run, trace);
+        let tr = trace(&product).expect("square product");
+        results.push(tr);
+        println!("   Run {}: trace(A×B) = {:.10}", run, tr);
     }
 
     let first = results[0];
@@ -176,6 +442,43 @@ fn matrix_determinism() {
     println!();
 }
 
+/// Demonstrate summation drift and Kahan compensation
+fn compensated_summation() {
+    println!("🧾 Compensated Summation");
+    println!();
+
+    // One large value followed by many small ones: each small addition
+    // rounds against the large running total
+    let (rows, cols) = (1000, 100);
+    let mut data = vec![0.001f32; rows * cols];
+    data[0] = 10_000.0;
+    let m = Matrix::from_vec(rows, cols, data).expect("valid matrix");
+
+    let naive: f32 = m.as_slice().iter().sum();
+    let kahan = sum_kahan(&m);
+    let reference: f64 = m.as_slice().iter().map(|&x| f64::from(x)).sum();
+
+    println!(
+        "   {}x{} matrix: 10000 + {} × 0.001",
+        rows,
+        cols,
+        rows * cols - 1
+    );
+    println!();
+    println!("   f64 reference: {:.4}", reference);
+    println!(
+        "   Naive f32:     {:.4} (error {:.4})",
+        naive,
+        (f64::from(naive) - reference).abs()
+    );
+    println!(
+        "   Kahan f32:     {:.4} (error {:.4})",
+        kahan,
+        (f64::from(kahan) - reference).abs()
+    );
+    println!();
+}
+
 /// Demonstrate ML-relevant operations
 fn ml_operations() {
     println!("🧠 ML-Relevant Matrix Operations");
@@ -193,8 +496,8 @@ fn ml_operations() {
     println!("   W (2x3 weights):");
     for row in 0..2 {
         print!("   ");
-        for col in 0..3 {
-            print!("{:>5.2} ", w.as_slice()[row * 3 + col]);
+        for value in w.row(row) {
+            print!("{:>5.2} ", value);
         }
         println!();
     }
@@ -204,34 +507,24 @@ fn ml_operations() {
     println!("   b (bias): {:?}", bias);
     println!();
 
-    // Manual matrix-vector multiplication: y = Wx + b
-    let mut output = [0.0f32; 2];
-    for i in 0..2 {
-        for (j, &inp) in input.iter().enumerate() {
-            output[i] += w.as_slice()[i * 3 + j] * inp;
-        }
-        output[i] += bias[i];
-    }
+    let output = matvec(&w, &input, &bias).expect("layer dimensions match");
 
     println!("   y (output): {:?}", output);
     println!();
 
+    // Treat the output as a 1×n batch so the activations apply row-wise
+    let y = Matrix::from_vec(1, output.len(), output).expect("valid 1xn output");
+
     // ReLU activation: max(0, x)
-    let activated: Vec<f32> = output.iter().map(|&x| x.max(0.0)).collect();
-    println!("   ReLU(y): {:?}", activated);
+    let activated = relu_matrix(&y);
+    println!("   ReLU(y): {:?}", activated.as_slice());
     println!();
 
-    // Softmax (simplified for 2 outputs)
-    let max_val = output.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
-    let exp_sum: f32 = output.iter().map(|x| (x - max_val).exp()).sum();
-    let softmax: Vec<f32> = output
-        .iter()
-        .map(|x| (x - max_val).exp() / exp_sum)
-        .collect();
-    println!("   Softmax(y): {:?}", softmax);
+    let softmax = softmax_rows(&y);
+    println!("   Softmax(y): {:?}", softmax.as_slice());
     println!(
         "   Sum = {:.4} (should be 1.0)",
-        softmax.iter().sum::<f32>()
+        softmax.as_slice().iter().sum::<f32>()
     );
     println!();
 }
@@ -259,10 +552,18 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
+    elementwise_operations();
+    println!("{}", "─".repeat(70));
+    println!();
+
     matrix_determinism();
     println!("{}", "─".repeat(70));
     println!();
 
+    compensated_summation();
+    println!("{}", "─".repeat(70));
+    println!();
+
     ml_operations();
     println!("{}", "─".repeat(70));
     println!();
@@ -303,20 +604,64 @@ mod tests {
         assert_eq!(m.as_slice().len(), 6);
     }
 
+    #[test]
+    fn test_transpose_twice_is_identity() {
+        let data = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let m = Matrix::from_vec(2, 3, data.clone()).expect("valid 2x3 matrix");
+
+        let t = transpose(&m);
+        assert_eq!((t.rows(), t.cols()), (3, 2));
+        assert_eq!(t.as_slice(), &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+
+        let back = transpose(&t);
+        assert_eq!((back.rows(), back.cols()), (2, 3));
+        assert_eq!(back.as_slice(), data.as_slice());
+    }
+
+    #[test]
+    fn test_row_and_col_accessors() {
+        let m = Matrix::from_vec(3, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0])
+            .expect("valid 3x3 matrix");
+
+        assert_eq!(m.row(1), &[4.0, 5.0, 6.0]);
+        assert_eq!(m.col(2), vec![3.0, 6.0, 9.0]);
+        assert_eq!(m.row(0), &[1.0, 2.0, 3.0]);
+        assert_eq!(m.col(0), vec![1.0, 4.0, 7.0]);
+    }
+
+    #[test]
+    fn test_trace() {
+        let identity = Matrix::from_vec(3, 3, vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0])
+            .expect("valid 3x3 identity");
+        assert_eq!(trace(&identity), Ok(3.0));
+
+        let rect = Matrix::from_vec(2, 3, vec![0.0; 6]).expect("valid 2x3");
+        assert!(trace(&rect).is_err());
+    }
+
+    #[test]
+    fn test_determinant() {
+        let m = Matrix::from_vec(2, 2, vec![4.0, 7.0, 2.0, 6.0]).expect("valid 2x2");
+        assert_eq!(determinant_2x2(&m), Ok(10.0));
+        assert_eq!(determinant(&m), Ok(10.0));
+
+        let m3 = Matrix::from_vec(3, 3, vec![6.0, 1.0, 1.0, 4.0, -2.0, 5.0, 2.0, 8.0, 7.0])
+            .expect("valid 3x3");
+        assert_eq!(determinant(&m3), Ok(-306.0));
+        assert!(determinant_2x2(&m3).is_err());
+
+        let rect = Matrix::from_vec(2, 3, vec![0.0; 6]).expect("valid 2x3");
+        assert!(determinant(&rect).is_err());
+    }
+
     #[test]
     fn test_matrix_multiplication() {
         // A: 2x2, B: 2x2
         let a = Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0]).expect("valid matrix A");
         let b = Matrix::from_vec(2, 2, vec![5.0, 6.0, 7.0, 8.0]).expect("valid matrix B");
 
-        let mut c = [0.0f32; 4];
-        for i in 0..2 {
-            for j in 0..2 {
-                for k in 0..2 {
-                    c[i * 2 + j] += a.as_slice()[i * 2 + k] * b.as_slice()[k * 2 + j];
-                }
-            }
-        }
+        let product = matmul(&a, &b).expect("2x2 × 2x2");
+        let c = product.as_slice();
 
         // C[0,0] = 1*5 + 2*7 = 19
         // C[0,1] = 1*6 + 2*8 = 22
@@ -328,6 +673,69 @@ mod tests {
         assert!((c[3] - 50.0).abs() < 1e-6);
     }
 
+    #[test]
+    fn test_matmul_rectangular() {
+        let a = Matrix::from_vec(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).expect("valid 2x3");
+        let b = Matrix::from_vec(3, 2, vec![7.0, 8.0, 9.0, 10.0, 11.0, 12.0]).expect("valid 3x2");
+
+        let c = matmul(&a, &b).expect("2x3 × 3x2");
+        assert_eq!((c.rows(), c.cols()), (2, 2));
+        assert_eq!(c.as_slice(), &[58.0, 64.0, 139.0, 154.0]);
+    }
+
+    #[test]
+    fn test_matmul_dimension_mismatch() {
+        let a = Matrix::from_vec(2, 3, vec![1.0; 6]).expect("valid 2x3");
+
+        let err = matmul(&a, &a).expect_err("2x3 × 2x3 is undefined");
+        assert!(err.contains("2x3 × 2x3"), "{}", err);
+    }
+
+    #[test]
+    fn test_matvec_layer_output() {
+        let w = Matrix::from_vec(2, 3, vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6]).expect("valid 2x3");
+
+        let y = matvec(&w, &[1.0, 2.0, 3.0], &[0.1, 0.2]).expect("dimensions match");
+
+        // y0 = 0.1 + 0.4 + 0.9 + 0.1, y1 = 0.4 + 1.0 + 1.8 + 0.2
+        assert_eq!(y.len(), 2);
+        assert!((y[0] - 1.5).abs() < 1e-6);
+        assert!((y[1] - 3.4).abs() < 1e-6);
+
+        assert!(matvec(&w, &[1.0, 2.0], &[0.1, 0.2]).is_err());
+        assert!(matvec(&w, &[1.0, 2.0, 3.0], &[0.1]).is_err());
+    }
+
+    #[test]
+    fn test_elementwise_add() {
+        let a = Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0]).expect("valid matrix A");
+        let b = Matrix::from_vec(2, 2, vec![5.0, 6.0, 7.0, 8.0]).expect("valid matrix B");
+
+        let sum = add(&a, &b).expect("same shape");
+        assert_eq!(sum.as_slice(), &[6.0, 8.0, 10.0, 12.0]);
+
+        let wide = Matrix::from_vec(1, 4, vec![0.0; 4]).expect("valid 1x4");
+        assert!(add(&a, &wide).is_err());
+    }
+
+    #[test]
+    fn test_elementwise_hadamard() {
+        let a = Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0]).expect("valid matrix A");
+        let b = Matrix::from_vec(2, 2, vec![5.0, 6.0, 7.0, 8.0]).expect("valid matrix B");
+
+        let product = hadamard(&a, &b).expect("same shape");
+        assert_eq!(product.as_slice(), &[5.0, 12.0, 21.0, 32.0]);
+    }
+
+    #[test]
+    fn test_scale() {
+        let a = Matrix::from_vec(2, 2, vec![1.0, -2.0, 3.0, 4.0]).expect("valid matrix A");
+
+        let scaled = scale(&a, 2.5);
+        assert_eq!((scaled.rows(), scaled.cols()), (2, 2));
+        assert_eq!(scaled.as_slice(), &[2.5, -5.0, 7.5, 10.0]);
+    }
+
     #[test]
     fn test_matrix_determinism() {
         let data = vec![1.0, 2.0, 3.0, 4.0];
@@ -346,6 +754,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sum_kahan_matches_f64_reference() {
+        let mut data = vec![0.001f32; 1000 * 100];
+        data[0] = 10_000.0;
+        let m = Matrix::from_vec(1000, 100, data).expect("valid matrix");
+
+        let reference: f64 = m.as_slice().iter().map(|&x| f64::from(x)).sum();
+        let naive: f32 = m.as_slice().iter().sum();
+        let kahan = sum_kahan(&m);
+
+        let naive_error = (f64::from(naive) - reference).abs();
+        let kahan_error = (f64::from(kahan) - reference).abs();
+
+        // Within one f32 ulp at this magnitude (~0.001)
+        assert!(kahan_error < 1e-3, "kahan error {}", kahan_error);
+        assert!(naive_error > 1.0, "naive error {}", naive_error);
+        assert_ne!(naive, kahan);
+    }
+
     #[test]
     fn test_relu_activation() {
         let values: Vec<f32> = vec![-1.0, 0.0, 1.0, -0.5, 2.0];
@@ -353,6 +780,36 @@ mod tests {
         assert_eq!(relu, vec![0.0, 0.0, 1.0, 0.0, 2.0]);
     }
 
+    #[test]
+    fn test_relu_matrix_zeroes_negatives() {
+        let m = Matrix::from_vec(2, 3, vec![-1.0, 0.0, 1.0, -0.5, 2.0, -3.0]).expect("valid 2x3");
+
+        let relu = relu_matrix(&m);
+        assert_eq!((relu.rows(), relu.cols()), (2, 3));
+        assert_eq!(relu.as_slice(), &[0.0, 0.0, 1.0, 0.0, 2.0, 0.0]);
+    }
+
+    #[test]
+    fn test_softmax_rows_each_sum_to_one() {
+        // Second row would overflow exp() without the max shift
+        let m = Matrix::from_vec(
+            3,
+            3,
+            vec![1.0, 2.0, 3.0, 1000.0, 1001.0, 1002.0, 0.0, 0.0, 0.0],
+        )
+        .expect("valid 3x3");
+
+        let softmax = softmax_rows(&m);
+        for row in softmax.as_slice().chunks(3) {
+            let sum: f32 = row.iter().sum();
+            assert!((sum - 1.0).abs() < 1e-6, "row {:?} sums to {}", row, sum);
+        }
+        // Rows are independent: the shifted row matches the small one
+        let s = softmax.as_slice();
+        assert!((s[0] - s[3]).abs() < 1e-6);
+        assert!((s[6] - 1.0 / 3.0).abs() < 1e-6);
+    }
+
     #[test]
     fn test_softmax_sums_to_one() {
         let logits = [1.0, 2.0, 3.0];
@@ -11,13 +11,16 @@
 /// - GPU slowdowns are real
 /// - Transfer overhead is unavoidable
 /// - CPU SIMD is underrated
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::ops::Range;
 use std::time::Instant;
-use trueno::Vector;
+use trueno::{Matrix, Vector};
 
 /// Benchmark result with detailed breakdown
 #[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 struct BenchResult {
     name: String,
     operation: String,
@@ -26,20 +29,292 @@ struct BenchResult {
     gpu_time_ms: f64,
     speedup: f64,
     winner: String,
+    /// Roofline classification of the operation at this size
+    bound: Bound,
 }
 
-/// Simulate GPU vector dot product
-fn simulated_gpu_dot(size: usize) -> f64 {
-    // Transfer overhead (two vectors to GPU)
-    let transfer_time = 0.05; // 50μs base + proportional to size
+/// Peak FLOPs per byte of memory traffic the machine can sustain
+///
+/// ~5 TFLOPS against ~500 GB/s of memory bandwidth. Operations doing fewer
+/// FLOPs per byte than this stall on memory, whatever the compute peak.
+const MACHINE_BALANCE: f64 = 10.0;
+
+/// Which roof limits an operation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+enum Bound {
+    #[serde(rename = "memory-bound")]
+    Memory,
+    #[serde(rename = "compute-bound")]
+    Compute,
+}
+
+impl std::fmt::Display for Bound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Bound::Memory => write!(f, "memory-bound"),
+            Bound::Compute => write!(f, "compute-bound"),
+        }
+    }
+}
+
+/// Classify an operation by its arithmetic intensity (FLOPs per byte moved)
+fn roofline_bound(flops: f64, bytes: usize, machine_balance: f64) -> Bound {
+    if flops / bytes as f64 >= machine_balance {
+        Bound::Compute
+    } else {
+        Bound::Memory
+    }
+}
+
+/// Work and data movement of one operation on f32 data
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct OpCost {
+    flops: f64,
+    bytes: usize,
+}
+
+impl OpCost {
+    /// Two vectors in, one scalar out; one multiply-add per element
+    fn dot(n: usize) -> Self {
+        Self {
+            flops: 2.0 * n as f64,
+            bytes: (2 * n + 1) * F32_BYTES,
+        }
+    }
+
+    /// One vector in, one out; one op per element
+    fn elementwise(n: usize) -> Self {
+        Self {
+            flops: n as f64,
+            bytes: 2 * n * F32_BYTES,
+        }
+    }
+
+    /// Two n×n matrices in, one out; 2n³ FLOPs
+    fn matmul(n: usize) -> Self {
+        Self {
+            flops: 2.0 * (n as f64).powi(3),
+            bytes: 3 * n * n * F32_BYTES,
+        }
+    }
+
+    fn bound(&self) -> Bound {
+        roofline_bound(self.flops, self.bytes, MACHINE_BALANCE)
+    }
+}
+
+/// Untimed runs before measuring, so caches and branch predictors are warm
+///
+/// The first touch of freshly allocated data pays for page faults and cache
+/// misses that steady-state use never sees; those runs are discarded.
+const WARMUP_ITERS: usize = 2;
+
+/// Measured runs per benchmark point
+const BENCH_ITERS: usize = 20;
+
+/// Summary statistics over repeated timings, in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BenchStat {
+    min_ms: f64,
+    median_ms: f64,
+    mean_ms: f64,
+    max_ms: f64,
+    /// Population standard deviation
+    stddev_ms: f64,
+}
+
+/// Run `f` repeatedly and summarize the times it reports
+///
+/// `f` performs one run and returns its duration in milliseconds, so the
+/// same helper works for measured CPU code and modeled GPU costs.
+/// `WARMUP_ITERS` runs are discarded before `iters` runs (at least one)
+/// are recorded.
+fn bench_repeated(f: impl FnMut() -> f64, iters: usize) -> BenchStat {
+    bench_with_warmup(f, WARMUP_ITERS, iters)
+}
+
+/// [`bench_repeated`] with an explicit number of discarded warmup runs
+fn bench_with_warmup(f: impl FnMut() -> f64, warmup: usize, iters: usize) -> BenchStat {
+    let mut samples = collect_samples(f, warmup, iters);
+    samples.sort_by(|a, b| a.total_cmp(b));
+
+    let n = samples.len();
+    let mean_ms = samples.iter().sum::<f64>() / n as f64;
+    let variance = samples.iter().map(|s| (s - mean_ms).powi(2)).sum::<f64>() / n as f64;
+
+    BenchStat {
+        min_ms: samples[0],
+        median_ms: median(&samples).expect("at least one sample is recorded"),
+        mean_ms,
+        max_ms: samples[n - 1],
+        stddev_ms: variance.sqrt(),
+    }
+}
+
+/// Raw timings of `iters` runs (at least one) after `warmup` discarded runs
+fn collect_samples(mut f: impl FnMut() -> f64, warmup: usize, iters: usize) -> Vec<f64> {
+    for _ in 0..warmup {
+        f();
+    }
+    (0..iters.max(1)).map(|_| f()).collect()
+}
+
+/// Middle sample, or the mean of the two middle samples for even counts;
+/// `None` when there are no samples
+fn median(samples: &[f64]) -> Option<f64> {
+    if samples.is_empty() {
+        return None;
+    }
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let n = sorted.len();
+    Some((sorted[(n - 1) / 2] + sorted[n / 2]) / 2.0)
+}
+
+/// Bootstrap resamples drawn by [`speedup_ci`]
+const BOOTSTRAP_RESAMPLES: usize = 1000;
+
+/// Fixed seed so confidence intervals are reproducible run to run
+const BOOTSTRAP_SEED: u64 = 42;
+
+/// Speedup of GPU over CPU with a confidence interval
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SpeedupCi {
+    estimate: f64,
+    lower: f64,
+    upper: f64,
+}
+
+impl SpeedupCi {
+    /// Whether the interval rules out "no difference" (1.0x)
+    fn is_conclusive(&self) -> bool {
+        self.lower > 1.0 || self.upper < 1.0
+    }
+}
+
+impl std::fmt::Display for SpeedupCi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.2}x ({:.2}–{:.2}x)",
+            self.estimate, self.lower, self.upper
+        )
+    }
+}
+
+/// Median CPU time over median GPU time, with a percentile bootstrap interval
+///
+/// Both sample sets are resampled with replacement `BOOTSTRAP_RESAMPLES`
+/// times using a seeded generator, so the interval is deterministic.
+/// Returns `None` if either sample set is empty.
+fn speedup_ci(cpu_samples: &[f64], gpu_samples: &[f64], confidence: f64) -> Option<SpeedupCi> {
+    let estimate = median(cpu_samples)? / median(gpu_samples)?;
+    let mut state = BOOTSTRAP_SEED;
+    let mut resample = |samples: &[f64]| -> Vec<f64> {
+        (0..samples.len())
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                samples[(state >> 33) as usize % samples.len()]
+            })
+            .collect()
+    };
+
+    let mut ratios: Vec<f64> = (0..BOOTSTRAP_RESAMPLES)
+        .map(|_| Some(median(&resample(cpu_samples))? / median(&resample(gpu_samples))?))
+        .collect::<Option<_>>()?;
+    ratios.sort_by(|a, b| a.total_cmp(b));
+
+    let tail = (1.0 - confidence.clamp(0.0, 1.0)) / 2.0;
+    let last = (ratios.len() - 1) as f64;
+    Some(SpeedupCi {
+        estimate,
+        lower: ratios[(tail * last).round() as usize],
+        upper: ratios[((1.0 - tail) * last).round() as usize],
+    })
+}
+
+/// Bytes per element moved to or from the GPU
+const F32_BYTES: usize = std::mem::size_of::<f32>();
+
+/// Simulated GPU cost model; every estimate is in milliseconds
+///
+/// Time = PCIe transfer of every byte moved + one kernel launch + compute
+/// at peak throughput. Transfer scales with data size, so memory-bound ops
+/// never amortize it the way compute-heavy ones do.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GpuModel {
+    /// Host↔device bandwidth in GB/s
+    pcie_gbps: f64,
+    /// Fixed cost of launching one kernel, in microseconds
+    kernel_launch_us: f64,
+    /// Sustained compute throughput in GFLOPS
+    gflops: f64,
+}
+
+impl Default for GpuModel {
+    /// Mid-range discrete GPU on PCIe 4.0 x16
+    fn default() -> Self {
+        Self {
+            pcie_gbps: 16.0,
+            kernel_launch_us: 20.0,
+            gflops: 5000.0,
+        }
+    }
+}
+
+impl GpuModel {
+    fn transfer_ms(&self, bytes: usize) -> f64 {
+        bytes as f64 / (self.pcie_gbps * 1e9) * 1000.0
+    }
+
+    fn launch_ms(&self) -> f64 {
+        self.kernel_launch_us / 1000.0
+    }
+
+    fn compute_ms(&self, flops: f64) -> f64 {
+        flops / (self.gflops * 1e9) * 1000.0
+    }
 
-    // Kernel overhead
-    let kernel_overhead = 0.02; // 20μs
+    /// Transfer every byte, launch one kernel, compute at peak
+    fn estimate_ms(&self, cost: OpCost) -> f64 {
+        self.transfer_ms(cost.bytes) + self.launch_ms() + self.compute_ms(cost.flops)
+    }
+
+    fn dot_ms(&self, n: usize) -> f64 {
+        self.estimate_ms(OpCost::dot(n))
+    }
 
-    // GPU compute: O(n) reduction, but with high parallelism
-    let compute_time = (size as f64) / 1e9; // ~1 GFLOP
+    fn elementwise_ms(&self, n: usize) -> f64 {
+        self.estimate_ms(OpCost::elementwise(n))
+    }
 
-    transfer_time + kernel_overhead + compute_time
+    fn matmul_ms(&self, n: usize) -> f64 {
+        self.estimate_ms(OpCost::matmul(n))
+    }
+}
+
+/// CPU naive matrix multiplication benchmark
+fn cpu_matmul(a: &Matrix<f32>, b: &Matrix<f32>) -> (f64, Vec<f32>) {
+    let (n, k_dim, m) = (a.rows(), a.cols(), b.cols());
+    let (a, b) = (a.as_slice(), b.as_slice());
+
+    let start = Instant::now();
+
+    let mut result = vec![0.0f32; n * m];
+    for i in 0..n {
+        for j in 0..m {
+            let mut sum = 0.0f32;
+            for k in 0..k_dim {
+                sum += a[i * k_dim + k] * b[k * m + j];
+            }
+            result[i * m + j] = sum;
+        }
+    }
+
+    let elapsed = start.elapsed().as_secs_f64() * 1000.0;
+    (elapsed, result)
 }
 
 /// CPU dot product benchmark
@@ -58,18 +333,23 @@ fn cpu_dot_product(v1: &Vector<f32>, v2: &Vector<f32>) -> (f64, f32) {
 }
 
 /// Benchmark dot product across sizes
-fn dot_product_benchmark() -> Vec<BenchResult> {
+fn dot_product_benchmark(gpu_model: &GpuModel) -> Vec<BenchResult> {
     println!("🔢 Dot Product Benchmark");
     println!();
+    println!(
+        "   Median of {} runs after {} warmup runs",
+        BENCH_ITERS, WARMUP_ITERS
+    );
+    println!();
 
     let sizes = [100, 1000, 10_000, 100_000, 1_000_000];
     let mut results = Vec::new();
 
     println!(
-        "   {:>12} │ {:>10} │ {:>10} │ {:>8} │ Winner",
+        "   {:>12} │ {:>10} │ {:>10} │ {:>8} │ Winner │ Bound",
         "Elements", "CPU (ms)", "GPU (ms)", "Speedup"
     );
-    println!("   ─────────────┼────────────┼────────────┼──────────┼────────");
+    println!("   ─────────────┼────────────┼────────────┼──────────┼────────┼──────────────");
 
     for &size in &sizes {
         let data1: Vec<f32> = (0..size).map(|i| (i % 100) as f32 / 100.0).collect();
@@ -78,15 +358,18 @@ fn dot_product_benchmark() -> Vec<BenchResult> {
         let v1 = Vector::from_slice(&data1);
         let v2 = Vector::from_slice(&data2);
 
-        let (cpu_time, _) = cpu_dot_product(&v1, &v2);
-        let gpu_time = simulated_gpu_dot(size);
+        let cpu = bench_repeated(|| cpu_dot_product(&v1, &v2).0, BENCH_ITERS);
+        let gpu = bench_repeated(|| gpu_model.dot_ms(size), BENCH_ITERS);
+        let bound = OpCost::dot(size).bound();
+        let cpu_time = cpu.median_ms;
+        let gpu_time = gpu.median_ms;
 
         let speedup = cpu_time / gpu_time;
         let winner = if speedup > 1.0 { "GPU" } else { "CPU" };
 
         println!(
-            "   {:>12} │ {:>10.4} │ {:>10.4} │ {:>7.2}x │ {}",
-            size, cpu_time, gpu_time, speedup, winner
+            "   {:>12} │ {:>10.4} │ {:>10.4} │ {:>7.2}x │ {:<6} │ {}",
+            size, cpu_time, gpu_time, speedup, winner, bound
         );
 
         results.push(BenchResult {
@@ -97,6 +380,7 @@ fn dot_product_benchmark() -> Vec<BenchResult> {
             gpu_time_ms: gpu_time,
             speedup,
             winner: winner.to_string(),
+            bound,
         });
     }
 
@@ -105,37 +389,50 @@ fn dot_product_benchmark() -> Vec<BenchResult> {
 }
 
 /// Element-wise operations benchmark
-fn elementwise_benchmark() -> Vec<BenchResult> {
+fn elementwise_benchmark(gpu_model: &GpuModel) -> Vec<BenchResult> {
     println!("➕ Element-wise Operations Benchmark");
     println!();
+    println!(
+        "   Median of {} runs after {} warmup runs",
+        BENCH_ITERS, WARMUP_ITERS
+    );
+    println!();
 
     let sizes = [1000, 10_000, 100_000, 1_000_000];
     let mut results = Vec::new();
 
     println!(
-        "   {:>12} │ {:>10} │ {:>10} │ {:>8} │ Winner",
+        "   {:>12} │ {:>10} │ {:>10} │ {:>8} │ Winner │ Bound",
         "Elements", "CPU (ms)", "GPU (ms)", "Speedup"
     );
-    println!("   ─────────────┼────────────┼────────────┼──────────┼────────");
+    println!("   ─────────────┼────────────┼────────────┼──────────┼────────┼──────────────");
 
     for &size in &sizes {
         let data: Vec<f32> = (0..size).map(|i| (i % 100) as f32).collect();
         let v = Vector::from_slice(&data);
 
         // CPU element-wise ReLU
-        let start = Instant::now();
-        let _relu: Vec<f32> = v.as_slice().iter().map(|&x| x.max(0.0)).collect();
-        let cpu_time = start.elapsed().as_secs_f64() * 1000.0;
+        let cpu = bench_repeated(
+            || {
+                let start = Instant::now();
+                let _relu: Vec<f32> = v.as_slice().iter().map(|&x| x.max(0.0)).collect();
+                start.elapsed().as_secs_f64() * 1000.0
+            },
+            BENCH_ITERS,
+        );
+        let cpu_time = cpu.median_ms;
 
         // Simulated GPU (parallelizes well)
-        let gpu_time = 0.05 + (size as f64) / 1e10; // Very fast on GPU
+        let gpu = bench_repeated(|| gpu_model.elementwise_ms(size), BENCH_ITERS);
+        let bound = OpCost::elementwise(size).bound();
+        let gpu_time = gpu.median_ms;
 
         let speedup = cpu_time / gpu_time;
         let winner = if speedup > 1.0 { "GPU" } else { "CPU" };
 
         println!(
-            "   {:>12} │ {:>10.4} │ {:>10.4} │ {:>7.2}x │ {}",
-            size, cpu_time, gpu_time, speedup, winner
+            "   {:>12} │ {:>10.4} │ {:>10.4} │ {:>7.2}x │ {:<6} │ {}",
+            size, cpu_time, gpu_time, speedup, winner, bound
         );
 
         results.push(BenchResult {
@@ -146,6 +443,66 @@ fn elementwise_benchmark() -> Vec<BenchResult> {
             gpu_time_ms: gpu_time,
             speedup,
             winner: winner.to_string(),
+            bound,
+        });
+    }
+
+    println!();
+    results
+}
+
+/// Matrix multiplication benchmark across sizes
+fn matmul_benchmark(gpu_model: &GpuModel) -> Vec<BenchResult> {
+    println!("✖️  Matrix Multiplication Benchmark");
+    println!();
+    println!(
+        "   Median of {} runs after {} warmup runs",
+        BENCH_ITERS, WARMUP_ITERS
+    );
+    println!();
+
+    let sizes = [8, 16, 32, 64, 128];
+    let mut results = Vec::new();
+
+    println!(
+        "   {:>12} │ {:>10} │ {:>10} │ {:>8} │ Winner │ Bound",
+        "Matrix", "CPU (ms)", "GPU (ms)", "Speedup"
+    );
+    println!("   ─────────────┼────────────┼────────────┼──────────┼────────┼──────────────");
+
+    for &n in &sizes {
+        let data: Vec<f32> = (0..n * n).map(|i| (i % 100) as f32 / 100.0).collect();
+        let a = Matrix::from_vec(n, n, data.clone()).expect("valid matrix A");
+        let b = Matrix::from_vec(n, n, data).expect("valid matrix B");
+
+        let cpu = bench_repeated(|| cpu_matmul(&a, &b).0, BENCH_ITERS);
+        let gpu = bench_repeated(|| gpu_model.matmul_ms(n), BENCH_ITERS);
+        let bound = OpCost::matmul(n).bound();
+        let cpu_time = cpu.median_ms;
+        let gpu_time = gpu.median_ms;
+
+        let speedup = cpu_time / gpu_time;
+        let winner = if speedup > 1.0 { "GPU" } else { "CPU" };
+
+        println!(
+            "   {:>12} │ {:>10.4} │ {:>10.4} │ {:>7.2}x │ {:<6} │ {}",
+            format!("{}×{}", n, n),
+            cpu_time,
+            gpu_time,
+            speedup,
+            winner,
+            bound
+        );
+
+        results.push(BenchResult {
+            name: "Matmul".to_string(),
+            operation: format!("{}×{}", n, n),
+            elements: n * n,
+            cpu_time_ms: cpu_time,
+            gpu_time_ms: gpu_time,
+            speedup,
+            winner: winner.to_string(),
+            bound,
         });
     }
 
@@ -153,6 +510,143 @@ fn elementwise_benchmark() -> Vec<BenchResult> {
     results
 }
 
+/// Smallest element count from which the GPU wins every larger size of `name`
+fn gpu_crossover(results: &[BenchResult], name: &str) -> Option<usize> {
+    let mut runs: Vec<&BenchResult> = results.iter().filter(|r| r.name == name).collect();
+    runs.sort_by_key(|r| r.elements);
+
+    runs.iter()
+        .rev()
+        .take_while(|r| r.winner == "GPU")
+        .last()
+        .map(|r| r.elements)
+}
+
+/// First element count in `range` at which the GPU is faster than the CPU
+///
+/// Binary search, so it assumes that once the GPU wins it keeps winning at
+/// every larger size. Returns `None` if the GPU does not win even at the top
+/// of the range.
+fn find_crossover(
+    cpu: impl Fn(usize) -> f64,
+    gpu: impl Fn(usize) -> f64,
+    range: Range<usize>,
+) -> Option<usize> {
+    let gpu_wins = |n: usize| gpu(n) < cpu(n);
+
+    let (mut lo, mut hi) = (range.start, range.end.checked_sub(1)?);
+    if lo > hi || !gpu_wins(hi) {
+        return None;
+    }
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if gpu_wins(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    Some(lo)
+}
+
+/// Pinpoint the dot product crossover instead of reading it off the table
+fn crossover_search(gpu_model: &GpuModel) {
+    println!("🎯 Dot Product Crossover Search");
+    println!();
+
+    let cpu_dot_time = |size: usize| {
+        let data: Vec<f32> = (0..size).map(|i| (i % 100) as f32 / 100.0).collect();
+        let v = Vector::from_slice(&data);
+        bench_repeated(|| cpu_dot_product(&v, &v).0, BENCH_ITERS).median_ms
+    };
+
+    let range = 1..1_000_001;
+    match find_crossover(cpu_dot_time, |n| gpu_model.dot_ms(n), range.clone()) {
+        Some(n) => println!("   GPU wins above {} elements", n - 1),
+        None => println!(
+            "   CPU wins across {}..{} elements: no crossover",
+            range.start, range.end
+        ),
+    }
+    println!();
+}
+
+/// Report speedups with a 95% confidence interval instead of a single number
+fn speedup_confidence(gpu_model: &GpuModel) {
+    println!("📏 Speedup Confidence Intervals (95%, bootstrap)");
+    println!();
+
+    for size in [1000, 10_000, 100_000] {
+        let data: Vec<f32> = (0..size).map(|i| (i % 100) as f32 / 100.0).collect();
+        let v = Vector::from_slice(&data);
+
+        let cpu = collect_samples(|| cpu_dot_product(&v, &v).0, WARMUP_ITERS, BENCH_ITERS);
+        let gpu = collect_samples(|| gpu_model.dot_ms(size), WARMUP_ITERS, BENCH_ITERS);
+        let ci = speedup_ci(&cpu, &gpu, 0.95).expect("at least one sample per side");
+
+        let verdict = if !ci.is_conclusive() {
+            "inconclusive"
+        } else if ci.estimate > 1.0 {
+            "GPU faster"
+        } else {
+            "CPU faster"
+        };
+        println!("   Dot Product {:>7}: {} {}", size, ci, verdict);
+    }
+    println!();
+}
+
+/// Column order of [`results_to_csv`]
+const CSV_HEADER: &str = "name,operation,elements,cpu_time_ms,gpu_time_ms,speedup,winner,bound";
+
+/// One header line plus one row per result
+///
+/// Fields are written as-is; benchmark names and operations contain no
+/// commas or quotes.
+fn results_to_csv(results: &[BenchResult]) -> String {
+    let mut csv = format!("{}\n", CSV_HEADER);
+    for r in results {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{}\n",
+            r.name,
+            r.operation,
+            r.elements,
+            r.cpu_time_ms,
+            r.gpu_time_ms,
+            r.speedup,
+            r.winner,
+            r.bound
+        ));
+    }
+    csv
+}
+
+/// Results as a pretty-printed JSON array
+fn results_to_json(results: &[BenchResult]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(results)?)
+}
+
+/// Archive the comparison as CSV and JSON for plotting
+fn export_results(results: &[BenchResult]) -> Result<()> {
+    println!("💾 Exporting Results");
+    println!();
+
+    let dir = std::env::temp_dir();
+    let csv_path = dir.join("ch07-cpu-gpu-comparison.csv");
+    let json_path = dir.join("ch07-cpu-gpu-comparison.json");
+
+    fs::write(&csv_path, results_to_csv(results))
+        .with_context(|| format!("writing {}", csv_path.display()))?;
+    fs::write(&json_path, results_to_json(results)?)
+        .with_context(|| format!("writing {}", json_path.display()))?;
+
+    println!("   {} results written to:", results.len());
+    println!("   ├─ {}", csv_path.display());
+    println!("   └─ {}", json_path.display());
+    println!();
+    Ok(())
+}
+
 /// Summary analysis of when to use GPU
 fn summary_analysis(results: &[BenchResult]) {
     println!("📈 Summary Analysis");
@@ -206,6 +700,25 @@ fn summary_analysis(results: &[BenchResult]) {
         println!();
     }
 
+    let mut names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+    names.dedup();
+    println!("   GPU crossover by operation:");
+    for (i, name) in names.iter().enumerate() {
+        let branch = if i + 1 == names.len() {
+            "└─"
+        } else {
+            "├─"
+        };
+        match gpu_crossover(results, name) {
+            Some(elements) => println!(
+                "   {} {:<12} GPU wins from {} elements",
+                branch, name, elements
+            ),
+            None => println!("   {} {:<12} CPU wins at every size tested", branch, name),
+        }
+    }
+    println!();
+
     // Recommendations
     println!("   Recommendations:");
     println!("   ├─ < 10,000 elements: Use CPU (SIMD)");
@@ -272,13 +785,32 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
+    let gpu_model = GpuModel::default();
+    println!(
+        "GPU model: {} GB/s PCIe, {} μs kernel launch, {} GFLOPS",
+        gpu_model.pcie_gbps, gpu_model.kernel_launch_us, gpu_model.gflops
+    );
+    println!();
+
     let mut all_results = Vec::new();
 
-    all_results.extend(dot_product_benchmark());
+    all_results.extend(dot_product_benchmark(&gpu_model));
+    println!("{}", "─".repeat(70));
+    println!();
+
+    all_results.extend(elementwise_benchmark(&gpu_model));
+    println!("{}", "─".repeat(70));
+    println!();
+
+    all_results.extend(matmul_benchmark(&gpu_model));
+    println!("{}", "─".repeat(70));
+    println!();
+
+    crossover_search(&gpu_model);
     println!("{}", "─".repeat(70));
     println!();
 
-    all_results.extend(elementwise_benchmark());
+    speedup_confidence(&gpu_model);
     println!("{}", "─".repeat(70));
     println!();
 
@@ -286,6 +818,10 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
+    export_results(&all_results)?;
+    println!("{}", "─".repeat(70));
+    println!();
+
     gpu_failure_cases();
     println!("{}", "─".repeat(70));
     println!();
@@ -333,8 +869,9 @@ mod tests {
 
     #[test]
     fn test_gpu_overhead_exists() {
-        let small_gpu = simulated_gpu_dot(100);
-        let large_gpu = simulated_gpu_dot(100_000);
+        let model = GpuModel::default();
+        let small_gpu = model.dot_ms(100);
+        let large_gpu = model.dot_ms(100_000);
 
         // Overhead is fixed, so per-element cost decreases with size
         let small_per_element = small_gpu / 100.0;
@@ -346,6 +883,218 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bench_repeated_statistics() {
+        let data: Vec<f32> = (0..10_000).map(|i| i as f32).collect();
+        let v = Vector::from_slice(&data);
+        let stat = bench_repeated(|| cpu_dot_product(&v, &v).0, 15);
+
+        assert!(stat.min_ms <= stat.median_ms && stat.median_ms <= stat.max_ms);
+        assert!(stat.min_ms <= stat.mean_ms && stat.mean_ms <= stat.max_ms);
+
+        let constant = bench_repeated(|| 2.5, 10);
+        assert_eq!(constant.median_ms, 2.5);
+        assert_eq!(constant.stddev_ms, 0.0);
+    }
+
+    #[test]
+    fn test_warmup_excludes_cold_first_call() {
+        // First call pays a 50ms cold-cache penalty, later calls take 1ms
+        let cold_then_warm = || {
+            let mut calls = 0;
+            move || {
+                calls += 1;
+                if calls == 1 {
+                    50.0
+                } else {
+                    1.0
+                }
+            }
+        };
+
+        let cold = bench_with_warmup(cold_then_warm(), 0, 5);
+        assert_eq!(cold.max_ms, 50.0);
+
+        let warm = bench_with_warmup(cold_then_warm(), 1, 5);
+        assert_eq!(warm.max_ms, 1.0);
+        assert!((warm.mean_ms - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_roofline_classification() {
+        // Dot product: 2 FLOPs per 8 bytes, far below the machine balance
+        let dot = OpCost::dot(1_000_000);
+        assert!(dot.flops / (dot.bytes as f64) < 1.0);
+        assert_eq!(dot.bound(), Bound::Memory);
+        assert_eq!(OpCost::elementwise(1_000_000).bound(), Bound::Memory);
+
+        // Matmul intensity grows with n: 2n³ FLOPs over 12n² bytes
+        assert_eq!(OpCost::matmul(512).bound(), Bound::Compute);
+        assert_eq!(OpCost::matmul(8).bound(), Bound::Memory);
+
+        assert_eq!(roofline_bound(100.0, 10, 10.0), Bound::Compute);
+        assert_eq!(roofline_bound(99.0, 10, 10.0), Bound::Memory);
+    }
+
+    #[test]
+    fn test_speedup_ci_separates_conclusive_results() {
+        // Overlapping, tight samples: no real difference
+        let cpu = [1.00, 1.02, 0.98, 1.01, 0.99, 1.03, 0.97, 1.00];
+        let gpu = [0.99, 1.01, 1.00, 0.98, 1.02, 1.00, 0.97, 1.03];
+        let tie = speedup_ci(&cpu, &gpu, 0.95).expect("non-empty samples");
+        assert!(tie.lower <= 1.0 && 1.0 <= tie.upper, "{}", tie);
+        assert!(!tie.is_conclusive());
+
+        // CPU twice as slow: interval sits well above 1.0
+        let slow_cpu: Vec<f64> = cpu.iter().map(|t| t * 2.0).collect();
+        let win = speedup_ci(&slow_cpu, &gpu, 0.95).expect("non-empty samples");
+        assert!(win.lower > 1.0, "{}", win);
+        assert!(win.lower <= win.estimate && win.estimate <= win.upper);
+
+        // Same seed, same interval
+        assert_eq!(speedup_ci(&slow_cpu, &gpu, 0.95), Some(win));
+    }
+
+    #[test]
+    fn test_empty_samples_have_no_median_or_ci() {
+        assert_eq!(median(&[]), None);
+        assert_eq!(median(&[3.0, 1.0, 2.0]), Some(2.0));
+        assert_eq!(median(&[4.0, 1.0, 3.0, 2.0]), Some(2.5));
+
+        let samples = [1.0, 2.0];
+        assert_eq!(speedup_ci(&[], &samples, 0.95), None);
+        assert_eq!(speedup_ci(&samples, &[], 0.95), None);
+    }
+
+    fn sample_result(elements: usize, cpu_time_ms: f64, gpu_time_ms: f64) -> BenchResult {
+        let speedup = cpu_time_ms / gpu_time_ms;
+        BenchResult {
+            name: "Dot Product".to_string(),
+            operation: format!("{}×1", elements),
+            elements,
+            cpu_time_ms,
+            gpu_time_ms,
+            speedup,
+            winner: if speedup > 1.0 { "GPU" } else { "CPU" }.to_string(),
+            bound: OpCost::dot(elements).bound(),
+        }
+    }
+
+    #[test]
+    fn test_results_to_csv() {
+        let results = vec![
+            sample_result(100, 0.01, 0.02),
+            sample_result(1_000_000, 4.0, 0.5),
+        ];
+
+        let csv = results_to_csv(&results);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 1 + results.len());
+        assert_eq!(lines[0], CSV_HEADER);
+
+        let header: Vec<&str> = lines[0].split(',').collect();
+        let speedup_col = header
+            .iter()
+            .position(|&c| c == "speedup")
+            .expect("speedup column");
+        let winner_col = header
+            .iter()
+            .position(|&c| c == "winner")
+            .expect("winner column");
+        for row in &lines[1..] {
+            let fields: Vec<&str> = row.split(',').collect();
+            assert_eq!(fields.len(), header.len());
+            let speedup: f64 = fields[speedup_col].parse().expect("numeric speedup");
+            let expected = if speedup > 1.0 { "GPU" } else { "CPU" };
+            assert_eq!(fields[winner_col], expected);
+        }
+
+        let json: serde_json::Value =
+            serde_json::from_str(&results_to_json(&results).expect("serializable"))
+                .expect("valid JSON");
+        assert_eq!(json[1]["winner"], "GPU");
+        assert_eq!(json[0]["bound"], "memory-bound");
+    }
+
+    #[test]
+    fn test_cpu_matmul_correctness() {
+        let a = Matrix::from_vec(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).expect("valid matrix A");
+        let b =
+            Matrix::from_vec(3, 2, vec![7.0, 8.0, 9.0, 10.0, 11.0, 12.0]).expect("valid matrix B");
+
+        let (_, result) = cpu_matmul(&a, &b);
+
+        assert_eq!(result, vec![58.0, 64.0, 139.0, 154.0]);
+    }
+
+    #[test]
+    fn test_matmul_crossover_below_dot_product() {
+        // CPU model: 2 GFLOPS sustained, no fixed overhead
+        let cpu_ms = |cost: OpCost| cost.flops * 5e-7;
+        let model = GpuModel::default();
+
+        let dot = find_crossover(
+            |n| cpu_ms(OpCost::dot(n)),
+            |n| model.dot_ms(n),
+            1..10_000_000,
+        )
+        .expect("GPU wins large dot products");
+        let matmul_dim = find_crossover(
+            |n| cpu_ms(OpCost::matmul(n)),
+            |n| model.matmul_ms(n),
+            1..10_000,
+        )
+        .expect("GPU wins large matmuls");
+
+        // O(n³) work amortizes the fixed GPU overhead much sooner than O(n);
+        // compare in elements, as the benchmark tables do (n×n for matmul)
+        let matmul = matmul_dim * matmul_dim;
+        assert!(
+            matmul < dot,
+            "matmul crossover {} should be below dot product crossover {}",
+            matmul,
+            dot
+        );
+    }
+
+    #[test]
+    fn test_find_crossover_on_dot_model() {
+        // CPU model: 1ns per element, no fixed overhead
+        let cpu = |n: usize| n as f64 * 1e-6;
+        let model = GpuModel::default();
+        let gpu = |n: usize| model.dot_ms(n);
+
+        let crossover = find_crossover(cpu, gpu, 1..10_000_000).expect("GPU wins at scale");
+
+        assert!(gpu(crossover) < cpu(crossover));
+        for n in [1, crossover / 2, crossover - 1] {
+            assert!(
+                cpu(n) <= gpu(n),
+                "CPU should win below the crossover at {}",
+                n
+            );
+        }
+
+        // No crossover when the range stops short of it
+        assert_eq!(find_crossover(cpu, gpu, 1..crossover), None);
+    }
+
+    #[test]
+    fn test_gpu_transfer_scales_with_size() {
+        let model = GpuModel::default();
+        let n = 1_000_000;
+
+        let small = model.transfer_ms(n * F32_BYTES);
+        let large = model.transfer_ms(2 * n * F32_BYTES);
+        assert!((large - 2.0 * small).abs() < 1e-12);
+
+        // Launch and compute are fixed or linear too, so the whole dot
+        // estimate grows by exactly the extra transfer and compute
+        let extra = model.dot_ms(2 * n) - model.dot_ms(n);
+        let expected = model.transfer_ms(2 * n * F32_BYTES) + model.compute_ms(2.0 * n as f64);
+        assert!((extra - expected).abs() < 1e-9);
+    }
+
     #[test]
     fn test_relu_correctness() {
         let data: Vec<f32> = vec![-2.0, -1.0, 0.0, 1.0, 2.0];
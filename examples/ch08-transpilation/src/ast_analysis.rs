@@ -7,10 +7,12 @@
 ///
 /// **VALIDATION:** `make run-ch08-ast`
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::fmt;
 
 /// Simple AST node types for demonstration
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 enum Expr {
     /// Integer literal: 42
     Int(i64),
@@ -30,15 +32,110 @@ enum Expr {
     },
     /// Function call: foo(x, y)
     Call { name: String, args: Vec<Expr> },
+    /// Conditional expression: if cond { then } else { els }
+    If {
+        cond: Box<Expr>,
+        then: Box<Expr>,
+        els: Box<Expr>,
+    },
+    /// Scoped binding: let name = value in body
+    Let {
+        name: String,
+        value: Box<Expr>,
+        body: Box<Expr>,
+    },
+    /// Indexing: array[index]
+    Index { array: Box<Expr>, index: Box<Expr> },
+}
+
+impl Expr {
+    /// Direct child nodes, left to right
+    fn children(&self) -> Vec<&Expr> {
+        match self {
+            Expr::Int(_) | Expr::Float(_) | Expr::Str(_) | Expr::Bool(_) | Expr::Var(_) => vec![],
+            Expr::BinOp { left, right, .. } => vec![left, right],
+            Expr::Call { args, .. } => args.iter().collect(),
+            Expr::If { cond, then, els } => vec![cond, then, els],
+            Expr::Let { value, body, .. } => vec![value, body],
+            Expr::Index { array, index } => vec![array, index],
+        }
+    }
+
+    /// Levels in the tree (a leaf has depth 1)
+    fn depth(&self) -> usize {
+        1 + self.children().iter().map(|c| c.depth()).max().unwrap_or(0)
+    }
+
+    /// Total number of nodes in the tree
+    fn node_count(&self) -> usize {
+        1 + self
+            .children()
+            .iter()
+            .map(|c| c.node_count())
+            .sum::<usize>()
+    }
+
+    /// Variables referenced but not bound by an enclosing `Let`
+    ///
+    /// These become the parameters of a generated function signature.
+    fn free_vars(&self) -> BTreeSet<String> {
+        match self {
+            Expr::Var(name) => BTreeSet::from([name.clone()]),
+            Expr::Let { name, value, body } => {
+                let mut vars = body.free_vars();
+                vars.remove(name);
+                vars.extend(value.free_vars());
+                vars
+            }
+            _ => self
+                .children()
+                .into_iter()
+                .flat_map(|c| c.free_vars())
+                .collect(),
+        }
+    }
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 enum BinOperator {
     Add,
     Sub,
     Mul,
     Div,
+    /// Integer division (`//` in Python). Rust's `/` on integers truncates
+    /// toward zero while Python floors; they agree for non-negative operands.
+    IntDiv,
+    Mod,
+    Pow,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+impl BinOperator {
+    fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            BinOperator::Lt | BinOperator::Le | BinOperator::Gt | BinOperator::Ge | BinOperator::Eq
+        )
+    }
+
+    /// Binding strength in Rust (higher binds tighter)
+    fn precedence(&self) -> u8 {
+        match self {
+            BinOperator::Pow => 4, // method call
+            BinOperator::Mul | BinOperator::Div | BinOperator::IntDiv | BinOperator::Mod => 3,
+            BinOperator::Add | BinOperator::Sub => 2,
+            BinOperator::Lt
+            | BinOperator::Le
+            | BinOperator::Gt
+            | BinOperator::Ge
+            | BinOperator::Eq => 1,
+        }
+    }
 }
 
 impl fmt::Display for BinOperator {
@@ -48,12 +145,20 @@ impl fmt::Display for BinOperator {
             BinOperator::Sub => write!(f, "-"),
             BinOperator::Mul => write!(f, "*"),
             BinOperator::Div => write!(f, "/"),
+            BinOperator::IntDiv => write!(f, "//"),
+            BinOperator::Mod => write!(f, "%"),
+            BinOperator::Pow => write!(f, "**"),
+            BinOperator::Lt => write!(f, "<"),
+            BinOperator::Le => write!(f, "<="),
+            BinOperator::Gt => write!(f, ">"),
+            BinOperator::Ge => write!(f, ">="),
+            BinOperator::Eq => write!(f, "=="),
         }
     }
 }
 
 /// Inferred type from AST analysis
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 enum Type {
     Int,
     Float,
@@ -112,46 +217,225 @@ fn print_ast(expr: &Expr, indent: usize) {
                 print_ast(arg, indent + 1);
             }
         }
+        Expr::If { cond, then, els } => {
+            println!("{}If", prefix);
+            print_ast(cond, indent + 1);
+            print_ast(then, indent + 1);
+            print_ast(els, indent + 1);
+        }
+        Expr::Let { name, value, body } => {
+            println!("{}Let({})", prefix, name);
+            print_ast(value, indent + 1);
+            print_ast(body, indent + 1);
+        }
+        Expr::Index { array, index } => {
+            println!("{}Index", prefix);
+            print_ast(array, indent + 1);
+            print_ast(index, indent + 1);
+        }
     }
 }
 
-/// Infer type of expression
+/// Infer type of expression (no variable context)
 fn infer_type(expr: &Expr) -> Type {
+    infer_type_env(expr, &HashMap::new())
+}
+
+/// Infer type of expression, looking up variables in `env`
+fn infer_type_env(expr: &Expr, env: &HashMap<String, Type>) -> Type {
     match expr {
         Expr::Int(_) => Type::Int,
         Expr::Float(_) => Type::Float,
         Expr::Str(_) => Type::Str,
         Expr::Bool(_) => Type::Bool,
-        Expr::Var(_) => Type::Unknown, // Would need context
+        Expr::Var(name) => env.get(name).cloned().unwrap_or(Type::Unknown),
         Expr::BinOp { op, left, right } => {
-            let left_type = infer_type(left);
-            let right_type = infer_type(right);
+            binop_type(op, &infer_type_env(left, env), &infer_type_env(right, env))
+        }
+        Expr::Call { .. } => Type::Unknown, // Would need function signature
+        Expr::If { cond, then, els } => {
+            // Condition must be Bool and both branches must agree
+            let then_type = infer_type_env(then, env);
+            if infer_type_env(cond, env) == Type::Bool && then_type == infer_type_env(els, env) {
+                then_type
+            } else {
+                Type::Unknown
+            }
+        }
+        Expr::Let { name, value, body } => {
+            let mut scope = env.clone();
+            scope.insert(name.clone(), infer_type_env(value, env));
+            infer_type_env(body, &scope)
+        }
+        Expr::Index { array, .. } => match infer_type_env(array, env) {
+            Type::Str => Type::Str,
+            _ => Type::Unknown, // No array types
+        },
+    }
+}
+
+/// Result type of `left op right` (`Unknown` for invalid combinations)
+fn binop_type(op: &BinOperator, left: &Type, right: &Type) -> Type {
+    let numeric = |t: &Type| matches!(t, Type::Int | Type::Float);
+    match (left, right, op) {
+        // Unknown propagates
+        (Type::Unknown, _, _) => Type::Unknown,
+        (_, Type::Unknown, _) => Type::Unknown,
 
-            match (left_type, right_type, op) {
-                // Int op Int -> Int (except division)
-                (Type::Int, Type::Int, BinOperator::Add) => Type::Int,
-                (Type::Int, Type::Int, BinOperator::Sub) => Type::Int,
-                (Type::Int, Type::Int, BinOperator::Mul) => Type::Int,
-                (Type::Int, Type::Int, BinOperator::Div) => Type::Float, // Division promotes to float
+        // Comparisons yield Bool for matching or numeric operands
+        (l, r, op) if op.is_comparison() => {
+            if l == r || (numeric(l) && numeric(r)) {
+                Type::Bool
+            } else {
+                Type::Unknown
+            }
+        }
+
+        // String concatenation
+        (Type::Str, Type::Str, BinOperator::Add) => Type::Str,
+
+        // Int op Int -> Int (except division)
+        (Type::Int, Type::Int, BinOperator::Add) => Type::Int,
+        (Type::Int, Type::Int, BinOperator::Sub) => Type::Int,
+        (Type::Int, Type::Int, BinOperator::Mul) => Type::Int,
+        (Type::Int, Type::Int, BinOperator::Mod) => Type::Int,
+        (Type::Int, Type::Int, BinOperator::Pow) => Type::Int,
+        (Type::Int, Type::Int, BinOperator::IntDiv) => Type::Int,
+        (Type::Int, Type::Int, BinOperator::Div) => Type::Float, // Division promotes to float
+
+        // Float with any numeric operand -> Float
+        (l, r, _) if numeric(l) && numeric(r) => Type::Float,
+
+        // Invalid combinations
+        _ => Type::Unknown,
+    }
+}
 
-                // Float in either position -> Float
-                (Type::Float, _, _) => Type::Float,
-                (_, Type::Float, _) => Type::Float,
+/// Structured type error, located by the offending subexpression
+#[derive(Debug, Clone, PartialEq)]
+enum TypeError {
+    /// Operator applied to incompatible operand types
+    OperandMismatch {
+        op: BinOperator,
+        left: Type,
+        right: Type,
+        expr: String,
+    },
+    /// Variable not present in the type environment
+    UnboundVariable(String),
+    /// `if` condition is not a `bool`
+    NonBoolCondition { found: Type, expr: String },
+    /// `if` branches have different types
+    BranchMismatch { then: Type, els: Type, expr: String },
+}
 
-                // Unknown propagates
-                (Type::Unknown, _, _) => Type::Unknown,
-                (_, Type::Unknown, _) => Type::Unknown,
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeError::OperandMismatch {
+                op,
+                left,
+                right,
+                expr,
+            } => write!(
+                f,
+                "cannot apply `{}` to {} and {} in `{}`",
+                op, left, right, expr
+            ),
+            TypeError::UnboundVariable(name) => write!(f, "unbound variable `{}`", name),
+            TypeError::NonBoolCondition { found, expr } => {
+                write!(
+                    f,
+                    "condition has type {}, expected bool in `{}`",
+                    found, expr
+                )
+            }
+            TypeError::BranchMismatch { then, els, expr } => {
+                write!(f, "if branches differ: {} vs {} in `{}`", then, els, expr)
+            }
+        }
+    }
+}
 
-                // Invalid combinations
-                _ => Type::Unknown,
+/// Type-check an expression, reporting the first invalid subexpression
+///
+/// Unlike `infer_type_env`, invalid combinations are errors rather than
+/// `Unknown`. `Unknown` (e.g. from calls without signatures) is still
+/// accepted and propagates.
+fn type_check(expr: &Expr, env: &HashMap<String, Type>) -> Result<Type, TypeError> {
+    match expr {
+        Expr::Var(name) => env
+            .get(name)
+            .cloned()
+            .ok_or_else(|| TypeError::UnboundVariable(name.clone())),
+        Expr::BinOp { op, left, right } => {
+            let left_type = type_check(left, env)?;
+            let right_type = type_check(right, env)?;
+            match binop_type(op, &left_type, &right_type) {
+                Type::Unknown if left_type != Type::Unknown && right_type != Type::Unknown => {
+                    Err(TypeError::OperandMismatch {
+                        op: op.clone(),
+                        left: left_type,
+                        right: right_type,
+                        expr: generate_rust(expr),
+                    })
+                }
+                ty => Ok(ty),
             }
         }
-        Expr::Call { .. } => Type::Unknown, // Would need function signature
+        Expr::Call { args, .. } => {
+            for arg in args {
+                type_check(arg, env)?;
+            }
+            Ok(Type::Unknown) // Would need function signature
+        }
+        Expr::If { cond, then, els } => {
+            let cond_type = type_check(cond, env)?;
+            if !matches!(cond_type, Type::Bool | Type::Unknown) {
+                return Err(TypeError::NonBoolCondition {
+                    found: cond_type,
+                    expr: generate_rust(cond),
+                });
+            }
+            let then_type = type_check(then, env)?;
+            let els_type = type_check(els, env)?;
+            match (then_type, els_type) {
+                (Type::Unknown, ty) | (ty, Type::Unknown) => Ok(ty),
+                (then, els) if then == els => Ok(then),
+                (then, els) => Err(TypeError::BranchMismatch {
+                    then,
+                    els,
+                    expr: generate_rust(expr),
+                }),
+            }
+        }
+        Expr::Let { name, value, body } => {
+            let mut scope = env.clone();
+            scope.insert(name.clone(), type_check(value, env)?);
+            type_check(body, &scope)
+        }
+        Expr::Index { array, index } => {
+            type_check(index, env)?;
+            match type_check(array, env)? {
+                Type::Str => Ok(Type::Str),
+                _ => Ok(Type::Unknown),
+            }
+        }
+        literal => Ok(infer_type(literal)),
     }
 }
 
-/// Generate Rust code from AST
+/// Generate Rust code from AST, emitting only the parentheses precedence requires
 fn generate_rust(expr: &Expr) -> String {
+    rust_code(expr, false)
+}
+
+/// Generate Rust code with every binary operation parenthesized
+fn generate_rust_verbose(expr: &Expr) -> String {
+    rust_code(expr, true)
+}
+
+fn rust_code(expr: &Expr, verbose: bool) -> String {
     match expr {
         Expr::Int(n) => format!("{}", n),
         Expr::Float(f) => format!("{:.1}", f),
@@ -159,19 +443,527 @@ fn generate_rust(expr: &Expr) -> String {
         Expr::Bool(b) => format!("{}", b),
         Expr::Var(name) => name.clone(),
         Expr::BinOp { op, left, right } => {
-            let left_code = generate_rust(left);
-            let right_code = generate_rust(right);
-            format!("({} {} {})", left_code, op, right_code)
+            let wrap = |child: &Expr, is_right: bool| {
+                let code = rust_code(child, verbose);
+                if !verbose && needs_parens(child, op, is_right) {
+                    format!("({})", code)
+                } else {
+                    code
+                }
+            };
+            // Cast an integer operand when the operation needs `f64`
+            let operand = |child: &Expr, is_right: bool, float: bool| {
+                if !float
+                    || emits_f64(child)
+                    || !matches!(infer_type(child), Type::Int | Type::Unknown)
+                {
+                    return wrap(child, is_right);
+                }
+                match child {
+                    Expr::Int(n) => format!("{}.0", n),
+                    Expr::Var(_) | Expr::Call { .. } | Expr::Index { .. } => {
+                        format!("({} as f64)", rust_code(child, verbose))
+                    }
+                    // Verbose output already parenthesizes binary operations
+                    Expr::BinOp { .. } if verbose => {
+                        format!("({} as f64)", rust_code(child, verbose))
+                    }
+                    _ => format!("(({}) as f64)", rust_code(child, verbose)),
+                }
+            };
+            match op {
+                // Rust has no power operator: i64::pow(u32)
+                BinOperator::Pow => {
+                    let receiver = match left.as_ref() {
+                        // Method receivers need a concrete integer type: `2i64.pow(3)`
+                        Expr::Int(n) if *n < 0 => format!("({}i64)", n),
+                        Expr::Int(n) => format!("{}i64", n),
+                        _ => wrap(left, false),
+                    };
+                    match right.as_ref() {
+                        Expr::Int(n) => format!("{}.pow({})", receiver, n),
+                        // `as` binds tighter than any binary operator
+                        _ => format!("{}.pow({} as u32)", receiver, wrap(right, false)),
+                    }
+                }
+                // `/` is true division: integer operands are cast so `1 / 2` is 0.5
+                BinOperator::Div if verbose => format!(
+                    "({} / {})",
+                    operand(left, false, true),
+                    operand(right, true, true)
+                ),
+                BinOperator::Div => {
+                    format!(
+                        "{} / {}",
+                        operand(left, false, true),
+                        operand(right, true, true)
+                    )
+                }
+                // Integer `/` already truncates in Rust
+                BinOperator::IntDiv if verbose => format!(
+                    "({} / {})",
+                    rust_code(left, verbose),
+                    rust_code(right, verbose)
+                ),
+                BinOperator::IntDiv => format!("{} / {}", wrap(left, false), wrap(right, true)),
+                // An `f64` operand (e.g. a true division) promotes the other side
+                _ => {
+                    let float = emits_f64(left) || emits_f64(right);
+                    let code = format!(
+                        "{} {} {}",
+                        operand(left, false, float),
+                        op,
+                        operand(right, true, float)
+                    );
+                    if verbose {
+                        format!("({})", code)
+                    } else {
+                        code
+                    }
+                }
+            }
+        }
+        Expr::Call { name, args } => {
+            let args_code: Vec<String> = args.iter().map(|a| rust_code(a, verbose)).collect();
+            format!("{}({})", name, args_code.join(", "))
+        }
+        Expr::If { cond, then, els } => format!(
+            "if {} {{ {} }} else {{ {} }}",
+            rust_code(cond, verbose),
+            rust_code(then, verbose),
+            rust_code(els, verbose)
+        ),
+        Expr::Let { name, value, body } => format!(
+            "{{ let {} = {}; {} }}",
+            name,
+            rust_code(value, verbose),
+            rust_code(body, verbose)
+        ),
+        Expr::Index { array, index } => {
+            let target = match array.as_ref() {
+                Expr::BinOp { .. } | Expr::If { .. } | Expr::Let { .. } => {
+                    format!("({})", rust_code(array, verbose))
+                }
+                _ => rust_code(array, verbose),
+            };
+            format!("{}[{}]", target, rust_code(index, verbose))
+        }
+    }
+}
+
+/// Does the generated Rust for `expr` have type `f64`?
+///
+/// True division always does, and so does arithmetic with an `f64` operand.
+fn emits_f64(expr: &Expr) -> bool {
+    match expr {
+        Expr::BinOp {
+            op: BinOperator::Div,
+            ..
+        } => true,
+        Expr::BinOp { op, left, right } if !op.is_comparison() && *op != BinOperator::Pow => {
+            emits_f64(left) || emits_f64(right)
+        }
+        Expr::If { then, els, .. } => emits_f64(then) || emits_f64(els),
+        Expr::Let { body, .. } => emits_f64(body),
+        _ => infer_type(expr) == Type::Float,
+    }
+}
+
+/// Does `child` need parentheses as an operand of `parent`?
+///
+/// Operators are left-associative, so an equal-precedence right operand keeps
+/// its parens (`a - (b - c)`); comparisons don't chain in Rust, so nested
+/// comparisons are always parenthesized. Operands of `.pow()` (precedence 4)
+/// are wrapped for any lower-precedence binary operation.
+fn needs_parens(child: &Expr, parent: &BinOperator, is_right: bool) -> bool {
+    match child {
+        Expr::BinOp { op, .. } => {
+            let (child_prec, parent_prec) = (op.precedence(), parent.precedence());
+            if *op == BinOperator::Pow {
+                false
+            } else if parent == &BinOperator::Pow {
+                true
+            } else {
+                child_prec < parent_prec
+                    || (child_prec == parent_prec && (is_right || parent.is_comparison()))
+            }
+        }
+        // `if` and blocks are expressions but can't be bare operands
+        Expr::If { .. } | Expr::Let { .. } => true,
+        _ => false,
+    }
+}
+
+/// Binding strength in Python (higher binds tighter)
+fn python_precedence(op: &BinOperator) -> u8 {
+    match op {
+        BinOperator::Pow => 4,
+        BinOperator::Mul | BinOperator::Div | BinOperator::IntDiv | BinOperator::Mod => 3,
+        BinOperator::Add | BinOperator::Sub => 2,
+        BinOperator::Lt | BinOperator::Le | BinOperator::Gt | BinOperator::Ge | BinOperator::Eq => {
+            1
+        }
+    }
+}
+
+/// Generate Python code from AST
+///
+/// Mirrors `generate_rust`: `/` is true division in both, booleans become
+/// `True`/`False` and strings are escaped.
+///
+/// Python's `//` and `%` floor (`-7 // 2` is -4, `-7 % 2` is 1) where Rust and
+/// `evaluate_value` truncate toward zero (-3 and -1), so integer division and
+/// remainder are emitted as `int(a / b)` and `math.fmod(a, b)` (which needs
+/// `import math`). Both go through floats, so they are exact below 2^53.
+fn generate_python(expr: &Expr) -> String {
+    match expr {
+        Expr::Int(n) => format!("{}", n),
+        Expr::Float(f) => format!("{:?}", f),
+        Expr::Str(s) => python_string(s),
+        Expr::Bool(true) => "True".to_string(),
+        Expr::Bool(false) => "False".to_string(),
+        Expr::Var(name) => name.clone(),
+        Expr::BinOp { op, left, right } => {
+            let wrap = |child: &Expr, is_right: bool| {
+                let code = generate_python(child);
+                if python_needs_parens(child, op, is_right) {
+                    format!("({})", code)
+                } else {
+                    code
+                }
+            };
+            match op {
+                BinOperator::IntDiv => {
+                    format!("int({} / {})", wrap(left, false), wrap(right, true))
+                }
+                BinOperator::Mod => format!(
+                    "math.fmod({}, {})",
+                    generate_python(left),
+                    generate_python(right)
+                ),
+                _ => format!("{} {} {}", wrap(left, false), op, wrap(right, true)),
+            }
         }
         Expr::Call { name, args } => {
-            let args_code: Vec<String> = args.iter().map(generate_rust).collect();
+            let args_code: Vec<String> = args.iter().map(generate_python).collect();
             format!("{}({})", name, args_code.join(", "))
         }
+        Expr::If { cond, then, els } => {
+            let wrap = |child: &Expr| match child {
+                Expr::If { .. } | Expr::Let { .. } => format!("({})", generate_python(child)),
+                _ => generate_python(child),
+            };
+            format!("{} if {} else {}", wrap(then), wrap(cond), wrap(els))
+        }
+        // Python has no let-expression; an immediately-applied lambda scopes the binding
+        Expr::Let { name, value, body } => format!(
+            "(lambda {}: {})({})",
+            name,
+            generate_python(body),
+            generate_python(value)
+        ),
+        Expr::Index { array, index } => {
+            let target = match array.as_ref() {
+                Expr::BinOp { .. } | Expr::If { .. } | Expr::Let { .. } => {
+                    format!("({})", generate_python(array))
+                }
+                _ => generate_python(array),
+            };
+            format!("{}[{}]", target, generate_python(index))
+        }
+    }
+}
+
+/// Does `child` need parentheses as a Python operand of `parent`?
+///
+/// `**` is right-associative and binds tighter than a unary minus on its
+/// left; comparisons chain in Python (`a < b < c`), so nested comparisons
+/// are always parenthesized.
+fn python_needs_parens(child: &Expr, parent: &BinOperator, is_right: bool) -> bool {
+    let right_assoc = *parent == BinOperator::Pow;
+    match child {
+        // Emitted as calls, which bind tightest
+        Expr::BinOp {
+            op: BinOperator::IntDiv | BinOperator::Mod,
+            ..
+        } => false,
+        Expr::BinOp { op, .. } => {
+            let (child_prec, parent_prec) = (python_precedence(op), python_precedence(parent));
+            child_prec < parent_prec
+                || (child_prec == parent_prec
+                    && (is_right != right_assoc || parent.is_comparison()))
+        }
+        Expr::Int(n) => right_assoc && !is_right && *n < 0,
+        Expr::Float(f) => right_assoc && !is_right && *f < 0.0,
+        Expr::If { .. } | Expr::Let { .. } => true,
+        _ => false,
+    }
+}
+
+/// Double-quoted Python string literal
+fn python_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Evaluate fully-literal subtrees at compile time
+///
+/// `x * (2 + 3)` becomes `x * 5`. Operations that would fail at runtime
+/// (division by zero, overflow) are left unfolded.
+fn fold_constants(expr: &Expr) -> Expr {
+    match expr {
+        Expr::BinOp { op, left, right } => {
+            let left = fold_constants(left);
+            let right = fold_constants(right);
+            fold_binop(op, &left, &right).unwrap_or_else(|| Expr::BinOp {
+                op: op.clone(),
+                left: Box::new(left),
+                right: Box::new(right),
+            })
+        }
+        Expr::Call { name, args } => Expr::Call {
+            name: name.clone(),
+            args: args.iter().map(fold_constants).collect(),
+        },
+        Expr::If { cond, then, els } => match fold_constants(cond) {
+            // Statically known condition: keep only the taken branch
+            Expr::Bool(true) => fold_constants(then),
+            Expr::Bool(false) => fold_constants(els),
+            cond => Expr::If {
+                cond: Box::new(cond),
+                then: Box::new(fold_constants(then)),
+                els: Box::new(fold_constants(els)),
+            },
+        },
+        Expr::Let { name, value, body } => Expr::Let {
+            name: name.clone(),
+            value: Box::new(fold_constants(value)),
+            body: Box::new(fold_constants(body)),
+        },
+        Expr::Index { array, index } => Expr::Index {
+            array: Box::new(fold_constants(array)),
+            index: Box::new(fold_constants(index)),
+        },
+        _ => expr.clone(),
+    }
+}
+
+/// Apply semantics-preserving algebraic identities bottom-up
+///
+/// `x + 0 → x`, `x - 0 → x` and `x * 1 → x` always hold. `x * 0 → 0` and
+/// `x - x → 0` drop `x`, so they only fire when `x` is certain to evaluate to
+/// an Int: an integer literal or a let-bound Int variable (its value was
+/// computed before the body runs). Anything else could fail (division by
+/// zero, overflow, an unbound variable) or be a Float.
+fn simplify(expr: &Expr) -> Expr {
+    simplify_in(expr, &HashMap::new())
+}
+
+/// `simplify` with the let-bound names in scope and their inferred types
+fn simplify_in(expr: &Expr, bound: &HashMap<String, Type>) -> Expr {
+    match expr {
+        Expr::BinOp { op, left, right } => {
+            let left = simplify_in(left, bound);
+            let right = simplify_in(right, bound);
+            let droppable = |e: &Expr| match e {
+                Expr::Int(_) => true,
+                Expr::Var(name) => bound.get(name) == Some(&Type::Int),
+                _ => false,
+            };
+            match (op, &left, &right) {
+                (BinOperator::Add, x, Expr::Int(0)) | (BinOperator::Add, Expr::Int(0), x) => {
+                    x.clone()
+                }
+                (BinOperator::Sub, x, Expr::Int(0)) => x.clone(),
+                (BinOperator::Mul, x, Expr::Int(1)) | (BinOperator::Mul, Expr::Int(1), x) => {
+                    x.clone()
+                }
+                (BinOperator::Mul, x, Expr::Int(0)) | (BinOperator::Mul, Expr::Int(0), x)
+                    if droppable(x) =>
+                {
+                    Expr::Int(0)
+                }
+                (BinOperator::Sub, x, y) if x == y && droppable(x) => Expr::Int(0),
+                _ => Expr::BinOp {
+                    op: op.clone(),
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            }
+        }
+        Expr::Call { name, args } => Expr::Call {
+            name: name.clone(),
+            args: args.iter().map(|a| simplify_in(a, bound)).collect(),
+        },
+        Expr::If { cond, then, els } => Expr::If {
+            cond: Box::new(simplify_in(cond, bound)),
+            then: Box::new(simplify_in(then, bound)),
+            els: Box::new(simplify_in(els, bound)),
+        },
+        Expr::Let { name, value, body } => {
+            let value = simplify_in(value, bound);
+            let mut inner = bound.clone();
+            inner.insert(name.clone(), infer_type_env(&value, bound));
+            Expr::Let {
+                name: name.clone(),
+                value: Box::new(value),
+                body: Box::new(simplify_in(body, &inner)),
+            }
+        }
+        Expr::Index { array, index } => Expr::Index {
+            array: Box::new(simplify_in(array, bound)),
+            index: Box::new(simplify_in(index, bound)),
+        },
+        _ => expr.clone(),
+    }
+}
+
+/// Fold a binary operation on two literals, if it is safe to do so
+fn fold_binop(op: &BinOperator, left: &Expr, right: &Expr) -> Option<Expr> {
+    match (left, right) {
+        (Expr::Int(l), Expr::Int(r)) => {
+            let (l, r) = (*l, *r);
+            let folded = match op {
+                BinOperator::Add => Expr::Int(l.checked_add(r)?),
+                BinOperator::Sub => Expr::Int(l.checked_sub(r)?),
+                BinOperator::Mul => Expr::Int(l.checked_mul(r)?),
+                // `/` is true division (Int / Int is Float); only `//` stays integral
+                BinOperator::Div if r != 0 => Expr::Float(l as f64 / r as f64),
+                BinOperator::Div => return None,
+                BinOperator::IntDiv => Expr::Int(l.checked_div(r)?),
+                BinOperator::Mod => Expr::Int(l.checked_rem(r)?),
+                BinOperator::Pow => Expr::Int(l.checked_pow(u32::try_from(r).ok()?)?),
+                BinOperator::Lt => Expr::Bool(l < r),
+                BinOperator::Le => Expr::Bool(l <= r),
+                BinOperator::Gt => Expr::Bool(l > r),
+                BinOperator::Ge => Expr::Bool(l >= r),
+                BinOperator::Eq => Expr::Bool(l == r),
+            };
+            Some(folded)
+        }
+        _ => {
+            // Mixed Int/Float literals promote to Float
+            let as_f64 = |e: &Expr| match e {
+                Expr::Int(n) => Some(*n as f64),
+                Expr::Float(x) => Some(*x),
+                _ => None,
+            };
+            let (l, r) = (as_f64(left)?, as_f64(right)?);
+            let folded = match op {
+                BinOperator::Add => Expr::Float(l + r),
+                BinOperator::Sub => Expr::Float(l - r),
+                BinOperator::Mul => Expr::Float(l * r),
+                BinOperator::Div if r != 0.0 => Expr::Float(l / r),
+                BinOperator::Lt => Expr::Bool(l < r),
+                BinOperator::Le => Expr::Bool(l <= r),
+                BinOperator::Gt => Expr::Bool(l > r),
+                BinOperator::Ge => Expr::Bool(l >= r),
+                BinOperator::Eq => Expr::Bool(l == r),
+                _ => return None,
+            };
+            Some(folded)
+        }
+    }
+}
+
+/// Static-analysis finding
+#[derive(Debug, Clone, PartialEq)]
+struct Lint {
+    /// Offending expression, as Rust source
+    expr: String,
+    message: String,
+}
+
+/// Flag indexing whose index can reach `array_len_var`
+///
+/// A loop `for i in start..end` is written `let i = range(start, end) in body`,
+/// the shape a Python `for i in range(...)` lowers to, so `i` is at most
+/// `end - 1`. Index bounds are tracked as `len + k`; any index whose bound
+/// has `k >= 0` is out of range on the last iteration. Indices the analysis
+/// can't bound are not flagged.
+fn detect_index_out_of_range(expr: &Expr, array_len_var: &str) -> Vec<Lint> {
+    let mut lints = Vec::new();
+    find_out_of_range(expr, array_len_var, &HashMap::new(), &mut lints);
+    lints
+}
+
+fn find_out_of_range(
+    expr: &Expr,
+    len_var: &str,
+    bounds: &HashMap<String, i64>,
+    lints: &mut Vec<Lint>,
+) {
+    match expr {
+        Expr::Let { name, value, body } => {
+            find_out_of_range(value, len_var, bounds, lints);
+            let mut scope = bounds.clone();
+            let loop_bound = match value.as_ref() {
+                Expr::Call { name: f, args } if f == "range" && args.len() == 2 => {
+                    len_offset(&args[1], len_var, bounds).map(|end| end - 1)
+                }
+                _ => None,
+            };
+            match loop_bound {
+                Some(max) => scope.insert(name.clone(), max),
+                None => scope.remove(name), // Shadowed by an unbounded value
+            };
+            find_out_of_range(body, len_var, &scope, lints);
+        }
+        Expr::Index { index, .. } => {
+            if let Some(k) = len_offset(index, len_var, bounds).filter(|&k| k >= 0) {
+                let reach = if k == 0 {
+                    len_var.to_string()
+                } else {
+                    format!("{} + {}", len_var, k)
+                };
+                lints.push(Lint {
+                    expr: generate_rust(expr),
+                    message: format!(
+                        "index can reach {}, past the last element at {} - 1",
+                        reach, len_var
+                    ),
+                });
+            }
+            for child in expr.children() {
+                find_out_of_range(child, len_var, bounds, lints);
+            }
+        }
+        _ => {
+            for child in expr.children() {
+                find_out_of_range(child, len_var, bounds, lints);
+            }
+        }
+    }
+}
+
+/// Largest value of `expr` as an offset `k` from `len_var` (`len + k`)
+fn len_offset(expr: &Expr, len_var: &str, bounds: &HashMap<String, i64>) -> Option<i64> {
+    match expr {
+        Expr::Var(name) if name == len_var => Some(0),
+        Expr::Var(name) => bounds.get(name).copied(),
+        Expr::BinOp { op, left, right } => match (op, left.as_ref(), right.as_ref()) {
+            (BinOperator::Add, e, Expr::Int(k)) | (BinOperator::Add, Expr::Int(k), e) => {
+                len_offset(e, len_var, bounds)?.checked_add(*k)
+            }
+            (BinOperator::Sub, e, Expr::Int(k)) => len_offset(e, len_var, bounds)?.checked_sub(*k),
+            _ => None,
+        },
+        _ => None,
     }
 }
 
 /// Demonstrate AST structure
-fn ast_structure_demo() {
+fn ast_structure_demo() -> Result<()> {
     println!("🌳 AST Structure");
     println!();
 
@@ -182,6 +974,29 @@ fn ast_structure_demo() {
     println!("   AST representation:");
     print_ast(&expr, 2);
     println!();
+    println!("   Depth: {}, nodes: {}", expr.depth(), expr.node_count());
+    println!();
+
+    // Free variables become function parameters
+    let params: Vec<String> = expr
+        .free_vars()
+        .into_iter()
+        .map(|v| format!("{}: i64", v))
+        .collect();
+    println!(
+        "   Signature: fn f({}) -> i64 {{ {} }}",
+        params.join(", "),
+        generate_rust(&expr)
+    );
+    println!();
+
+    // ASTs round-trip through JSON, so transpiler inputs can live on disk
+    let json = serde_json::to_string(&expr)?;
+    let loaded: Expr = serde_json::from_str(&json)?;
+    println!("   As JSON: {}", json);
+    println!("   Round-trips: {}", loaded == expr);
+    println!();
+    Ok(())
 }
 
 /// Demonstrate type inference
@@ -210,6 +1025,14 @@ fn type_inference_demo() {
                 right: Box::new(Expr::Int(2)),
             },
         ),
+        (
+            "1 < 2.5",
+            Expr::BinOp {
+                op: BinOperator::Lt,
+                left: Box::new(Expr::Int(1)),
+                right: Box::new(Expr::Float(2.5)),
+            },
+        ),
     ];
 
     println!("   {:>15} │ {:>10}", "Expression", "Type");
@@ -220,6 +1043,34 @@ fn type_inference_demo() {
         println!("   {:>15} │ {:>10}", source, ty);
     }
     println!();
+
+    // Type checking reports *why* an expression is ill-typed
+    let ill_typed = Expr::BinOp {
+        op: BinOperator::Mul,
+        left: Box::new(Expr::Var("x".to_string())),
+        right: Box::new(Expr::BinOp {
+            op: BinOperator::Add,
+            left: Box::new(Expr::Str("a".to_string())),
+            right: Box::new(Expr::Int(1)),
+        }),
+    };
+    let env = HashMap::from([("x".to_string(), Type::Int)]);
+    if let Err(e) = type_check(&ill_typed, &env) {
+        println!("   type_check(x * (\"a\" + 1)): ❌ {}", e);
+    }
+    println!();
+
+    // Variables resolve through a type environment
+    let env = HashMap::from([("x".to_string(), Type::Int), ("y".to_string(), Type::Int)]);
+    let expr = build_example_ast();
+    println!("   With x: i64, y: i64");
+    println!(
+        "   {:>15} │ {:>10} (without env: {})",
+        "x + y * 2",
+        infer_type_env(&expr, &env),
+        infer_type(&expr)
+    );
+    println!();
 }
 
 /// Demonstrate code generation
@@ -247,57 +1098,688 @@ fn code_generation_demo() {
             name: "calculate".to_string(),
             args: vec![Expr::Var("x".to_string()), Expr::Int(5)],
         },
+        Expr::BinOp {
+            op: BinOperator::Pow,
+            left: Box::new(Expr::Var("n".to_string())),
+            right: Box::new(Expr::Int(2)),
+        },
+        Expr::BinOp {
+            op: BinOperator::Le,
+            left: Box::new(Expr::BinOp {
+                op: BinOperator::Mod,
+                left: Box::new(Expr::Var("x".to_string())),
+                right: Box::new(Expr::Int(2)),
+            }),
+            right: Box::new(Expr::Int(0)),
+        },
+        Expr::If {
+            cond: Box::new(Expr::BinOp {
+                op: BinOperator::Gt,
+                left: Box::new(Expr::Var("x".to_string())),
+                right: Box::new(Expr::Int(0)),
+            }),
+            then: Box::new(Expr::Var("x".to_string())),
+            els: Box::new(Expr::Int(0)),
+        },
+        Expr::Let {
+            name: "t".to_string(),
+            value: Box::new(Expr::BinOp {
+                op: BinOperator::Mul,
+                left: Box::new(Expr::Var("x".to_string())),
+                right: Box::new(Expr::Int(2)),
+            }),
+            body: Box::new(Expr::BinOp {
+                op: BinOperator::Add,
+                left: Box::new(Expr::Var("t".to_string())),
+                right: Box::new(Expr::Int(1)),
+            }),
+        },
     ];
 
-    println!("   Generated Rust code:");
+    println!("   Generated Rust code (minimal │ fully parenthesized):");
     println!();
 
     for expr in examples {
-        let rust_code = generate_rust(&expr);
-        println!("   → {}", rust_code);
+        println!(
+            "   → {:<28} │ {}",
+            generate_rust(&expr),
+            generate_rust_verbose(&expr)
+        );
     }
     println!();
 }
 
-/// Evaluate expression (for verification)
-fn evaluate(expr: &Expr, vars: &std::collections::HashMap<String, i64>) -> Option<i64> {
-    match expr {
-        Expr::Int(n) => Some(*n),
-        Expr::Var(name) => vars.get(name).copied(),
-        Expr::BinOp { op, left, right } => {
-            let l = evaluate(left, vars)?;
-            let r = evaluate(right, vars)?;
-            match op {
-                BinOperator::Add => Some(l + r),
-                BinOperator::Sub => Some(l - r),
-                BinOperator::Mul => Some(l * r),
-                BinOperator::Div => Some(l / r),
-            }
-        }
-        _ => None, // Floats, strings, etc. not supported
-    }
-}
-
-/// Demonstrate semantic preservation through evaluation
-fn semantic_preservation_demo() {
-    println!("✅ Semantic Preservation");
+/// Demonstrate compile-time constant folding
+fn constant_folding_demo() {
+    println!("⚡ Constant Folding");
     println!();
 
-    let expr = build_example_ast(); // x + y * 2
-
-    let mut vars = std::collections::HashMap::new();
-
-    let test_cases = vec![
-        (5, 3, 11),  // 5 + 3 * 2 = 11
-        (0, 10, 20), // 0 + 10 * 2 = 20
-        (10, -2, 6), // 10 + (-2) * 2 = 6
-    ];
-
-    println!("   Testing: x + y * 2");
-    println!();
-    println!(
-        "   {:>5} {:>5} │ {:>8} │ {:>8}",
-        "x", "y", "Expected", "Actual"
+    let examples = vec![
+        // x * (2 + 3)
+        Expr::BinOp {
+            op: BinOperator::Mul,
+            left: Box::new(Expr::Var("x".to_string())),
+            right: Box::new(Expr::BinOp {
+                op: BinOperator::Add,
+                left: Box::new(Expr::Int(2)),
+                right: Box::new(Expr::Int(3)),
+            }),
+        },
+        // (2 ** 10) < 1000
+        Expr::BinOp {
+            op: BinOperator::Lt,
+            left: Box::new(Expr::BinOp {
+                op: BinOperator::Pow,
+                left: Box::new(Expr::Int(2)),
+                right: Box::new(Expr::Int(10)),
+            }),
+            right: Box::new(Expr::Int(1000)),
+        },
+        // 1 / 0 must not be folded
+        Expr::BinOp {
+            op: BinOperator::Div,
+            left: Box::new(Expr::Int(1)),
+            right: Box::new(Expr::Int(0)),
+        },
+    ];
+
+    for expr in examples {
+        println!(
+            "   {:<22} → {}",
+            generate_rust(&expr),
+            generate_rust(&fold_constants(&expr))
+        );
+    }
+    println!();
+
+    // Algebraic identities remove work constant folding can't see
+    println!("   Algebraic simplification:");
+    let var = |n: &str| Box::new(Expr::Var(n.to_string()));
+    let examples = vec![
+        // (x + 0) * 1
+        Expr::BinOp {
+            op: BinOperator::Mul,
+            left: Box::new(Expr::BinOp {
+                op: BinOperator::Add,
+                left: var("x"),
+                right: Box::new(Expr::Int(0)),
+            }),
+            right: Box::new(Expr::Int(1)),
+        },
+        // let n = 3 in y + (n - n) * 2
+        Expr::Let {
+            name: "n".to_string(),
+            value: Box::new(Expr::Int(3)),
+            body: Box::new(Expr::BinOp {
+                op: BinOperator::Add,
+                left: var("y"),
+                right: Box::new(Expr::BinOp {
+                    op: BinOperator::Mul,
+                    left: Box::new(Expr::BinOp {
+                        op: BinOperator::Sub,
+                        left: var("n"),
+                        right: var("n"),
+                    }),
+                    right: Box::new(Expr::Int(2)),
+                }),
+            }),
+        },
+        // x * 0 keeps x: it may be unbound
+        Expr::BinOp {
+            op: BinOperator::Mul,
+            left: var("x"),
+            right: Box::new(Expr::Int(0)),
+        },
+    ];
+    for expr in examples {
+        println!(
+            "   {:<22} → {}",
+            generate_rust(&expr),
+            generate_rust(&simplify(&expr))
+        );
+    }
+    println!();
+}
+
+/// Demonstrate generating several target languages from one AST
+fn multi_target_demo() {
+    println!("🎯 Multi-Target Code Generation");
+    println!();
+
+    let examples = vec![
+        build_example_ast(),
+        Expr::BinOp {
+            op: BinOperator::IntDiv,
+            left: Box::new(Expr::Var("total".to_string())),
+            right: Box::new(Expr::Var("count".to_string())),
+        },
+        Expr::BinOp {
+            op: BinOperator::Pow,
+            left: Box::new(Expr::Var("x".to_string())),
+            right: Box::new(Expr::Int(2)),
+        },
+        Expr::If {
+            cond: Box::new(Expr::Bool(true)),
+            then: Box::new(Expr::Str("yes".to_string())),
+            els: Box::new(Expr::Str("no".to_string())),
+        },
+    ];
+
+    println!("   {:<34} │ Python", "Rust");
+    println!("   {}┼{}", "─".repeat(35), "─".repeat(30));
+    for expr in examples {
+        println!(
+            "   {:<34} │ {}",
+            generate_rust(&expr),
+            generate_python(&expr)
+        );
+    }
+    println!();
+}
+
+/// Evaluate expression (for verification)
+///
+/// Integer-only: true division `/` yields a Float (see `binop_type` and
+/// `evaluate_value`), so it has no value here; use `//` for integer division.
+fn evaluate(expr: &Expr, vars: &std::collections::HashMap<String, i64>) -> Option<i64> {
+    match expr {
+        Expr::Int(n) => Some(*n),
+        Expr::Var(name) => vars.get(name).copied(),
+        Expr::BinOp { op, left, right } => {
+            let l = evaluate(left, vars)?;
+            let r = evaluate(right, vars)?;
+            match op {
+                // Overflow, division by zero and `i64::MIN / -1` have no value
+                BinOperator::Add => l.checked_add(r),
+                BinOperator::Sub => l.checked_sub(r),
+                BinOperator::Mul => l.checked_mul(r),
+                BinOperator::Div => None,
+                BinOperator::IntDiv => l.checked_div(r),
+                BinOperator::Mod => l.checked_rem(r),
+                BinOperator::Pow => l.checked_pow(u32::try_from(r).ok()?),
+                // Comparisons evaluate to 1 (true) or 0 (false)
+                BinOperator::Lt => Some((l < r) as i64),
+                BinOperator::Le => Some((l <= r) as i64),
+                BinOperator::Gt => Some((l > r) as i64),
+                BinOperator::Ge => Some((l >= r) as i64),
+                BinOperator::Eq => Some((l == r) as i64),
+            }
+        }
+        // Truthiness: any non-zero integer selects the `then` branch
+        Expr::If { cond, then, els } => {
+            if evaluate(cond, vars)? != 0 {
+                evaluate(then, vars)
+            } else {
+                evaluate(els, vars)
+            }
+        }
+        // The binding is only visible inside `body`
+        Expr::Let { name, value, body } => {
+            let mut scope = vars.clone();
+            scope.insert(name.clone(), evaluate(value, vars)?);
+            evaluate(body, &scope)
+        }
+        _ => None, // Floats, strings, etc. not supported
+    }
+}
+
+/// Runtime value for the typed evaluator
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Bool(bool),
+}
+
+impl Value {
+    fn type_of(&self) -> Type {
+        match self {
+            Value::Int(_) => Type::Int,
+            Value::Float(_) => Type::Float,
+            Value::Str(_) => Type::Str,
+            Value::Bool(_) => Type::Bool,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Int(n) => Some(*n as f64),
+            Value::Float(x) => Some(*x),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{}", n),
+            Value::Float(x) => write!(f, "{:?}", x),
+            Value::Str(s) => write!(f, "{:?}", s),
+            Value::Bool(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+/// Runtime error from the typed evaluator
+#[derive(Debug, Clone, PartialEq)]
+enum EvalError {
+    UnboundVariable(String),
+    UnknownFunction(String),
+    InvalidOperands {
+        op: BinOperator,
+        left: Type,
+        right: Type,
+    },
+    NonBoolCondition(Type),
+    DivisionByZero,
+    Overflow,
+    /// Function called with the wrong number of arguments
+    Arity {
+        name: String,
+        expected: usize,
+        found: usize,
+    },
+    /// Function called with an argument of the wrong type
+    InvalidArgument {
+        name: String,
+        found: Type,
+    },
+    /// Indexing a value that is not a string, or with a non-integer index
+    NotIndexable {
+        target: Type,
+        index: Type,
+    },
+    IndexOutOfRange {
+        index: i64,
+        len: usize,
+    },
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::UnboundVariable(name) => write!(f, "unbound variable `{}`", name),
+            EvalError::UnknownFunction(name) => write!(f, "unknown function `{}`", name),
+            EvalError::InvalidOperands { op, left, right } => {
+                write!(f, "cannot apply `{}` to {} and {}", op, left, right)
+            }
+            EvalError::NonBoolCondition(found) => {
+                write!(f, "condition has type {}, expected bool", found)
+            }
+            EvalError::DivisionByZero => write!(f, "division by zero"),
+            EvalError::Overflow => write!(f, "integer overflow"),
+            EvalError::Arity {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "`{}` expects {} argument(s), got {}",
+                name, expected, found
+            ),
+            EvalError::InvalidArgument { name, found } => {
+                write!(f, "`{}` does not accept {}", name, found)
+            }
+            EvalError::NotIndexable { target, index } => {
+                write!(f, "cannot index {} with {}", target, index)
+            }
+            EvalError::IndexOutOfRange { index, len } => {
+                write!(f, "index {} out of range for length {}", index, len)
+            }
+        }
+    }
+}
+
+/// A callable available to `Expr::Call`
+type Function = Box<dyn Fn(&[Value]) -> Result<Value, EvalError>>;
+
+/// Named functions that `Expr::Call` resolves against
+#[derive(Default)]
+struct FunctionRegistry {
+    functions: HashMap<String, Function>,
+}
+
+impl FunctionRegistry {
+    /// Registry with the `max`, `min` and `abs` built-ins
+    fn with_builtins() -> Self {
+        let mut registry = Self::default();
+        registry.register("max", |args| extremum("max", args, true));
+        registry.register("min", |args| extremum("min", args, false));
+        registry.register("abs", |args| match args {
+            [Value::Int(n)] => n.checked_abs().map(Value::Int).ok_or(EvalError::Overflow),
+            [Value::Float(x)] => Ok(Value::Float(x.abs())),
+            [other] => Err(EvalError::InvalidArgument {
+                name: "abs".to_string(),
+                found: other.type_of(),
+            }),
+            _ => Err(EvalError::Arity {
+                name: "abs".to_string(),
+                expected: 1,
+                found: args.len(),
+            }),
+        });
+        registry
+    }
+
+    fn register(
+        &mut self,
+        name: &str,
+        function: impl Fn(&[Value]) -> Result<Value, EvalError> + 'static,
+    ) {
+        self.functions.insert(name.to_string(), Box::new(function));
+    }
+
+    /// Evaluate `expr`, resolving calls against this registry
+    ///
+    /// Follows the type rules of `infer_type`: mixed Int/Float arithmetic
+    /// promotes to Float, `/` is true division, `//` integer division, and
+    /// `+` concatenates strings.
+    fn evaluate(&self, expr: &Expr, vars: &HashMap<String, Value>) -> Result<Value, EvalError> {
+        match expr {
+            Expr::Int(n) => Ok(Value::Int(*n)),
+            Expr::Float(x) => Ok(Value::Float(*x)),
+            Expr::Str(s) => Ok(Value::Str(s.clone())),
+            Expr::Bool(b) => Ok(Value::Bool(*b)),
+            Expr::Var(name) => vars
+                .get(name)
+                .cloned()
+                .ok_or_else(|| EvalError::UnboundVariable(name.clone())),
+            Expr::BinOp { op, left, right } => {
+                let l = self.evaluate(left, vars)?;
+                let r = self.evaluate(right, vars)?;
+                apply_binop(op, l, r)
+            }
+            Expr::Call { name, args } => {
+                let function = self
+                    .functions
+                    .get(name)
+                    .ok_or_else(|| EvalError::UnknownFunction(name.clone()))?;
+                let args = args
+                    .iter()
+                    .map(|arg| self.evaluate(arg, vars))
+                    .collect::<Result<Vec<_>, _>>()?;
+                function(&args)
+            }
+            Expr::If { cond, then, els } => match self.evaluate(cond, vars)? {
+                Value::Bool(true) => self.evaluate(then, vars),
+                Value::Bool(false) => self.evaluate(els, vars),
+                other => Err(EvalError::NonBoolCondition(other.type_of())),
+            },
+            Expr::Let { name, value, body } => {
+                let mut scope = vars.clone();
+                scope.insert(name.clone(), self.evaluate(value, vars)?);
+                self.evaluate(body, &scope)
+            }
+            // Strings index by character
+            Expr::Index { array, index } => {
+                match (self.evaluate(array, vars)?, self.evaluate(index, vars)?) {
+                    (Value::Str(s), Value::Int(i)) => usize::try_from(i)
+                        .ok()
+                        .and_then(|i| s.chars().nth(i))
+                        .map(|c| Value::Str(c.to_string()))
+                        .ok_or(EvalError::IndexOutOfRange {
+                            index: i,
+                            len: s.chars().count(),
+                        }),
+                    (target, index) => Err(EvalError::NotIndexable {
+                        target: target.type_of(),
+                        index: index.type_of(),
+                    }),
+                }
+            }
+        }
+    }
+}
+
+/// `max`/`min` over one or more numbers (Int if all arguments are Int)
+fn extremum(name: &str, args: &[Value], want_max: bool) -> Result<Value, EvalError> {
+    if args.is_empty() {
+        return Err(EvalError::Arity {
+            name: name.to_string(),
+            expected: 1,
+            found: 0,
+        });
+    }
+    let ints: Option<Vec<i64>> = args
+        .iter()
+        .map(|a| match a {
+            Value::Int(n) => Some(*n),
+            _ => None,
+        })
+        .collect();
+    if let Some(ints) = ints {
+        let best = if want_max {
+            ints.iter().max()
+        } else {
+            ints.iter().min()
+        };
+        return Ok(Value::Int(*best.expect("args is non-empty")));
+    }
+
+    let mut best: Option<f64> = None;
+    for arg in args {
+        let x = arg.as_f64().ok_or_else(|| EvalError::InvalidArgument {
+            name: name.to_string(),
+            found: arg.type_of(),
+        })?;
+        best = Some(match best {
+            Some(b) if want_max => b.max(x),
+            Some(b) => b.min(x),
+            None => x,
+        });
+    }
+    Ok(Value::Float(best.expect("args is non-empty")))
+}
+
+/// Evaluate with full value support (ints, floats, strings, booleans)
+/// and the built-in functions
+fn evaluate_value(expr: &Expr, vars: &HashMap<String, Value>) -> Result<Value, EvalError> {
+    FunctionRegistry::with_builtins().evaluate(expr, vars)
+}
+
+fn apply_binop(op: &BinOperator, left: Value, right: Value) -> Result<Value, EvalError> {
+    let invalid = |l: &Value, r: &Value| EvalError::InvalidOperands {
+        op: op.clone(),
+        left: l.type_of(),
+        right: r.type_of(),
+    };
+    match (&left, &right) {
+        (Value::Int(l), Value::Int(r)) => {
+            let (l, r) = (*l, *r);
+            let zero_check = || {
+                if r == 0 {
+                    Err(EvalError::DivisionByZero)
+                } else {
+                    Ok(())
+                }
+            };
+            Ok(match op {
+                BinOperator::Add => Value::Int(l.checked_add(r).ok_or(EvalError::Overflow)?),
+                BinOperator::Sub => Value::Int(l.checked_sub(r).ok_or(EvalError::Overflow)?),
+                BinOperator::Mul => Value::Int(l.checked_mul(r).ok_or(EvalError::Overflow)?),
+                BinOperator::Div => {
+                    zero_check()?;
+                    Value::Float(l as f64 / r as f64)
+                }
+                BinOperator::IntDiv => {
+                    zero_check()?;
+                    Value::Int(l.checked_div(r).ok_or(EvalError::Overflow)?)
+                }
+                BinOperator::Mod => {
+                    zero_check()?;
+                    Value::Int(l.checked_rem(r).ok_or(EvalError::Overflow)?)
+                }
+                BinOperator::Pow => {
+                    let exp = u32::try_from(r).map_err(|_| invalid(&left, &right))?;
+                    Value::Int(l.checked_pow(exp).ok_or(EvalError::Overflow)?)
+                }
+                BinOperator::Lt => Value::Bool(l < r),
+                BinOperator::Le => Value::Bool(l <= r),
+                BinOperator::Gt => Value::Bool(l > r),
+                BinOperator::Ge => Value::Bool(l >= r),
+                BinOperator::Eq => Value::Bool(l == r),
+            })
+        }
+        (Value::Str(l), Value::Str(r)) => match op {
+            BinOperator::Add => Ok(Value::Str(format!("{}{}", l, r))),
+            BinOperator::Lt => Ok(Value::Bool(l < r)),
+            BinOperator::Le => Ok(Value::Bool(l <= r)),
+            BinOperator::Gt => Ok(Value::Bool(l > r)),
+            BinOperator::Ge => Ok(Value::Bool(l >= r)),
+            BinOperator::Eq => Ok(Value::Bool(l == r)),
+            _ => Err(invalid(&left, &right)),
+        },
+        (Value::Bool(l), Value::Bool(r)) if *op == BinOperator::Eq => Ok(Value::Bool(l == r)),
+        _ => {
+            // Promote mixed Int/Float operands to Float
+            let (Some(l), Some(r)) = (left.as_f64(), right.as_f64()) else {
+                return Err(invalid(&left, &right));
+            };
+            if r == 0.0
+                && matches!(
+                    op,
+                    BinOperator::Div | BinOperator::IntDiv | BinOperator::Mod
+                )
+            {
+                return Err(EvalError::DivisionByZero);
+            }
+            Ok(match op {
+                BinOperator::Add => Value::Float(l + r),
+                BinOperator::Sub => Value::Float(l - r),
+                BinOperator::Mul => Value::Float(l * r),
+                BinOperator::Div => Value::Float(l / r),
+                BinOperator::IntDiv => Value::Float((l / r).trunc()),
+                BinOperator::Mod => Value::Float(l % r),
+                BinOperator::Pow => Value::Float(l.powf(r)),
+                BinOperator::Lt => Value::Bool(l < r),
+                BinOperator::Le => Value::Bool(l <= r),
+                BinOperator::Gt => Value::Bool(l > r),
+                BinOperator::Ge => Value::Bool(l >= r),
+                BinOperator::Eq => Value::Bool(l == r),
+            })
+        }
+    }
+}
+
+/// Demonstrate typed evaluation beyond integers
+fn typed_evaluation_demo() {
+    println!("🧮 Typed Evaluation");
+    println!();
+
+    let bin = |op, l: Expr, r: Expr| Expr::BinOp {
+        op,
+        left: Box::new(l),
+        right: Box::new(r),
+    };
+    let examples = vec![
+        bin(BinOperator::Add, Expr::Float(1.5), Expr::Int(2)),
+        bin(BinOperator::Div, Expr::Int(1), Expr::Int(2)),
+        bin(
+            BinOperator::Add,
+            Expr::Str("sovereign".to_string()),
+            Expr::Str("-ai".to_string()),
+        ),
+        bin(
+            BinOperator::Lt,
+            Expr::Var("x".to_string()),
+            Expr::Float(2.5),
+        ),
+        bin(BinOperator::IntDiv, Expr::Int(1), Expr::Int(0)),
+        Expr::Call {
+            name: "max".to_string(),
+            args: vec![Expr::Var("x".to_string()), Expr::Int(5)],
+        },
+        Expr::Call {
+            name: "abs".to_string(),
+            args: vec![Expr::Float(-0.5)],
+        },
+        Expr::Index {
+            array: Box::new(Expr::Str("ai".to_string())),
+            index: Box::new(Expr::Var("x".to_string())),
+        },
+    ];
+    let vars = HashMap::from([("x".to_string(), Value::Int(2))]);
+
+    println!("   With x = 2");
+    for expr in examples {
+        let result = match evaluate_value(&expr, &vars) {
+            Ok(value) => format!("{} ({})", value, value.type_of()),
+            Err(e) => format!("error: {}", e),
+        };
+        println!("   {:<22} → {}", generate_rust(&expr), result);
+    }
+    println!();
+}
+
+/// Demonstrate catching the off-by-one loop from Chapter 2 statically
+fn index_lint_demo() {
+    println!("🔎 Off-by-one Detection");
+    println!();
+
+    let var = |name: &str| Expr::Var(name.to_string());
+    let loop_over = |end: Expr| Expr::Let {
+        name: "i".to_string(),
+        value: Box::new(Expr::Call {
+            name: "range".to_string(),
+            args: vec![Expr::Int(0), end],
+        }),
+        body: Box::new(Expr::Index {
+            array: Box::new(var("items")),
+            index: Box::new(var("i")),
+        }),
+    };
+    let len_plus = |k: i64| Expr::BinOp {
+        op: BinOperator::Add,
+        left: Box::new(var("len")),
+        right: Box::new(Expr::Int(k)),
+    };
+
+    let cases = vec![
+        ("for i in range(len + 1)", loop_over(len_plus(1))),
+        ("for i in range(len)", loop_over(var("len"))),
+        (
+            "items[len]",
+            Expr::Index {
+                array: Box::new(var("items")),
+                index: Box::new(var("len")),
+            },
+        ),
+    ];
+
+    for (label, expr) in cases {
+        let lints = detect_index_out_of_range(&expr, "len");
+        if lints.is_empty() {
+            println!("   {:<24} ✅ no findings", label);
+        }
+        for lint in lints {
+            println!("   {:<24} ❌ {}: {}", label, lint.expr, lint.message);
+        }
+    }
+    println!();
+}
+
+/// Demonstrate semantic preservation through evaluation
+fn semantic_preservation_demo() {
+    println!("✅ Semantic Preservation");
+    println!();
+
+    let expr = build_example_ast(); // x + y * 2
+
+    let mut vars = std::collections::HashMap::new();
+
+    let test_cases = vec![
+        (5, 3, 11),  // 5 + 3 * 2 = 11
+        (0, 10, 20), // 0 + 10 * 2 = 20
+        (10, -2, 6), // 10 + (-2) * 2 = 6
+    ];
+
+    println!("   Testing: x + y * 2");
+    println!();
+    println!(
+        "   {:>5} {:>5} │ {:>8} │ {:>8}",
+        "x", "y", "Expected", "Actual"
     );
     println!("   ────────────┼──────────┼──────────");
 
@@ -324,7 +1806,7 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
-    ast_structure_demo();
+    ast_structure_demo()?;
     println!("{}", "─".repeat(70));
     println!();
 
@@ -336,6 +1818,22 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
+    multi_target_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
+    constant_folding_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
+    typed_evaluation_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
+    index_lint_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
     semantic_preservation_demo();
     println!("{}", "─".repeat(70));
     println!();
@@ -389,7 +1887,7 @@ mod tests {
             left: Box::new(Expr::Var("x".to_string())),
             right: Box::new(Expr::Int(1)),
         };
-        let code = generate_rust(&expr);
+        let code = generate_rust_verbose(&expr);
         assert_eq!(code, "(x + 1)");
     }
 
@@ -417,4 +1915,1049 @@ mod tests {
             assert_eq!(result, expected, "x={}, y={}", x, y);
         }
     }
+
+    #[test]
+    fn test_new_operators_codegen() {
+        let modulo = Expr::BinOp {
+            op: BinOperator::Mod,
+            left: Box::new(Expr::Var("x".to_string())),
+            right: Box::new(Expr::Int(2)),
+        };
+        assert_eq!(generate_rust_verbose(&modulo), "(x % 2)");
+        assert_eq!(generate_rust(&modulo), "x % 2");
+
+        let less = Expr::BinOp {
+            op: BinOperator::Lt,
+            left: Box::new(Expr::Var("a".to_string())),
+            right: Box::new(Expr::Var("b".to_string())),
+        };
+        assert_eq!(generate_rust_verbose(&less), "(a < b)");
+
+        let pow = Expr::BinOp {
+            op: BinOperator::Pow,
+            left: Box::new(Expr::Var("x".to_string())),
+            right: Box::new(Expr::Int(3)),
+        };
+        assert_eq!(generate_rust(&pow), "x.pow(3)");
+
+        let var_pow = Expr::BinOp {
+            op: BinOperator::Pow,
+            left: Box::new(Expr::Int(2)),
+            right: Box::new(Expr::Var("n".to_string())),
+        };
+        assert_eq!(generate_rust(&var_pow), "2i64.pow(n as u32)");
+    }
+
+    #[test]
+    fn test_comparison_infers_bool() {
+        let cmp = Expr::BinOp {
+            op: BinOperator::Ge,
+            left: Box::new(Expr::Int(3)),
+            right: Box::new(Expr::Float(2.0)),
+        };
+        assert_eq!(infer_type(&cmp), Type::Bool);
+
+        let mismatched = Expr::BinOp {
+            op: BinOperator::Eq,
+            left: Box::new(Expr::Str("a".to_string())),
+            right: Box::new(Expr::Int(1)),
+        };
+        assert_eq!(infer_type(&mismatched), Type::Unknown);
+
+        let pow = Expr::BinOp {
+            op: BinOperator::Pow,
+            left: Box::new(Expr::Int(2)),
+            right: Box::new(Expr::Int(10)),
+        };
+        assert_eq!(infer_type(&pow), Type::Int);
+    }
+
+    #[test]
+    fn test_evaluate_new_operators() {
+        let vars = std::collections::HashMap::new();
+        let bin = |op, l, r| Expr::BinOp {
+            op,
+            left: Box::new(Expr::Int(l)),
+            right: Box::new(Expr::Int(r)),
+        };
+        assert_eq!(evaluate(&bin(BinOperator::Mod, 7, 3), &vars), Some(1));
+        assert_eq!(evaluate(&bin(BinOperator::Pow, 2, 10), &vars), Some(1024));
+        assert_eq!(evaluate(&bin(BinOperator::Pow, 2, -1), &vars), None);
+        assert_eq!(evaluate(&bin(BinOperator::Lt, 1, 2), &vars), Some(1));
+        assert_eq!(evaluate(&bin(BinOperator::Eq, 1, 2), &vars), Some(0));
+    }
+
+    #[test]
+    fn test_evaluate_division_by_zero_is_none() {
+        let vars = std::collections::HashMap::new();
+        let bin = |op, l, r| Expr::BinOp {
+            op,
+            left: Box::new(Expr::Int(l)),
+            right: Box::new(Expr::Int(r)),
+        };
+        assert_eq!(evaluate(&bin(BinOperator::Mod, 7, 0), &vars), None);
+        assert_eq!(evaluate(&bin(BinOperator::Div, 7, 0), &vars), None);
+        assert_eq!(evaluate(&bin(BinOperator::IntDiv, 7, 0), &vars), None);
+        assert_eq!(evaluate(&bin(BinOperator::Mod, i64::MIN, -1), &vars), None);
+        assert_eq!(
+            evaluate(&bin(BinOperator::IntDiv, i64::MIN, -1), &vars),
+            None
+        );
+    }
+
+    #[test]
+    fn test_evaluate_overflow_is_none() {
+        let vars = std::collections::HashMap::new();
+        let bin = |op, l, r| Expr::BinOp {
+            op,
+            left: Box::new(Expr::Int(l)),
+            right: Box::new(Expr::Int(r)),
+        };
+        assert_eq!(evaluate(&bin(BinOperator::Add, i64::MAX, 1), &vars), None);
+        assert_eq!(evaluate(&bin(BinOperator::Sub, i64::MIN, 1), &vars), None);
+        assert_eq!(evaluate(&bin(BinOperator::Mul, i64::MAX, 2), &vars), None);
+        assert_eq!(evaluate(&bin(BinOperator::Mul, i64::MIN, -1), &vars), None);
+        assert_eq!(
+            evaluate(&bin(BinOperator::Add, i64::MAX, 0), &vars),
+            Some(i64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_infer_type_env() {
+        let env = HashMap::from([("x".to_string(), Type::Int), ("r".to_string(), Type::Float)]);
+
+        assert_eq!(infer_type_env(&Expr::Var("x".to_string()), &env), Type::Int);
+        assert_eq!(
+            infer_type_env(&Expr::Var("missing".to_string()), &env),
+            Type::Unknown
+        );
+        assert_eq!(infer_type(&Expr::Var("x".to_string())), Type::Unknown);
+
+        let x_plus_one = Expr::BinOp {
+            op: BinOperator::Add,
+            left: Box::new(Expr::Var("x".to_string())),
+            right: Box::new(Expr::Int(1)),
+        };
+        assert_eq!(infer_type_env(&x_plus_one, &env), Type::Int);
+
+        let r_times_x = Expr::BinOp {
+            op: BinOperator::Mul,
+            left: Box::new(Expr::Var("r".to_string())),
+            right: Box::new(Expr::Var("x".to_string())),
+        };
+        assert_eq!(infer_type_env(&r_times_x, &env), Type::Float);
+    }
+
+    #[test]
+    fn test_fold_nested_literals() {
+        // (1 + 2) * (10 - 4) → 18
+        let expr = Expr::BinOp {
+            op: BinOperator::Mul,
+            left: Box::new(Expr::BinOp {
+                op: BinOperator::Add,
+                left: Box::new(Expr::Int(1)),
+                right: Box::new(Expr::Int(2)),
+            }),
+            right: Box::new(Expr::BinOp {
+                op: BinOperator::Sub,
+                left: Box::new(Expr::Int(10)),
+                right: Box::new(Expr::Int(4)),
+            }),
+        };
+        assert_eq!(fold_constants(&expr), Expr::Int(18));
+
+        let mixed = Expr::BinOp {
+            op: BinOperator::Add,
+            left: Box::new(Expr::Float(1.5)),
+            right: Box::new(Expr::Int(2)),
+        };
+        assert_eq!(fold_constants(&mixed), Expr::Float(3.5));
+    }
+
+    #[test]
+    fn test_fold_partial() {
+        // x * (2 + 3) → x * 5
+        let expr = Expr::BinOp {
+            op: BinOperator::Mul,
+            left: Box::new(Expr::Var("x".to_string())),
+            right: Box::new(Expr::BinOp {
+                op: BinOperator::Add,
+                left: Box::new(Expr::Int(2)),
+                right: Box::new(Expr::Int(3)),
+            }),
+        };
+        let expected = Expr::BinOp {
+            op: BinOperator::Mul,
+            left: Box::new(Expr::Var("x".to_string())),
+            right: Box::new(Expr::Int(5)),
+        };
+        assert_eq!(fold_constants(&expr), expected);
+
+        let vars = HashMap::from([("x".to_string(), 7)]);
+        assert_eq!(evaluate(&fold_constants(&expr), &vars), Some(35));
+    }
+
+    #[test]
+    fn test_fold_division_by_zero_left_alone() {
+        let expr = Expr::BinOp {
+            op: BinOperator::Div,
+            left: Box::new(Expr::Int(1)),
+            right: Box::new(Expr::Int(0)),
+        };
+        assert_eq!(fold_constants(&expr), expr);
+
+        let overflow = Expr::BinOp {
+            op: BinOperator::Mul,
+            left: Box::new(Expr::Int(i64::MAX)),
+            right: Box::new(Expr::Int(2)),
+        };
+        assert_eq!(fold_constants(&overflow), overflow);
+    }
+
+    #[test]
+    fn test_fold_true_division_preserves_value() {
+        // 1 / 2 is Float 0.5, not integer 0
+        let half = Expr::BinOp {
+            op: BinOperator::Div,
+            left: Box::new(Expr::Int(1)),
+            right: Box::new(Expr::Int(2)),
+        };
+        let vars = HashMap::new();
+        assert_eq!(fold_constants(&half), Expr::Float(0.5));
+        assert_eq!(
+            evaluate_value(&fold_constants(&half), &vars),
+            evaluate_value(&half, &vars)
+        );
+
+        let floor = Expr::BinOp {
+            op: BinOperator::IntDiv,
+            left: Box::new(Expr::Int(7)),
+            right: Box::new(Expr::Int(2)),
+        };
+        assert_eq!(fold_constants(&floor), Expr::Int(3));
+    }
+
+    #[test]
+    fn test_fold_agrees_with_evaluate() {
+        // The integer evaluator must give the same answer before and after folding
+        let vars = HashMap::new();
+        for op in [
+            BinOperator::Add,
+            BinOperator::Sub,
+            BinOperator::Mul,
+            BinOperator::Div,
+            BinOperator::IntDiv,
+            BinOperator::Mod,
+        ] {
+            for (l, r) in [(7, 2), (6, 2), (-7, 2), (1, 0), (i64::MAX, 2)] {
+                let expr = Expr::BinOp {
+                    op: op.clone(),
+                    left: Box::new(Expr::Int(l)),
+                    right: Box::new(Expr::Int(r)),
+                };
+                assert_eq!(
+                    evaluate(&fold_constants(&expr), &vars),
+                    evaluate(&expr, &vars),
+                    "fold changes evaluate on {}",
+                    generate_rust(&expr)
+                );
+            }
+        }
+        let div = Expr::BinOp {
+            op: BinOperator::Div,
+            left: Box::new(Expr::Int(7)),
+            right: Box::new(Expr::Int(2)),
+        };
+        assert_eq!(evaluate(&div, &vars), None);
+    }
+
+    #[test]
+    fn test_fold_agrees_with_evaluate_value() {
+        // Whenever fold_constants produces a result, it must be the value
+        // apply_binop computes for the same AST
+        let ops = [
+            BinOperator::Add,
+            BinOperator::Sub,
+            BinOperator::Mul,
+            BinOperator::Div,
+            BinOperator::IntDiv,
+            BinOperator::Mod,
+            BinOperator::Pow,
+            BinOperator::Lt,
+            BinOperator::Le,
+            BinOperator::Gt,
+            BinOperator::Ge,
+            BinOperator::Eq,
+        ];
+        let literals = [
+            Expr::Int(-7),
+            Expr::Int(0),
+            Expr::Int(1),
+            Expr::Int(2),
+            Expr::Float(-1.5),
+            Expr::Float(0.0),
+            Expr::Float(2.5),
+        ];
+        let vars = HashMap::new();
+        for op in &ops {
+            for left in &literals {
+                for right in &literals {
+                    let expr = Expr::BinOp {
+                        op: op.clone(),
+                        left: Box::new(left.clone()),
+                        right: Box::new(right.clone()),
+                    };
+                    let folded = fold_constants(&expr);
+                    if folded != expr {
+                        assert_eq!(
+                            evaluate_value(&folded, &vars),
+                            evaluate_value(&expr, &vars),
+                            "fold and evaluate disagree on {}",
+                            generate_rust(&expr)
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_minimal_parentheses() {
+        let var = |n: &str| Box::new(Expr::Var(n.to_string()));
+        let bin = |op, left, right| Expr::BinOp { op, left, right };
+
+        // x + y * 2 needs no parens
+        assert_eq!(generate_rust(&build_example_ast()), "x + y * 2");
+        assert_eq!(generate_rust_verbose(&build_example_ast()), "(x + (y * 2))");
+
+        // (x + y) * 2 keeps its required parens
+        let sum_times = bin(
+            BinOperator::Mul,
+            Box::new(bin(BinOperator::Add, var("x"), var("y"))),
+            Box::new(Expr::Int(2)),
+        );
+        assert_eq!(generate_rust(&sum_times), "(x + y) * 2");
+
+        // Left-associative: a - b - c vs a - (b - c)
+        let left_nested = bin(
+            BinOperator::Sub,
+            Box::new(bin(BinOperator::Sub, var("a"), var("b"))),
+            var("c"),
+        );
+        assert_eq!(generate_rust(&left_nested), "a - b - c");
+        let right_nested = bin(
+            BinOperator::Sub,
+            var("a"),
+            Box::new(bin(BinOperator::Sub, var("b"), var("c"))),
+        );
+        assert_eq!(generate_rust(&right_nested), "a - (b - c)");
+
+        // Comparisons never chain; .pow() receivers bind tightest
+        let cmp = bin(
+            BinOperator::Eq,
+            Box::new(bin(BinOperator::Lt, var("a"), var("b"))),
+            Box::new(Expr::Bool(true)),
+        );
+        assert_eq!(generate_rust(&cmp), "(a < b) == true");
+        let pow = bin(
+            BinOperator::Pow,
+            Box::new(bin(BinOperator::Add, var("a"), var("b"))),
+            Box::new(bin(BinOperator::Sub, var("n"), Box::new(Expr::Int(1)))),
+        );
+        assert_eq!(generate_rust(&pow), "(a + b).pow((n - 1) as u32)");
+    }
+
+    /// if x > 0 { x } else { 0 - x }
+    fn abs_if() -> Expr {
+        Expr::If {
+            cond: Box::new(Expr::BinOp {
+                op: BinOperator::Gt,
+                left: Box::new(Expr::Var("x".to_string())),
+                right: Box::new(Expr::Int(0)),
+            }),
+            then: Box::new(Expr::Var("x".to_string())),
+            els: Box::new(Expr::BinOp {
+                op: BinOperator::Sub,
+                left: Box::new(Expr::Int(0)),
+                right: Box::new(Expr::Var("x".to_string())),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_if_type_unification() {
+        let env = HashMap::from([("x".to_string(), Type::Int)]);
+        assert_eq!(infer_type_env(&abs_if(), &env), Type::Int);
+
+        let mismatched = Expr::If {
+            cond: Box::new(Expr::Bool(true)),
+            then: Box::new(Expr::Int(1)),
+            els: Box::new(Expr::Str("one".to_string())),
+        };
+        assert_eq!(infer_type(&mismatched), Type::Unknown);
+
+        let non_bool_cond = Expr::If {
+            cond: Box::new(Expr::Int(1)),
+            then: Box::new(Expr::Int(1)),
+            els: Box::new(Expr::Int(2)),
+        };
+        assert_eq!(infer_type(&non_bool_cond), Type::Unknown);
+    }
+
+    #[test]
+    fn test_if_branch_selection() {
+        let expr = abs_if();
+        for (x, expected) in [(5, 5), (-3, 3), (0, 0)] {
+            let vars = HashMap::from([("x".to_string(), x)]);
+            assert_eq!(evaluate(&expr, &vars), Some(expected), "x={}", x);
+        }
+
+        assert_eq!(generate_rust(&expr), "if x > 0 { x } else { 0 - x }");
+
+        // Literal conditions fold away the untaken branch
+        let literal = Expr::If {
+            cond: Box::new(Expr::BinOp {
+                op: BinOperator::Lt,
+                left: Box::new(Expr::Int(1)),
+                right: Box::new(Expr::Int(2)),
+            }),
+            then: Box::new(Expr::Var("a".to_string())),
+            els: Box::new(Expr::Var("b".to_string())),
+        };
+        assert_eq!(fold_constants(&literal), Expr::Var("a".to_string()));
+    }
+
+    /// let x = 5 in x + 1
+    fn let_x_plus_one() -> Expr {
+        Expr::Let {
+            name: "x".to_string(),
+            value: Box::new(Expr::Int(5)),
+            body: Box::new(Expr::BinOp {
+                op: BinOperator::Add,
+                left: Box::new(Expr::Var("x".to_string())),
+                right: Box::new(Expr::Int(1)),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_let_binding() {
+        let expr = let_x_plus_one();
+        assert_eq!(evaluate(&expr, &HashMap::new()), Some(6));
+        assert_eq!(infer_type(&expr), Type::Int);
+        assert_eq!(generate_rust(&expr), "{ let x = 5; x + 1 }");
+
+        // Inner binding shadows an outer x
+        let outer = HashMap::from([("x".to_string(), 100)]);
+        assert_eq!(evaluate(&expr, &outer), Some(6));
+    }
+
+    #[test]
+    fn test_let_does_not_leak() {
+        // (let x = 5 in x + 1) + x
+        let expr = Expr::BinOp {
+            op: BinOperator::Add,
+            left: Box::new(let_x_plus_one()),
+            right: Box::new(Expr::Var("x".to_string())),
+        };
+        assert_eq!(evaluate(&expr, &HashMap::new()), None);
+
+        let outer = HashMap::from([("x".to_string(), 10)]);
+        assert_eq!(evaluate(&expr, &outer), Some(16));
+        assert_eq!(generate_rust(&expr), "({ let x = 5; x + 1 }) + x");
+        assert_eq!(infer_type(&expr), Type::Unknown);
+    }
+
+    #[test]
+    fn test_rust_true_division_casts_operands() {
+        let var = |n: &str| Box::new(Expr::Var(n.to_string()));
+        let bin = |op, left, right| Box::new(Expr::BinOp { op, left, right });
+        let a_div_b = || bin(BinOperator::Div, var("a"), var("b"));
+
+        // Casts are parenthesized: `a as f64 < c` would parse `<` as generics
+        let cmp = bin(BinOperator::Lt, a_div_b(), var("c"));
+        assert_eq!(generate_rust(&cmp), "(a as f64) / (b as f64) < (c as f64)");
+
+        // The integer sibling of an f64 division is promoted too
+        let plus_one = bin(BinOperator::Add, a_div_b(), Box::new(Expr::Int(1)));
+        assert_eq!(generate_rust(&plus_one), "(a as f64) / (b as f64) + 1.0");
+        assert_eq!(
+            generate_rust_verbose(&plus_one),
+            "(((a as f64) / (b as f64)) + 1.0)"
+        );
+
+        // ... through any number of arithmetic levels
+        let nested = bin(BinOperator::Ge, plus_one, var("c"));
+        assert_eq!(
+            generate_rust(&nested),
+            "(a as f64) / (b as f64) + 1.0 >= (c as f64)"
+        );
+
+        let sum = bin(BinOperator::Add, var("x"), Box::new(Expr::Int(1)));
+        let expr = bin(BinOperator::Div, sum, Box::new(Expr::Float(2.0)));
+        assert_eq!(generate_rust(&expr), "((x + 1) as f64) / 2.0");
+        assert_eq!(generate_rust_verbose(&expr), "(((x + 1) as f64) / 2.0)");
+
+        // Integer-only arithmetic is left alone
+        assert_eq!(generate_rust(&build_example_ast()), "x + y * 2");
+    }
+
+    #[test]
+    fn test_negative_remainder_semantics() {
+        // Python's `%` and `//` floor; the generated code must truncate like Rust
+        let bin = |op| Expr::BinOp {
+            op,
+            left: Box::new(Expr::Int(-7)),
+            right: Box::new(Expr::Int(2)),
+        };
+        let rem = bin(BinOperator::Mod);
+        assert_eq!(generate_rust(&rem), "-7 % 2");
+        assert_eq!(generate_python(&rem), "math.fmod(-7, 2)"); // -1.0, not 1
+        assert_eq!(evaluate_value(&rem, &HashMap::new()), Ok(Value::Int(-1)));
+
+        let quot = bin(BinOperator::IntDiv);
+        assert_eq!(generate_rust(&quot), "-7 / 2");
+        assert_eq!(generate_python(&quot), "int(-7 / 2)"); // -3, not -4
+        assert_eq!(evaluate_value(&quot, &HashMap::new()), Ok(Value::Int(-3)));
+
+        // Calls need no parentheses as operands
+        let sum = Expr::BinOp {
+            op: BinOperator::Mul,
+            left: Box::new(rem),
+            right: Box::new(Expr::Var("x".to_string())),
+        };
+        assert_eq!(generate_python(&sum), "math.fmod(-7, 2) * x");
+    }
+
+    #[test]
+    fn test_generate_python() {
+        let bin = |op, l: Expr, r: Expr| Expr::BinOp {
+            op,
+            left: Box::new(l),
+            right: Box::new(r),
+        };
+
+        // True division is `/` in both targets; integer division truncates
+        let div = bin(BinOperator::Div, Expr::Int(1), Expr::Int(2));
+        assert_eq!(generate_python(&div), "1 / 2");
+        assert_eq!(generate_rust(&div), "1.0 / 2.0");
+        let int_div = bin(BinOperator::IntDiv, Expr::Int(7), Expr::Int(2));
+        assert_eq!(generate_python(&int_div), "int(7 / 2)");
+        assert_eq!(generate_rust(&int_div), "7 / 2");
+
+        assert_eq!(generate_python(&Expr::Bool(true)), "True");
+        assert_eq!(generate_python(&Expr::Bool(false)), "False");
+        assert_eq!(
+            generate_python(&Expr::Str("say \"hi\"\n".to_string())),
+            "\"say \\\"hi\\\"\\n\""
+        );
+
+        assert_eq!(generate_python(&build_example_ast()), "x + y * 2");
+        let pow = bin(BinOperator::Pow, Expr::Int(-2), Expr::Var("n".to_string()));
+        assert_eq!(generate_python(&pow), "(-2) ** n");
+        let cmp = bin(
+            BinOperator::Eq,
+            bin(
+                BinOperator::Lt,
+                Expr::Var("a".to_string()),
+                Expr::Var("b".to_string()),
+            ),
+            Expr::Bool(true),
+        );
+        assert_eq!(generate_python(&cmp), "(a < b) == True");
+    }
+
+    #[test]
+    fn test_generate_python_if_let() {
+        let cond = Expr::If {
+            cond: Box::new(Expr::Var("ok".to_string())),
+            then: Box::new(Expr::Int(1)),
+            els: Box::new(Expr::Int(0)),
+        };
+        assert_eq!(generate_python(&cond), "1 if ok else 0");
+        assert_eq!(generate_python(&let_x_plus_one()), "(lambda x: x + 1)(5)");
+    }
+
+    #[test]
+    fn test_type_check_mismatch() {
+        let env = HashMap::new();
+        let bad = Expr::BinOp {
+            op: BinOperator::Add,
+            left: Box::new(Expr::Str("a".to_string())),
+            right: Box::new(Expr::Int(1)),
+        };
+        assert_eq!(
+            type_check(&bad, &env),
+            Err(TypeError::OperandMismatch {
+                op: BinOperator::Add,
+                left: Type::Str,
+                right: Type::Int,
+                expr: "\"a\" + 1".to_string(),
+            })
+        );
+        // infer_type silently degrades instead
+        assert_eq!(infer_type(&bad), Type::Unknown);
+
+        // The error points at the offending subtree, not the root
+        let nested = Expr::BinOp {
+            op: BinOperator::Mul,
+            left: Box::new(Expr::Float(2.0)),
+            right: Box::new(bad),
+        };
+        match type_check(&nested, &env) {
+            Err(TypeError::OperandMismatch { expr, .. }) => assert_eq!(expr, "\"a\" + 1"),
+            other => panic!("expected operand mismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_type_check_ok() {
+        let env = HashMap::from([("x".to_string(), Type::Int), ("y".to_string(), Type::Int)]);
+        assert_eq!(type_check(&build_example_ast(), &env), Ok(Type::Int));
+        assert_eq!(
+            type_check(&let_x_plus_one(), &HashMap::new()),
+            Ok(Type::Int)
+        );
+
+        assert_eq!(
+            type_check(&build_example_ast(), &HashMap::new()),
+            Err(TypeError::UnboundVariable("x".to_string()))
+        );
+        let non_bool = Expr::If {
+            cond: Box::new(Expr::Int(1)),
+            then: Box::new(Expr::Int(1)),
+            els: Box::new(Expr::Int(2)),
+        };
+        assert!(matches!(
+            type_check(&non_bool, &env),
+            Err(TypeError::NonBoolCondition {
+                found: Type::Int,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_value_promotion() {
+        let vars = HashMap::new();
+        let sum = Expr::BinOp {
+            op: BinOperator::Add,
+            left: Box::new(Expr::Float(1.5)),
+            right: Box::new(Expr::Int(2)),
+        };
+        assert_eq!(evaluate_value(&sum, &vars), Ok(Value::Float(3.5)));
+        assert_eq!(
+            evaluate_value(&sum, &vars).map(|v| v.type_of()),
+            Ok(infer_type(&sum))
+        );
+
+        let concat = Expr::BinOp {
+            op: BinOperator::Add,
+            left: Box::new(Expr::Str("a".to_string())),
+            right: Box::new(Expr::Str("b".to_string())),
+        };
+        assert_eq!(
+            evaluate_value(&concat, &vars),
+            Ok(Value::Str("ab".to_string()))
+        );
+        assert_eq!(infer_type(&concat), Type::Str);
+    }
+
+    #[test]
+    fn test_evaluate_value_errors() {
+        let vars = HashMap::from([("x".to_string(), Value::Int(3))]);
+        let bad = Expr::BinOp {
+            op: BinOperator::Mul,
+            left: Box::new(Expr::Str("a".to_string())),
+            right: Box::new(Expr::Var("x".to_string())),
+        };
+        assert_eq!(
+            evaluate_value(&bad, &vars),
+            Err(EvalError::InvalidOperands {
+                op: BinOperator::Mul,
+                left: Type::Str,
+                right: Type::Int,
+            })
+        );
+
+        let div_zero = Expr::BinOp {
+            op: BinOperator::Mod,
+            left: Box::new(Expr::Var("x".to_string())),
+            right: Box::new(Expr::Int(0)),
+        };
+        assert_eq!(
+            evaluate_value(&div_zero, &vars),
+            Err(EvalError::DivisionByZero)
+        );
+        assert_eq!(
+            evaluate_value(&Expr::Var("y".to_string()), &vars),
+            Err(EvalError::UnboundVariable("y".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_builtin_calls() {
+        let max_x_5 = Expr::Call {
+            name: "max".to_string(),
+            args: vec![Expr::Var("x".to_string()), Expr::Int(5)],
+        };
+        for (x, expected) in [(7, 7), (3, 5), (-2, 5)] {
+            let vars = HashMap::from([("x".to_string(), Value::Int(x))]);
+            assert_eq!(evaluate_value(&max_x_5, &vars), Ok(Value::Int(expected)));
+        }
+
+        let vars = HashMap::from([("x".to_string(), Value::Float(7.5))]);
+        assert_eq!(evaluate_value(&max_x_5, &vars), Ok(Value::Float(7.5)));
+
+        let min_abs = Expr::Call {
+            name: "min".to_string(),
+            args: vec![
+                Expr::Call {
+                    name: "abs".to_string(),
+                    args: vec![Expr::Int(-3)],
+                },
+                Expr::Int(4),
+            ],
+        };
+        assert_eq!(evaluate_value(&min_abs, &HashMap::new()), Ok(Value::Int(3)));
+    }
+
+    #[test]
+    fn test_call_errors_and_registration() {
+        let vars = HashMap::new();
+        let unknown = Expr::Call {
+            name: "sqrt".to_string(),
+            args: vec![Expr::Int(4)],
+        };
+        assert_eq!(
+            evaluate_value(&unknown, &vars),
+            Err(EvalError::UnknownFunction("sqrt".to_string()))
+        );
+
+        let bad_arity = Expr::Call {
+            name: "abs".to_string(),
+            args: vec![],
+        };
+        assert!(matches!(
+            evaluate_value(&bad_arity, &vars),
+            Err(EvalError::Arity {
+                expected: 1,
+                found: 0,
+                ..
+            })
+        ));
+
+        let mut registry = FunctionRegistry::with_builtins();
+        registry.register("sqrt", |args| match args {
+            [v] => v
+                .as_f64()
+                .map(|x| Value::Float(x.sqrt()))
+                .ok_or(EvalError::InvalidArgument {
+                    name: "sqrt".to_string(),
+                    found: v.type_of(),
+                }),
+            _ => Err(EvalError::Arity {
+                name: "sqrt".to_string(),
+                expected: 1,
+                found: args.len(),
+            }),
+        });
+        assert_eq!(registry.evaluate(&unknown, &vars), Ok(Value::Float(2.0)));
+    }
+
+    /// Expressions exercising every simplification rule
+    fn simplification_cases() -> Vec<Expr> {
+        let var = |n: &str| Box::new(Expr::Var(n.to_string()));
+        let int = |n| Box::new(Expr::Int(n));
+        let bin = |op, left, right| Box::new(Expr::BinOp { op, left, right });
+        vec![
+            // (x + 0) * 1
+            *bin(
+                BinOperator::Mul,
+                bin(BinOperator::Add, var("x"), int(0)),
+                int(1),
+            ),
+            // 0 + y * 0
+            *bin(
+                BinOperator::Add,
+                int(0),
+                bin(BinOperator::Mul, var("y"), int(0)),
+            ),
+            // (x * y - x * y) + y
+            *bin(
+                BinOperator::Add,
+                bin(
+                    BinOperator::Sub,
+                    bin(BinOperator::Mul, var("x"), var("y")),
+                    bin(BinOperator::Mul, var("x"), var("y")),
+                ),
+                var("y"),
+            ),
+            // 1 * (x - 0) + (y - x) * 1
+            *bin(
+                BinOperator::Add,
+                bin(
+                    BinOperator::Mul,
+                    int(1),
+                    bin(BinOperator::Sub, var("x"), int(0)),
+                ),
+                bin(
+                    BinOperator::Mul,
+                    bin(BinOperator::Sub, var("y"), var("x")),
+                    int(1),
+                ),
+            ),
+            // let t = x * 1 in t - t + y
+            Expr::Let {
+                name: "t".to_string(),
+                value: bin(BinOperator::Mul, var("x"), int(1)),
+                body: bin(
+                    BinOperator::Add,
+                    bin(BinOperator::Sub, var("t"), var("t")),
+                    var("y"),
+                ),
+            },
+            build_example_ast(),
+            // let n = 3 in y + (n - n) * 2
+            Expr::Let {
+                name: "n".to_string(),
+                value: int(3),
+                body: bin(
+                    BinOperator::Add,
+                    var("y"),
+                    bin(
+                        BinOperator::Mul,
+                        bin(BinOperator::Sub, var("n"), var("n")),
+                        int(2),
+                    ),
+                ),
+            },
+            // (y // 0) * 0: the dropped operand fails
+            *bin(
+                BinOperator::Mul,
+                bin(BinOperator::IntDiv, var("y"), int(0)),
+                int(0),
+            ),
+            // x ** -1 - x ** -1: both operands fail
+            *bin(
+                BinOperator::Sub,
+                bin(BinOperator::Pow, var("x"), int(-1)),
+                bin(BinOperator::Pow, var("x"), int(-1)),
+            ),
+            // let f = 1.5 in f * 0: the result must stay a Float
+            Expr::Let {
+                name: "f".to_string(),
+                value: Box::new(Expr::Float(1.5)),
+                body: bin(BinOperator::Mul, var("f"), int(0)),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_simplify_rules() {
+        let cases = simplification_cases();
+        assert_eq!(simplify(&cases[0]), Expr::Var("x".to_string()));
+        assert_eq!(generate_rust(&simplify(&cases[3])), "x + (y - x)");
+        assert_eq!(
+            generate_rust(&simplify(&cases[4])),
+            "{ let t = x; t - t + y }"
+        );
+        assert_eq!(generate_rust(&simplify(&cases[6])), "{ let n = 3; y }");
+        // Nothing to simplify
+        assert_eq!(simplify(&cases[5]), cases[5]);
+
+        // Free variables may be unbound or overflow, so they are never dropped
+        assert_eq!(generate_rust(&simplify(&cases[1])), "y * 0");
+        assert_eq!(simplify(&cases[2]), cases[2]);
+        let vars = HashMap::from([("x".to_string(), i64::MAX), ("y".to_string(), 2)]);
+        assert_eq!(evaluate(&cases[2], &vars), None);
+
+        // Failing and Float operands are kept
+        for case in &cases[7..] {
+            assert_eq!(&simplify(case), case);
+        }
+        assert_eq!(
+            evaluate_value(&simplify(&cases[9]), &HashMap::new()),
+            Ok(Value::Float(0.0))
+        );
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_simplify_preserves_semantics(x in -10_000i64..10_000, y in -10_000i64..10_000) {
+            let vars = HashMap::from([("x".to_string(), x), ("y".to_string(), y)]);
+            let values = HashMap::from([
+                ("x".to_string(), Value::Int(x)),
+                ("y".to_string(), Value::Int(y)),
+            ]);
+            for expr in simplification_cases() {
+                let simplified = simplify(&expr);
+                proptest::prop_assert_eq!(evaluate(&simplified, &vars), evaluate(&expr, &vars));
+                proptest::prop_assert_eq!(
+                    evaluate_value(&simplified, &values),
+                    evaluate_value(&expr, &values)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_depth_and_node_count() {
+        let expr = build_example_ast(); // x + y * 2
+        assert_eq!(expr.depth(), 3);
+        assert_eq!(expr.node_count(), 5);
+
+        assert_eq!(Expr::Int(1).depth(), 1);
+        assert_eq!(Expr::Int(1).node_count(), 1);
+
+        // let x = 5 in x + 1: Let, 5, BinOp, x, 1
+        assert_eq!(let_x_plus_one().depth(), 3);
+        assert_eq!(let_x_plus_one().node_count(), 5);
+
+        let call = Expr::Call {
+            name: "f".to_string(),
+            args: vec![build_example_ast(), Expr::Int(0)],
+        };
+        assert_eq!(call.depth(), 4);
+        assert_eq!(call.node_count(), 7);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        // if (x + y * 2) ** 2 > 1.5 { "big" } else { "small" }
+        let expr = Expr::If {
+            cond: Box::new(Expr::BinOp {
+                op: BinOperator::Gt,
+                left: Box::new(Expr::BinOp {
+                    op: BinOperator::Pow,
+                    left: Box::new(build_example_ast()),
+                    right: Box::new(Expr::Int(2)),
+                }),
+                right: Box::new(Expr::Float(1.5)),
+            }),
+            then: Box::new(Expr::Str("big".to_string())),
+            els: Box::new(Expr::Str("small".to_string())),
+        };
+
+        let json = serde_json::to_string(&expr).expect("serialize");
+        let back: Expr = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(back, expr);
+
+        let ty: Type = serde_json::from_str("\"Float\"").expect("deserialize type");
+        assert_eq!(ty, Type::Float);
+        assert!(serde_json::from_str::<Expr>("{\"Nope\": 1}").is_err());
+    }
+
+    #[test]
+    fn test_free_vars() {
+        let names = |e: &Expr| e.free_vars().into_iter().collect::<Vec<_>>();
+
+        assert_eq!(names(&build_example_ast()), vec!["x", "y"]);
+        let literal_only = Expr::BinOp {
+            op: BinOperator::Add,
+            left: Box::new(Expr::Int(1)),
+            right: Box::new(Expr::Float(2.0)),
+        };
+        assert!(literal_only.free_vars().is_empty());
+
+        // let x = 5 in x + 1 binds its own x
+        assert!(let_x_plus_one().free_vars().is_empty());
+
+        // let t = x in t + y: the value's vars are free, the bound name isn't
+        let shadowing = Expr::Let {
+            name: "t".to_string(),
+            value: Box::new(Expr::Var("x".to_string())),
+            body: Box::new(Expr::BinOp {
+                op: BinOperator::Add,
+                left: Box::new(Expr::Var("t".to_string())),
+                right: Box::new(Expr::Var("y".to_string())),
+            }),
+        };
+        assert_eq!(names(&shadowing), vec!["x", "y"]);
+
+        let call = Expr::Call {
+            name: "max".to_string(),
+            args: vec![Expr::Var("b".to_string()), Expr::Var("a".to_string())],
+        };
+        assert_eq!(names(&call), vec!["a", "b"]);
+    }
+
+    fn index(array: &str, index: Expr) -> Expr {
+        Expr::Index {
+            array: Box::new(Expr::Var(array.to_string())),
+            index: Box::new(index),
+        }
+    }
+
+    fn for_range(var: &str, end: Expr, body: Expr) -> Expr {
+        Expr::Let {
+            name: var.to_string(),
+            value: Box::new(Expr::Call {
+                name: "range".to_string(),
+                args: vec![Expr::Int(0), end],
+            }),
+            body: Box::new(body),
+        }
+    }
+
+    fn len_op(op: BinOperator, k: i64) -> Expr {
+        Expr::BinOp {
+            op,
+            left: Box::new(Expr::Var("len".to_string())),
+            right: Box::new(Expr::Int(k)),
+        }
+    }
+
+    #[test]
+    fn test_detect_index_out_of_range() {
+        let var = |name: &str| Expr::Var(name.to_string());
+
+        // for i in 0..len + 1 { items[i] }: i reaches len
+        let off_by_one = for_range("i", len_op(BinOperator::Add, 1), index("items", var("i")));
+        let lints = detect_index_out_of_range(&off_by_one, "len");
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].expr, "items[i]");
+        assert!(lints[0].message.contains("reach len"));
+
+        // Direct access at len, and beyond it
+        assert_eq!(
+            detect_index_out_of_range(&index("items", var("len")), "len").len(),
+            1
+        );
+        let beyond = index("items", len_op(BinOperator::Add, 2));
+        assert!(detect_index_out_of_range(&beyond, "len")[0]
+            .message
+            .contains("len + 2"));
+
+        // In-range accesses are clean
+        let in_range = for_range("i", var("len"), index("items", var("i")));
+        assert!(detect_index_out_of_range(&in_range, "len").is_empty());
+        let last = index("items", len_op(BinOperator::Sub, 1));
+        assert!(detect_index_out_of_range(&last, "len").is_empty());
+        let unknown = index("items", var("j"));
+        assert!(detect_index_out_of_range(&unknown, "len").is_empty());
+    }
+
+    #[test]
+    fn test_index_codegen_and_eval() {
+        let expr = index("s", Expr::Var("i".to_string()));
+        assert_eq!(generate_rust(&expr), "s[i]");
+        assert_eq!(generate_python(&expr), "s[i]");
+
+        let registry = FunctionRegistry::with_builtins();
+        let mut vars = HashMap::from([
+            ("s".to_string(), Value::Str("ai".to_string())),
+            ("i".to_string(), Value::Int(1)),
+        ]);
+        assert_eq!(
+            registry.evaluate(&expr, &vars),
+            Ok(Value::Str("i".to_string()))
+        );
+        vars.insert("i".to_string(), Value::Int(2));
+        assert_eq!(
+            registry.evaluate(&expr, &vars),
+            Err(EvalError::IndexOutOfRange { index: 2, len: 2 })
+        );
+    }
 }
@@ -6,10 +6,14 @@
 /// - Type-safe model loading
 ///
 /// **VALIDATION:** `make run-ch13`
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
 
 /// Inference configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 struct InferenceConfig {
     batch_size: usize,
@@ -17,8 +21,7 @@ struct InferenceConfig {
     precision: Precision,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 enum Precision {
     F32,
     F64,
@@ -34,23 +37,132 @@ impl Default for InferenceConfig {
     }
 }
 
+impl InferenceConfig {
+    /// Reject settings that cannot run
+    fn validate(&self) -> Result<(), String> {
+        if self.batch_size == 0 {
+            return Err("batch_size must be at least 1".to_string());
+        }
+        if self.num_threads == 0 {
+            return Err("num_threads must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Nonlinearity applied to each raw model output
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+enum Activation {
+    #[default]
+    Identity,
+    Relu,
+    Sigmoid,
+    Tanh,
+}
+
+impl Activation {
+    fn apply(self, z: f64) -> f64 {
+        match self {
+            Activation::Identity => z,
+            Activation::Relu => z.max(0.0),
+            Activation::Sigmoid => 1.0 / (1.0 + (-z).exp()),
+            Activation::Tanh => z.tanh(),
+        }
+    }
+}
+
 /// A trained model ready for inference
-#[derive(Debug, Clone)]
+///
+/// Weights are stored one row per output, so a single-output model is
+/// simply a 1×n matrix with a one-element bias.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 struct Model {
-    weights: Vec<f64>,
-    bias: f64,
+    weights: Vec<Vec<f64>>,
+    bias: Vec<f64>,
+    #[serde(default)]
+    activation: Activation,
     config: InferenceConfig,
 }
 
 impl Model {
-    /// Load model with given weights
+    /// Load single-output model with given weights
     fn new(weights: Vec<f64>, bias: f64) -> Self {
         Self {
+            weights: vec![weights],
+            bias: vec![bias],
+            activation: Activation::Identity,
+            config: InferenceConfig::default(),
+        }
+    }
+
+    /// Load multi-output model: one weight row and one bias per output
+    fn multi_output(weights: Vec<Vec<f64>>, bias: Vec<f64>) -> Result<Self, String> {
+        let model = Self {
             weights,
             bias,
+            activation: Activation::Identity,
             config: InferenceConfig::default(),
+        };
+        model.validate()?;
+        Ok(model)
+    }
+
+    /// Check weight/bias dimensions and the inference config
+    fn validate(&self) -> Result<(), String> {
+        let (weights, bias) = (&self.weights, &self.bias);
+        if weights.is_empty() {
+            return Err("model must have at least one output".to_string());
         }
+        if weights.len() != bias.len() {
+            return Err(format!(
+                "{} weight rows but {} bias terms",
+                weights.len(),
+                bias.len()
+            ));
+        }
+        let input_dim = weights[0].len();
+        if let Some((i, row)) = weights
+            .iter()
+            .enumerate()
+            .find(|(_, row)| row.len() != input_dim)
+        {
+            return Err(format!(
+                "weight row {} has {} features, expected {}",
+                i,
+                row.len(),
+                input_dim
+            ));
+        }
+        self.config.validate()
+    }
+
+    /// Write weights, bias and config as JSON
+    fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json).with_context(|| format!("writing model {}", path.display()))
+    }
+
+    /// Read a model saved with [`Model::save`], rejecting invalid ones
+    fn load(path: &Path) -> Result<Model> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("reading model {}", path.display()))?;
+        let model: Model = serde_json::from_str(&text)
+            .with_context(|| format!("parsing model {}", path.display()))?;
+        model
+            .validate()
+            .map_err(|e| anyhow::anyhow!("invalid model {}: {}", path.display(), e))?;
+        Ok(model)
+    }
+
+    /// Number of input features
+    fn input_dim(&self) -> usize {
+        self.weights[0].len()
+    }
+
+    /// Number of outputs
+    fn output_dim(&self) -> usize {
+        self.weights.len()
     }
 
     /// Configure inference settings
@@ -60,19 +172,102 @@ impl Model {
         self
     }
 
-    /// Single prediction
+    /// Set the output activation
+    fn with_activation(mut self, activation: Activation) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    /// Single prediction (the first, or only, output)
     fn predict(&self, x: &[f64]) -> f64 {
-        let sum: f64 = self
-            .weights
+        self.predict_multi(x)[0]
+    }
+
+    /// Single prediction that rejects inputs of the wrong length
+    fn try_predict(&self, x: &[f64]) -> Result<f64, String> {
+        if x.len() != self.input_dim() {
+            return Err(format!(
+                "input has {} features, model expects {}",
+                x.len(),
+                self.input_dim()
+            ));
+        }
+        Ok(self.predict(x))
+    }
+
+    /// Prediction for every output, accumulated at the configured precision
+    fn predict_multi(&self, x: &[f64]) -> Vec<f64> {
+        self.weights
+            .iter()
+            .zip(self.bias.iter())
+            .map(|(row, b)| {
+                let z = match self.config.precision {
+                    Precision::F64 => {
+                        let sum: f64 = row.iter().zip(x.iter()).map(|(w, xi)| w * xi).sum();
+                        sum + b
+                    }
+                    Precision::F32 => {
+                        let sum: f32 = row
+                            .iter()
+                            .zip(x.iter())
+                            .map(|(w, xi)| *w as f32 * *xi as f32)
+                            .sum();
+                        (sum + *b as f32) as f64
+                    }
+                };
+                self.activation.apply(z)
+            })
+            .collect()
+    }
+
+    /// Class probabilities via a numerically stable softmax over the outputs
+    fn predict_proba(&self, x: &[f64]) -> Vec<f64> {
+        let logits = self.predict_multi(x);
+        let max = logits.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let exps: Vec<f64> = logits.iter().map(|l| (l - max).exp()).collect();
+        let total: f64 = exps.iter().sum();
+        exps.iter().map(|e| e / total).collect()
+    }
+
+    /// Index of the largest output; ties resolve to the lowest index
+    fn predict_class(&self, x: &[f64]) -> usize {
+        self.predict_multi(x)
             .iter()
-            .zip(x.iter())
-            .map(|(w, xi)| w * xi)
-            .sum();
-        sum + self.bias
+            .enumerate()
+            .fold((0, f64::NEG_INFINITY), |(best_i, best), (i, &v)| {
+                if v > best {
+                    (i, v)
+                } else {
+                    (best_i, best)
+                }
+            })
+            .0
     }
 
-    /// Batch prediction for efficiency
+    /// Batch prediction, split across `config.num_threads` workers
+    ///
+    /// Each worker handles one contiguous chunk and the chunks are joined
+    /// in order, so the output order always matches the input order.
     fn predict_batch(&self, batch: &[Vec<f64>]) -> Vec<f64> {
+        let threads = self.config.num_threads.max(1);
+        if threads == 1 || batch.len() < 2 {
+            return self.predict_batch_sequential(batch);
+        }
+        let chunk_size = batch.len().div_ceil(threads);
+        std::thread::scope(|s| {
+            let handles: Vec<_> = batch
+                .chunks(chunk_size)
+                .map(|chunk| s.spawn(move || self.predict_batch_sequential(chunk)))
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().expect("inference worker panicked"))
+                .collect()
+        })
+    }
+
+    /// Batch prediction on the calling thread
+    fn predict_batch_sequential(&self, batch: &[Vec<f64>]) -> Vec<f64> {
         batch.iter().map(|x| self.predict(x)).collect()
     }
 
@@ -102,6 +297,35 @@ impl PredictionResult {
     }
 }
 
+/// Timing summary for a benchmarked batch
+#[derive(Debug, Clone)]
+struct InferenceStats {
+    predictions: usize,
+    total_time: Duration,
+    /// Predictions per second
+    throughput: f64,
+    mean_latency: Duration,
+}
+
+impl InferenceStats {
+    /// Derive throughput and latency from a prediction count and elapsed time
+    fn from_timing(predictions: usize, total_time: Duration) -> Self {
+        // Clamp to 1ns so tiny batches on coarse clocks stay finite
+        let secs = total_time.as_secs_f64().max(1e-9);
+        let mean_latency = if predictions == 0 {
+            Duration::ZERO
+        } else {
+            total_time.div_f64(predictions as f64)
+        };
+        Self {
+            predictions,
+            total_time,
+            throughput: predictions as f64 / secs,
+            mean_latency,
+        }
+    }
+}
+
 /// Inference engine with model management
 struct InferenceEngine {
     models: Vec<(String, Model)>,
@@ -112,8 +336,19 @@ impl InferenceEngine {
         Self { models: Vec::new() }
     }
 
+    /// Register a model, replacing any existing model with the same name
     fn register_model(&mut self, name: &str, model: Model) {
-        self.models.push((name.to_string(), model));
+        match self.models.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = model,
+            None => self.models.push((name.to_string(), model)),
+        }
+    }
+
+    /// Remove a model, returning whether it was registered
+    fn unregister(&mut self, name: &str) -> bool {
+        let before = self.models.len();
+        self.models.retain(|(n, _)| n != name);
+        self.models.len() != before
     }
 
     fn get_model(&self, name: &str) -> Option<&Model> {
@@ -121,7 +356,63 @@ impl InferenceEngine {
     }
 
     fn predict(&self, model_name: &str, x: &[f64]) -> Option<f64> {
-        self.get_model(model_name).map(|m| m.predict(x))
+        self.get_model(model_name)?.try_predict(x).ok()
+    }
+
+    /// Mean prediction across several models
+    ///
+    /// Returns `None` if the list is empty, a name is unknown, or a model
+    /// does not accept an input of this length.
+    fn predict_ensemble(&self, model_names: &[&str], x: &[f64]) -> Option<f64> {
+        let preds = self.ensemble_predictions(model_names, x)?;
+        Some(preds.iter().sum::<f64>() / preds.len() as f64)
+    }
+
+    /// Ensemble mean with bounds of ± one standard deviation across members
+    ///
+    /// Members that disagree widen the band, so the uncertainty comes from
+    /// the models themselves rather than a hard-coded margin.
+    fn predict_with_ensemble_bounds(
+        &self,
+        model_names: &[&str],
+        x: &[f64],
+    ) -> Option<PredictionResult> {
+        let preds = self.ensemble_predictions(model_names, x)?;
+        let n = preds.len() as f64;
+        let mean = preds.iter().sum::<f64>() / n;
+        let variance = preds.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / n;
+        let std_dev = variance.sqrt();
+        Some(PredictionResult {
+            value: mean,
+            lower_bound: mean - std_dev,
+            upper_bound: mean + std_dev,
+        })
+    }
+
+    /// Individual member predictions, or `None` if any member cannot answer
+    fn ensemble_predictions(&self, model_names: &[&str], x: &[f64]) -> Option<Vec<f64>> {
+        if model_names.is_empty() {
+            return None;
+        }
+        model_names
+            .iter()
+            .map(|name| self.predict(name, x))
+            .collect()
+    }
+
+    /// Time batch inference for one model
+    ///
+    /// Returns `None` if the model is unknown or any input has the wrong
+    /// length.
+    fn benchmark(&self, name: &str, batch: &[Vec<f64>]) -> Option<InferenceStats> {
+        let model = self.get_model(name)?;
+        if batch.iter().any(|x| x.len() != model.input_dim()) {
+            return None;
+        }
+        let start = Instant::now();
+        let preds = model.predict_batch(batch);
+        let elapsed = start.elapsed();
+        Some(InferenceStats::from_timing(preds.len(), elapsed))
     }
 
     fn model_count(&self) -> usize {
@@ -138,7 +429,10 @@ fn basic_inference_demo() {
     let model = Model::new(vec![2.0], 1.0);
 
     println!("   Model: y = 2x + 1");
-    println!("   Weights: {:?}, Bias: {}", model.weights, model.bias);
+    println!(
+        "   Weights: {:?}, Bias: {}",
+        model.weights[0], model.bias[0]
+    );
     println!();
 
     let test_inputs: Vec<f64> = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
@@ -159,6 +453,10 @@ fn batch_inference_demo() {
 
     let model = Model::new(vec![2.0, 3.0], 1.0);
     println!("   Model: y = 2*x1 + 3*x2 + 1");
+    println!(
+        "   Workers: {} threads (order-preserving chunks)",
+        model.config.num_threads
+    );
     println!();
 
     let batch: Vec<Vec<f64>> = vec![vec![1.0, 1.0], vec![2.0, 2.0], vec![3.0, 3.0]];
@@ -174,6 +472,134 @@ fn batch_inference_demo() {
     println!();
 }
 
+/// Demonstrate multi-output inference
+fn multi_output_demo() -> Result<()> {
+    println!("🎛️  Multi-Output Inference");
+    println!();
+
+    let model = Model::multi_output(
+        vec![vec![1.0, 2.0], vec![-1.0, 0.5], vec![0.0, 3.0]],
+        vec![0.0, 1.0, -2.0],
+    )
+    .map_err(anyhow::Error::msg)?;
+    println!(
+        "   Model: {} inputs → {} outputs",
+        model.input_dim(),
+        model.output_dim()
+    );
+    for (row, b) in model.weights.iter().zip(model.bias.iter()) {
+        println!("   - weights {:?}, bias {:+.1}", row, b);
+    }
+    println!();
+
+    let input = [2.0, 4.0];
+    let outputs = model.predict_multi(&input);
+    println!("   Input:   {:?}", input);
+    println!("   Outputs: {:?}", outputs);
+    let proba = model.predict_proba(&input);
+    let formatted: Vec<String> = proba.iter().map(|p| format!("{:.4}", p)).collect();
+    println!("   Softmax: [{}]", formatted.join(", "));
+    println!(
+        "   Class:   {} (ties resolve to the lowest index)",
+        model.predict_class(&input)
+    );
+    println!();
+
+    match Model::multi_output(vec![vec![1.0, 2.0], vec![1.0]], vec![0.0, 0.0]) {
+        Ok(_) => println!("   ❌ Ragged weights accepted"),
+        Err(e) => println!("   ✅ Ragged weights rejected: {}", e),
+    }
+    println!();
+
+    // Ship the weights as a file instead of recompiling them in
+    let path = std::env::temp_dir().join("ch13-multi-output-model.json");
+    model.save(&path)?;
+    let loaded = Model::load(&path)?;
+    let identical = loaded.predict_multi(&input) == outputs;
+    println!("   Saved and reloaded {}", path.display());
+    println!(
+        "   {} Reloaded model predicts identically",
+        if identical { "✅" } else { "❌" }
+    );
+    fs::remove_file(&path)?;
+    println!();
+
+    Ok(())
+}
+
+/// Demonstrate nonlinear activations
+fn activation_demo() {
+    println!("〰️  Output Activations");
+    println!();
+
+    let activations = [
+        Activation::Identity,
+        Activation::Relu,
+        Activation::Sigmoid,
+        Activation::Tanh,
+    ];
+    let inputs = [-2.0, 0.0, 2.0];
+
+    print!("   {:>10} │", "Activation");
+    for x in &inputs {
+        print!(" {:>8}", format!("x={:.1}", x));
+    }
+    println!();
+    println!("   ───────────┼───────────────────────────");
+
+    for activation in activations {
+        // y = act(x)
+        let model = Model::new(vec![1.0], 0.0).with_activation(activation);
+        print!("   {:>10} │", format!("{:?}", activation));
+        for x in &inputs {
+            print!(" {:>8.4}", model.predict(&[*x]));
+        }
+        println!();
+    }
+    println!();
+}
+
+/// Demonstrate the F32/F64 precision tradeoff
+fn precision_demo() {
+    println!("🎚️  Inference Precision");
+    println!();
+
+    let weights = vec![0.1, 1.0 / 3.0, 2.0_f64.sqrt(), -0.7];
+    let input = vec![3.0, 0.3, 1.1, 2.9];
+    let batch: Vec<Vec<f64>> = (0..50_000)
+        .map(|i| input.iter().map(|v| v + i as f64 * 1e-6).collect())
+        .collect();
+
+    println!(
+        "   {:>9} │ {:>20} │ {:>12}",
+        "Precision", "Prediction", "Batch time"
+    );
+    println!("   ──────────┼──────────────────────┼─────────────");
+
+    let mut preds = Vec::new();
+    for precision in [Precision::F64, Precision::F32] {
+        let config = InferenceConfig {
+            precision,
+            ..InferenceConfig::default()
+        };
+        let model = Model::new(weights.clone(), 0.2).with_config(config);
+        let pred = model.predict(&input);
+        let start = std::time::Instant::now();
+        let _ = model.predict_batch_sequential(&batch);
+        let elapsed = start.elapsed();
+        println!(
+            "   {:>9} │ {:>20.15} │ {:>10.2?}",
+            format!("{:?}", precision),
+            pred,
+            elapsed
+        );
+        preds.push(pred);
+    }
+    println!();
+    println!("   |F64 - F32| = {:.3e}", (preds[0] - preds[1]).abs());
+    println!();
+}
+
 /// Demonstrate determinism
 fn determinism_demo() {
     println!("🔁 Inference Determinism");
@@ -241,6 +667,37 @@ fn uncertainty_demo() {
     println!();
 }
 
+/// Demonstrate data-driven uncertainty from ensemble disagreement
+fn ensemble_uncertainty_demo() {
+    println!("🎲 Ensemble Uncertainty");
+    println!();
+
+    let mut engine = InferenceEngine::new();
+    engine.register_model("agree_a", Model::new(vec![2.0], 1.0));
+    engine.register_model("agree_b", Model::new(vec![2.05], 0.95));
+    engine.register_model("disagree_a", Model::new(vec![1.0], 2.0));
+    engine.register_model("disagree_b", Model::new(vec![3.0], 0.0));
+
+    let x = [3.0];
+    println!(
+        "   {:>11} │ {:>8} │ {:>16}",
+        "Ensemble", "Mean", "±1σ bounds"
+    );
+    println!("   ────────────┼──────────┼─────────────────");
+    for (label, members) in [
+        ("agreeing", ["agree_a", "agree_b"]),
+        ("disagreeing", ["disagree_a", "disagree_b"]),
+    ] {
+        if let Some(result) = engine.predict_with_ensemble_bounds(&members, &x) {
+            println!(
+                "   {:>11} │ {:>8.4} │ [{:.4}, {:.4}]",
+                label, result.value, result.lower_bound, result.upper_bound
+            );
+        }
+    }
+    println!();
+}
+
 /// Demonstrate inference engine
 fn engine_demo() {
     println!("🏭 Inference Engine");
@@ -254,6 +711,15 @@ fn engine_demo() {
     engine.register_model("classifier", Model::new(vec![0.5, -0.5], 0.0));
 
     println!("   Registered {} models", engine.model_count());
+
+    // Re-registering a name swaps in the new model instead of shadowing it
+    engine.register_model("linear", Model::new(vec![2.0], 1.0));
+    engine.register_model("scratch", Model::new(vec![0.0], 0.0));
+    engine.unregister("scratch");
+    println!(
+        "   After re-registering \"linear\" and removing \"scratch\": {} models",
+        engine.model_count()
+    );
     println!();
 
     let test_input = vec![2.0];
@@ -269,6 +735,50 @@ fn engine_demo() {
     if let Some(pred) = engine.predict("classifier", &test_input2) {
         println!("   - classifier: {:.4}", pred);
     }
+    if let Some(model) = engine.get_model("classifier") {
+        if let Err(e) = model.try_predict(&test_input) {
+            println!("   - classifier with x=[2.0]: rejected ({})", e);
+        }
+    }
+    println!();
+
+    let members = ["quadratic_approx", "classifier"];
+    match engine.predict_ensemble(&members, &test_input2) {
+        Some(pred) => println!("   Ensemble {:?}: {:.4}", members, pred),
+        None => println!("   Ensemble {:?}: incompatible", members),
+    }
+    if engine
+        .predict_ensemble(&["linear", "classifier"], &test_input2)
+        .is_none()
+    {
+        println!("   Ensemble [\"linear\", \"classifier\"]: rejected (input sizes differ)");
+    }
+    println!();
+}
+
+/// Demonstrate inference benchmarking
+fn benchmark_demo() {
+    println!("⏱️  Inference Benchmark");
+    println!();
+
+    let mut engine = InferenceEngine::new();
+    engine.register_model("linear4", Model::new(vec![0.5, -1.0, 2.0, 0.25], 0.1));
+
+    println!(
+        "   {:>8} │ {:>12} │ {:>14} │ {:>12}",
+        "Batch", "Total", "Throughput", "Latency"
+    );
+    println!("   ─────────┼──────────────┼────────────────┼─────────────");
+
+    for size in [1_000, 10_000, 100_000] {
+        let batch: Vec<Vec<f64>> = (0..size).map(|i| vec![i as f64, 1.0, -0.5, 2.0]).collect();
+        if let Some(stats) = engine.benchmark("linear4", &batch) {
+            println!(
+                "   {:>8} │ {:>12.2?} │ {:>10.0} /s │ {:>12.2?}",
+                stats.predictions, stats.total_time, stats.throughput, stats.mean_latency
+            );
+        }
+    }
     println!();
 }
 
@@ -312,6 +822,18 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
+    multi_output_demo()?;
+    println!("{}", "─".repeat(70));
+    println!();
+
+    activation_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
+    precision_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
     determinism_demo();
     println!("{}", "─".repeat(70));
     println!();
@@ -324,6 +846,14 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
+    ensemble_uncertainty_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
+    benchmark_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
     eu_compliance();
     println!("{}", "─".repeat(70));
     println!();
@@ -345,8 +875,128 @@ mod tests {
     #[test]
     fn test_model_creation() {
         let model = Model::new(vec![1.0, 2.0], 0.5);
-        assert_eq!(model.weights.len(), 2);
-        assert_eq!(model.bias, 0.5);
+        assert_eq!(model.weights, vec![vec![1.0, 2.0]]);
+        assert_eq!(model.bias, vec![0.5]);
+        assert_eq!(model.input_dim(), 2);
+        assert_eq!(model.output_dim(), 1);
+    }
+
+    #[test]
+    fn test_multi_output_prediction() {
+        let model = Model::multi_output(vec![vec![1.0, 2.0], vec![-1.0, 0.5]], vec![0.5, 1.0])
+            .expect("valid dimensions");
+        let preds = model.predict_multi(&[2.0, 4.0]);
+        // y0 = 1*2 + 2*4 + 0.5 = 10.5, y1 = -1*2 + 0.5*4 + 1 = 1
+        assert_eq!(preds.len(), 2);
+        assert!((preds[0] - 10.5).abs() < 1e-10);
+        assert!((preds[1] - 1.0).abs() < 1e-10);
+        assert!((model.predict(&[2.0, 4.0]) - 10.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_predict_proba_sums_to_one() {
+        let model = Model::multi_output(
+            vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]],
+            vec![0.0, 0.0, 0.0],
+        )
+        .expect("valid dimensions");
+        // Large logits must not overflow
+        for input in [[0.5, -0.5], [1000.0, 999.0], [-800.0, 3.0]] {
+            let proba = model.predict_proba(&input);
+            assert_eq!(proba.len(), 3);
+            assert!(proba.iter().all(|p| p.is_finite() && *p >= 0.0));
+            assert!((proba.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_predict_class_is_argmax() {
+        let model = Model::multi_output(
+            vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![-1.0, 0.0]],
+            vec![0.0, 0.0, 0.0],
+        )
+        .expect("valid dimensions");
+        assert_eq!(model.predict_class(&[3.0, 1.0]), 0);
+        assert_eq!(model.predict_class(&[1.0, 3.0]), 1);
+        assert_eq!(model.predict_class(&[-3.0, 1.0]), 2);
+        let proba = model.predict_proba(&[1.0, 3.0]);
+        assert!(proba[1] > proba[0] && proba[1] > proba[2]);
+        // Tie between outputs 0 and 1 picks the lower index
+        assert_eq!(model.predict_class(&[2.0, 2.0]), 0);
+    }
+
+    #[test]
+    fn test_model_save_load_roundtrip() {
+        let config = InferenceConfig {
+            batch_size: 16,
+            num_threads: 2,
+            precision: Precision::F64,
+        };
+        let model = Model::multi_output(vec![vec![0.1, -2.3], vec![4.5, 0.7]], vec![0.3, -1.1])
+            .expect("valid dimensions")
+            .with_config(config);
+        let path = std::env::temp_dir().join(format!("ch13-model-{}.json", std::process::id()));
+        model.save(&path).expect("save");
+        let loaded = Model::load(&path).expect("load");
+
+        let input = [1.25, -0.75];
+        assert_eq!(loaded.predict_multi(&input), model.predict_multi(&input));
+        assert_eq!(loaded.config.batch_size, 16);
+        assert_eq!(loaded.config.num_threads, 2);
+
+        // Invalid config is rejected on load
+        let mut broken = model.clone();
+        broken.config.num_threads = 0;
+        broken.save(&path).expect("save");
+        let err = Model::load(&path).expect_err("zero threads must be rejected");
+        assert!(err.to_string().contains("num_threads"));
+        fs::remove_file(&path).expect("cleanup");
+    }
+
+    #[test]
+    fn test_activations() {
+        let linear = Model::new(vec![2.0], 1.0);
+        let identity = Model::new(vec![2.0], 1.0).with_activation(Activation::Identity);
+        for x in [-3.0, 0.0, 4.5] {
+            assert_eq!(identity.predict(&[x]), linear.predict(&[x]));
+        }
+
+        let relu = Model::new(vec![1.0], 0.0).with_activation(Activation::Relu);
+        assert_eq!(relu.predict(&[-2.5]), 0.0);
+        assert_eq!(relu.predict(&[2.5]), 2.5);
+
+        let sigmoid = Model::new(vec![1.0], 0.0).with_activation(Activation::Sigmoid);
+        assert!((sigmoid.predict(&[0.0]) - 0.5).abs() < 1e-12);
+        assert!(sigmoid.predict(&[10.0]) > 0.99);
+
+        let tanh = Model::new(vec![1.0], 0.0).with_activation(Activation::Tanh);
+        assert_eq!(tanh.predict(&[0.0]), 0.0);
+        assert!(tanh.predict(&[-10.0]) < -0.99);
+    }
+
+    #[test]
+    fn test_f32_precision_path() {
+        let weights = vec![0.1, 1.0 / 3.0, 2.0_f64.sqrt()];
+        let input = [3.0, 0.3, 1.1];
+        let f64_model = Model::new(weights.clone(), 0.2);
+        let f32_model = Model::new(weights, 0.2).with_config(InferenceConfig {
+            precision: Precision::F32,
+            ..InferenceConfig::default()
+        });
+
+        let a = f64_model.predict(&input);
+        let b = f32_model.predict(&input);
+        assert!((a - b).abs() < 1e-5, "F32 and F64 should agree closely");
+        assert_ne!(a, b, "F32 path must actually compute in f32");
+    }
+
+    #[test]
+    fn test_multi_output_dimension_validation() {
+        assert!(Model::multi_output(vec![], vec![]).is_err());
+        assert!(Model::multi_output(vec![vec![1.0]], vec![0.0, 1.0]).is_err());
+        let err = Model::multi_output(vec![vec![1.0, 2.0], vec![1.0]], vec![0.0, 0.0])
+            .expect_err("ragged rows must be rejected");
+        assert!(err.contains("row 1"));
     }
 
     #[test]
@@ -377,6 +1027,28 @@ mod tests {
         assert!((preds[2] - 6.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_parallel_batch_matches_sequential() {
+        let batch: Vec<Vec<f64>> = (0..103)
+            .map(|i| vec![i as f64 * 0.1, (i % 7) as f64 - 3.0])
+            .collect();
+        let sequential = Model::new(vec![1.7, -0.3], 0.25).predict_batch_sequential(&batch);
+
+        for threads in [1, 2, 4, 8, 200] {
+            let config = InferenceConfig {
+                num_threads: threads,
+                ..InferenceConfig::default()
+            };
+            let model = Model::new(vec![1.7, -0.3], 0.25).with_config(config);
+            assert_eq!(
+                model.predict_batch(&batch),
+                sequential,
+                "threads = {}",
+                threads
+            );
+        }
+    }
+
     #[test]
     fn test_prediction_bounds() {
         let model = Model::new(vec![1.0], 0.0);
@@ -404,6 +1076,128 @@ mod tests {
         assert!((pred.expect("prediction should succeed") - 7.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_register_replaces_existing_model() {
+        let mut engine = InferenceEngine::new();
+        engine.register_model("m", Model::new(vec![1.0], 0.0));
+        engine.register_model("m", Model::new(vec![3.0], 1.0));
+
+        assert_eq!(engine.model_count(), 1);
+        assert_eq!(engine.predict("m", &[2.0]), Some(7.0));
+    }
+
+    #[test]
+    fn test_unregister() {
+        let mut engine = InferenceEngine::new();
+        engine.register_model("a", Model::new(vec![1.0], 0.0));
+        engine.register_model("b", Model::new(vec![2.0], 0.0));
+
+        assert!(engine.unregister("a"));
+        assert_eq!(engine.model_count(), 1);
+        assert!(engine.get_model("a").is_none());
+        assert!(engine.get_model("b").is_some());
+        assert!(!engine.unregister("a"));
+        assert_eq!(engine.model_count(), 1);
+    }
+
+    #[test]
+    fn test_try_predict_rejects_wrong_dimension() {
+        let model = Model::new(vec![1.0, 2.0, 3.0], 0.0);
+        let err = model
+            .try_predict(&[1.0, 2.0])
+            .expect_err("2 inputs for a 3-feature model");
+        assert_eq!(err, "input has 2 features, model expects 3");
+        assert!(model.try_predict(&[1.0, 2.0, 3.0, 4.0]).is_err());
+        assert_eq!(model.try_predict(&[1.0, 1.0, 1.0]), Ok(6.0));
+
+        let mut engine = InferenceEngine::new();
+        engine.register_model("wide", model);
+        assert!(engine.predict("wide", &[1.0, 2.0]).is_none());
+    }
+
+    #[test]
+    fn test_predict_ensemble_is_mean() {
+        let mut engine = InferenceEngine::new();
+        engine.register_model("a", Model::new(vec![2.0, 1.0], 1.0));
+        engine.register_model("b", Model::new(vec![-1.0, 3.0], 0.0));
+        engine.register_model("narrow", Model::new(vec![1.0], 0.0));
+
+        let x = [1.5, 2.0];
+        let a = engine.predict("a", &x).expect("a");
+        let b = engine.predict("b", &x).expect("b");
+        let ensemble = engine.predict_ensemble(&["a", "b"], &x).expect("ensemble");
+        assert!((ensemble - (a + b) / 2.0).abs() < 1e-12);
+
+        assert!(engine.predict_ensemble(&["a", "missing"], &x).is_none());
+        assert!(engine.predict_ensemble(&["a", "narrow"], &x).is_none());
+        assert!(engine.predict_ensemble(&[], &x).is_none());
+    }
+
+    #[test]
+    fn test_ensemble_bounds_widen_with_disagreement() {
+        let mut engine = InferenceEngine::new();
+        engine.register_model("a1", Model::new(vec![2.0], 1.0));
+        engine.register_model("a2", Model::new(vec![2.0], 1.1));
+        engine.register_model("d1", Model::new(vec![1.0], 0.0));
+        engine.register_model("d2", Model::new(vec![3.0], 2.0));
+
+        let x = [2.0];
+        let agree = engine
+            .predict_with_ensemble_bounds(&["a1", "a2"], &x)
+            .expect("agreeing ensemble");
+        let disagree = engine
+            .predict_with_ensemble_bounds(&["d1", "d2"], &x)
+            .expect("disagreeing ensemble");
+
+        let width = |r: &PredictionResult| r.upper_bound - r.lower_bound;
+        assert!(width(&disagree) > width(&agree));
+        assert!((agree.value - 5.05).abs() < 1e-12);
+        assert!((agree.lower_bound - 5.0).abs() < 1e-12);
+        // d1 = 2, d2 = 8: mean 5, std dev 3
+        assert!((disagree.value - 5.0).abs() < 1e-12);
+        assert!((disagree.lower_bound - 2.0).abs() < 1e-12);
+        assert!((disagree.upper_bound - 8.0).abs() < 1e-12);
+
+        assert!(engine
+            .predict_with_ensemble_bounds(&["a1", "missing"], &x)
+            .is_none());
+    }
+
+    #[test]
+    fn test_inference_stats_from_timing() {
+        let small = InferenceStats::from_timing(1_000, Duration::from_millis(10));
+        let large = InferenceStats::from_timing(10_000, Duration::from_millis(100));
+
+        assert!((small.throughput - 100_000.0).abs() < 1e-6);
+        assert!((large.throughput - small.throughput).abs() < 1e-6);
+        assert_eq!(small.mean_latency, Duration::from_micros(10));
+        assert_eq!(large.mean_latency, small.mean_latency);
+        assert_eq!(
+            InferenceStats::from_timing(0, Duration::ZERO).mean_latency,
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_benchmark_scales_with_batch_size() {
+        let mut engine = InferenceEngine::new();
+        engine.register_model("m", Model::new(vec![1.0, 2.0], 0.5));
+
+        let batch = |n: usize| -> Vec<Vec<f64>> { (0..n).map(|i| vec![i as f64, 1.0]).collect() };
+        let small = engine.benchmark("m", &batch(2_000)).expect("benchmark");
+        let large = engine.benchmark("m", &batch(200_000)).expect("benchmark");
+
+        assert_eq!(small.predictions, 2_000);
+        assert_eq!(large.predictions, 200_000);
+        assert!(small.throughput > 0.0 && large.throughput > 0.0);
+        // 100x the work should take clearly longer, but well under 10_000x
+        assert!(large.total_time > small.total_time);
+        assert!(large.total_time < small.total_time * 10_000);
+
+        assert!(engine.benchmark("missing", &batch(10)).is_none());
+        assert!(engine.benchmark("m", &[vec![1.0]]).is_none());
+    }
+
     #[test]
     fn test_inference_determinism() {
         let model = Model::new(vec![1.5, 2.5], 0.5);
@@ -7,13 +7,58 @@
 ///
 /// **VALIDATION:** `make run-ch15`
 use anyhow::Result;
+use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
+/// Scalar-quantized vector: each component is `offset + code * scale`
+#[derive(Debug, Clone)]
+struct QuantizedVector {
+    codes: Vec<u8>,
+    scale: f64,
+    offset: f64,
+}
+
+impl QuantizedVector {
+    /// Quantize to `bits` bits per component using the vector's own min/max range
+    fn encode(vector: &[f64], bits: u8) -> Self {
+        let min = vector.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = vector.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let levels = f64::from((1u16 << bits) - 1);
+        let scale = if max > min { (max - min) / levels } else { 0.0 };
+
+        let codes = vector
+            .iter()
+            .map(|x| {
+                if scale > 0.0 {
+                    ((x - min) / scale).round() as u8
+                } else {
+                    0
+                }
+            })
+            .collect();
+
+        Self {
+            codes,
+            scale,
+            offset: if min.is_finite() { min } else { 0.0 },
+        }
+    }
+
+    fn decode(&self) -> Vec<f64> {
+        self.codes
+            .iter()
+            .map(|&c| self.offset + f64::from(c) * self.scale)
+            .collect()
+    }
+}
+
 /// Vector embedding with metadata
 #[derive(Debug, Clone)]
 struct Embedding {
     id: String,
     vector: Vec<f64>,
+    quantized: Option<QuantizedVector>,
     metadata: HashMap<String, String>,
 }
 
@@ -22,17 +67,44 @@ impl Embedding {
         Self {
             id: id.to_string(),
             vector,
+            quantized: None,
             metadata: HashMap::new(),
         }
     }
 
+    /// Replace the f64 storage with a scalar-quantized copy
+    fn quantize(mut self, bits: u8) -> Self {
+        self.quantized = Some(QuantizedVector::encode(&self.vector, bits));
+        self.vector = Vec::new();
+        self
+    }
+
     fn with_metadata(mut self, key: &str, value: &str) -> Self {
         self.metadata.insert(key.to_string(), value.to_string());
         self
     }
 
     fn dimension(&self) -> usize {
-        self.vector.len()
+        match &self.quantized {
+            Some(q) => q.codes.len(),
+            None => self.vector.len(),
+        }
+    }
+
+    /// Vector values, reconstructed on demand when quantized
+    fn values(&self) -> Cow<'_, [f64]> {
+        match &self.quantized {
+            Some(q) => Cow::Owned(q.decode()),
+            None => Cow::Borrowed(&self.vector),
+        }
+    }
+
+    /// Bytes used to store the vector payload
+    fn storage_bytes(&self) -> usize {
+        match &self.quantized {
+            Some(q) => q.codes.len() + 2 * std::mem::size_of::<f64>(),
+            None => self.vector.len() * std::mem::size_of::<f64>(),
+        }
     }
 }
 
@@ -42,8 +114,14 @@ enum DistanceMetric {
     Euclidean,
     Cosine,
     DotProduct,
+    Manhattan,
+    Chebyshev,
+    Hamming,
 }
 
+/// Components closer than this count as equal for the Hamming metric
+const HAMMING_TOLERANCE: f64 = 1e-9;
+
 /// Compute distance between two vectors
 fn compute_distance(a: &[f64], b: &[f64], metric: DistanceMetric) -> f64 {
     match metric {
@@ -60,6 +138,17 @@ fn compute_distance(a: &[f64], b: &[f64], metric: DistanceMetric) -> f64 {
             1.0 - (dot / (norm_a * norm_b))
         }
         DistanceMetric::DotProduct => -a.iter().zip(b.iter()).map(|(x, y)| x * y).sum::<f64>(),
+        DistanceMetric::Manhattan => a.iter().zip(b.iter()).map(|(x, y)| (x - y).abs()).sum(),
+        DistanceMetric::Chebyshev => a
+            .iter()
+            .zip(b.iter())
+            .map(|(x, y)| (x - y).abs())
+            .fold(0.0, f64::max),
+        DistanceMetric::Hamming => a
+            .iter()
+            .zip(b.iter())
+            .filter(|(x, y)| (*x - *y).abs() > HAMMING_TOLERANCE)
+            .count() as f64,
     }
 }
 
@@ -72,11 +161,37 @@ struct SearchResult {
     embedding: Embedding,
 }
 
+/// Order results by distance, breaking ties by id so the order is total
+/// and independent of insertion order
+fn compare_results(a: &SearchResult, b: &SearchResult) -> Ordering {
+    a.distance
+        .partial_cmp(&b.distance)
+        .expect("valid distance comparison")
+        .then_with(|| a.id.cmp(&b.id))
+}
+
+/// Fraction of the exact top-k ids that also appear in the approximate top-k
+///
+/// Returns 1.0 when there is nothing to recall (`k == 0` or no exact results).
+fn recall_at_k(exact: &[SearchResult], approx: &[SearchResult], k: usize) -> f64 {
+    let expected = &exact[..k.min(exact.len())];
+    if expected.is_empty() {
+        return 1.0;
+    }
+    let found = &approx[..k.min(approx.len())];
+    let shared = expected
+        .iter()
+        .filter(|e| found.iter().any(|a| a.id == e.id))
+        .count();
+    shared as f64 / expected.len() as f64
+}
+
 /// Vector database with exact search
 struct VectorDB {
     embeddings: Vec<Embedding>,
     dimension: usize,
     metric: DistanceMetric,
+    quantization_bits: Option<u8>,
 }
 
 impl VectorDB {
@@ -85,9 +200,20 @@ impl VectorDB {
             embeddings: Vec::new(),
             dimension,
             metric,
+            quantization_bits: None,
         }
     }
 
+    /// Store inserted vectors with `bits`-bit scalar quantization (1-8)
+    fn with_quantization(mut self, bits: u8) -> Self {
+        assert!(
+            (1..=8).contains(&bits),
+            "quantization bits must be in 1..=8, got {bits}"
+        );
+        self.quantization_bits = Some(bits);
+        self
+    }
+
     fn insert(&mut self, embedding: Embedding) -> Result<(), String> {
         if embedding.dimension() != self.dimension {
             return Err(format!(
@@ -96,6 +222,10 @@ impl VectorDB {
                 embedding.dimension()
             ));
         }
+        let embedding = match self.quantization_bits {
+            Some(bits) => embedding.quantize(bits),
+            None => embedding,
+        };
         self.embeddings.push(embedding);
         Ok(())
     }
@@ -106,16 +236,12 @@ impl VectorDB {
             .iter()
             .map(|e| SearchResult {
                 id: e.id.clone(),
-                distance: compute_distance(query, &e.vector, self.metric),
+                distance: compute_distance(query, &e.values(), self.metric),
                 embedding: e.clone(),
             })
             .collect();
 
-        results.sort_by(|a, b| {
-            a.distance
-                .partial_cmp(&b.distance)
-                .expect("valid distance comparison")
-        });
+        results.sort_by(compare_results);
         results.truncate(k);
         results
     }
@@ -128,6 +254,10 @@ impl VectorDB {
         self.embeddings.len()
     }
 
+    fn storage_bytes(&self) -> usize {
+        self.embeddings.iter().map(Embedding::storage_bytes).sum()
+    }
+
     fn delete(&mut self, id: &str) -> bool {
         let initial_len = self.embeddings.len();
         self.embeddings.retain(|e| e.id != id);
@@ -135,6 +265,55 @@ impl VectorDB {
     }
 }
 
+/// Vector database split across shards by a stable hash of the embedding id
+struct ShardedVectorDB {
+    shards: Vec<VectorDB>,
+}
+
+impl ShardedVectorDB {
+    fn new(num_shards: usize, dimension: usize, metric: DistanceMetric) -> Self {
+        assert!(num_shards > 0, "at least one shard is required");
+        Self {
+            shards: (0..num_shards)
+                .map(|_| VectorDB::new(dimension, metric))
+                .collect(),
+        }
+    }
+
+    /// FNV-1a hash of the id, stable across runs and platforms
+    fn shard_for(&self, id: &str) -> usize {
+        let hash = id.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |h, b| {
+            (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        });
+        (hash % self.shards.len() as u64) as usize
+    }
+
+    fn insert(&mut self, embedding: Embedding) -> Result<(), String> {
+        let shard = self.shard_for(&embedding.id);
+        self.shards[shard].insert(embedding)
+    }
+
+    /// Search every shard and merge the per-shard top-k into a global top-k
+    fn search(&self, query: &[f64], k: usize) -> Vec<SearchResult> {
+        let mut results: Vec<_> = self
+            .shards
+            .iter()
+            .flat_map(|shard| shard.search(query, k))
+            .collect();
+        results.sort_by(compare_results);
+        results.truncate(k);
+        results
+    }
+
+    fn shard_sizes(&self) -> Vec<usize> {
+        self.shards.iter().map(VectorDB::len).collect()
+    }
+
+    fn len(&self) -> usize {
+        self.shards.iter().map(VectorDB::len).sum()
+    }
+}
+
 /// Demonstrate basic operations
 fn basic_demo() {
     println!("📊 Basic Vector Database Operations");
@@ -191,12 +370,18 @@ fn distance_metrics_demo() {
     let euclidean = compute_distance(&a, &b, DistanceMetric::Euclidean);
     let cosine = compute_distance(&a, &b, DistanceMetric::Cosine);
     let dot = compute_distance(&a, &b, DistanceMetric::DotProduct);
+    let manhattan = compute_distance(&a, &b, DistanceMetric::Manhattan);
+    let chebyshev = compute_distance(&a, &b, DistanceMetric::Chebyshev);
+    let hamming = compute_distance(&a, &b, DistanceMetric::Hamming);
 
     println!("   {:>12} │ {:>10}", "Metric", "Distance");
     println!("   ─────────────┼───────────");
     println!("   {:>12} │ {:>10.4}", "Euclidean", euclidean);
     println!("   {:>12} │ {:>10.4}", "Cosine", cosine);
     println!("   {:>12} │ {:>10.4}", "DotProduct", dot);
+    println!("   {:>12} │ {:>10.4}", "Manhattan", manhattan);
+    println!("   {:>12} │ {:>10.4}", "Chebyshev", chebyshev);
+    println!("   {:>12} │ {:>10.4}", "Hamming", hamming);
     println!();
 }
 
@@ -269,6 +454,96 @@ fn crud_demo() {
     println!();
 }
 
+/// Deterministic pseudo-random vectors in [-1, 1) from a linear congruential generator
+fn pseudo_random_vectors(count: usize, dimension: usize, seed: u64) -> Vec<Vec<f64>> {
+    let mut state = seed;
+    (0..count)
+        .map(|_| {
+            (0..dimension)
+                .map(|_| {
+                    state = state
+                        .wrapping_mul(6_364_136_223_846_793_005)
+                        .wrapping_add(1_442_695_040_888_963_407);
+                    (state >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Demonstrate scalar quantization
+fn quantization_demo() {
+    println!("🗜️  Scalar Quantization");
+    println!();
+
+    let dimension = 64;
+    let mut exact = VectorDB::new(dimension, DistanceMetric::Euclidean);
+    let mut quantized = VectorDB::new(dimension, DistanceMetric::Euclidean).with_quantization(8);
+
+    for (i, v) in pseudo_random_vectors(500, dimension, 42)
+        .into_iter()
+        .enumerate()
+    {
+        let id = format!("v{:03}", i);
+        exact
+            .insert(Embedding::new(&id, v.clone()))
+            .expect("embedding insertion should succeed");
+        quantized
+            .insert(Embedding::new(&id, v))
+            .expect("embedding insertion should succeed");
+    }
+
+    let query = &pseudo_random_vectors(1, dimension, 7)[0];
+    let recall = recall_at_k(&exact.search(query, 10), &quantized.search(query, 10), 10);
+
+    println!("   {:>10} │ {:>12}", "Storage", "Bytes");
+    println!("   ───────────┼─────────────");
+    println!("   {:>10} │ {:>12}", "f64", exact.storage_bytes());
+    println!("   {:>10} │ {:>12}", "8-bit", quantized.storage_bytes());
+    println!();
+    println!("   Recall@10 vs exact search: {:.2}", recall);
+    println!();
+}
+
+/// Demonstrate sharded search
+fn sharding_demo() {
+    println!("🧩 Sharded Search");
+    println!();
+
+    let dimension = 8;
+    let mut single = VectorDB::new(dimension, DistanceMetric::Euclidean);
+    let mut sharded = ShardedVectorDB::new(4, dimension, DistanceMetric::Euclidean);
+
+    for (i, v) in pseudo_random_vectors(200, dimension, 11)
+        .into_iter()
+        .enumerate()
+    {
+        let id = format!("v{:03}", i);
+        single
+            .insert(Embedding::new(&id, v.clone()))
+            .expect("embedding insertion should succeed");
+        sharded
+            .insert(Embedding::new(&id, v))
+            .expect("embedding insertion should succeed");
+    }
+
+    let query = &pseudo_random_vectors(1, dimension, 5)[0];
+    let single_ids: Vec<_> = single.search(query, 5).into_iter().map(|r| r.id).collect();
+    let sharded_ids: Vec<_> = sharded.search(query, 5).into_iter().map(|r| r.id).collect();
+
+    println!("   Embeddings: {}", sharded.len());
+    println!("   Shard sizes: {:?}", sharded.shard_sizes());
+    println!("   Single DB top-5: {:?}", single_ids);
+    println!("   Sharded top-5:   {:?}", sharded_ids);
+    println!();
+    if single_ids == sharded_ids {
+        println!("   ✅ Sharded merge matches single-database search");
+    } else {
+        println!("   ❌ Sharded merge diverged from single-database search!");
+    }
+    println!();
+}
+
 /// EU AI Act compliance
 fn eu_compliance() {
     println!("🇪🇺 EU AI Act Compliance");
@@ -317,6 +592,14 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
+    quantization_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
+    sharding_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
     eu_compliance();
     println!("{}", "─".repeat(70));
     println!();
@@ -364,6 +647,30 @@ mod tests {
         assert!((dist - 1.0).abs() < 1e-10); // Orthogonal = max distance
     }
 
+    #[test]
+    fn test_manhattan_distance() {
+        let a = vec![1.0, 2.0, 3.0];
+        let b = vec![4.0, 6.0, 8.0];
+        let dist = compute_distance(&a, &b, DistanceMetric::Manhattan);
+        assert!((dist - 12.0).abs() < 1e-10); // 3 + 4 + 5
+    }
+
+    #[test]
+    fn test_chebyshev_distance() {
+        let a = vec![1.0, 2.0, 3.0];
+        let b = vec![4.0, 6.0, 8.0];
+        let dist = compute_distance(&a, &b, DistanceMetric::Chebyshev);
+        assert!((dist - 5.0).abs() < 1e-10); // max(3, 4, 5)
+    }
+
+    #[test]
+    fn test_hamming_distance() {
+        let a = vec![1.0, 0.0, 1.0, 1.0];
+        let b = vec![1.0, 1.0, 0.0, 1.0 + 1e-12];
+        let dist = compute_distance(&a, &b, DistanceMetric::Hamming);
+        assert!((dist - 2.0).abs() < 1e-10); // Last component within tolerance
+    }
+
     #[test]
     fn test_db_insert_and_search() {
         let mut db = VectorDB::new(2, DistanceMetric::Euclidean);
@@ -408,6 +715,136 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_search_tie_breaking_by_id() {
+        let mut db = VectorDB::new(2, DistanceMetric::Euclidean);
+        // All four points are at distance 1.0 from the origin
+        for (id, v) in [
+            ("d", vec![1.0, 0.0]),
+            ("b", vec![0.0, 1.0]),
+            ("c", vec![-1.0, 0.0]),
+            ("a", vec![0.0, -1.0]),
+        ] {
+            db.insert(Embedding::new(id, v)).expect("insert");
+        }
+
+        for _ in 0..5 {
+            let ids: Vec<_> = db
+                .search(&[0.0, 0.0], 4)
+                .into_iter()
+                .map(|r| r.id)
+                .collect();
+            assert_eq!(ids, vec!["a", "b", "c", "d"]);
+        }
+    }
+
+    #[test]
+    fn test_quantization_roundtrip() {
+        let emb = Embedding::new("q", vec![-1.0, 0.0, 0.5, 1.0]).quantize(8);
+        assert_eq!(emb.dimension(), 4);
+        for (orig, recon) in [-1.0, 0.0, 0.5, 1.0].iter().zip(emb.values().iter()) {
+            assert!((orig - recon).abs() <= 2.0 / 255.0);
+        }
+    }
+
+    #[test]
+    fn test_quantized_search_recall() {
+        let dimension = 32;
+        let k = 10;
+        let mut exact = VectorDB::new(dimension, DistanceMetric::Euclidean);
+        let mut quantized =
+            VectorDB::new(dimension, DistanceMetric::Euclidean).with_quantization(8);
+        for (i, v) in pseudo_random_vectors(300, dimension, 1)
+            .into_iter()
+            .enumerate()
+        {
+            let id = format!("v{}", i);
+            exact
+                .insert(Embedding::new(&id, v.clone()))
+                .expect("insert");
+            quantized.insert(Embedding::new(&id, v)).expect("insert");
+        }
+
+        let queries = pseudo_random_vectors(20, dimension, 2);
+        let recall = queries
+            .iter()
+            .map(|q| recall_at_k(&exact.search(q, k), &quantized.search(q, k), k))
+            .sum::<f64>()
+            / queries.len() as f64;
+        assert!(recall > 0.95, "recall {} should exceed 0.95", recall);
+    }
+
+    #[test]
+    fn test_quantized_storage_fraction() {
+        let dimension = 64;
+        let mut exact = VectorDB::new(dimension, DistanceMetric::Euclidean);
+        let mut quantized =
+            VectorDB::new(dimension, DistanceMetric::Euclidean).with_quantization(8);
+        for (i, v) in pseudo_random_vectors(10, dimension, 3)
+            .into_iter()
+            .enumerate()
+        {
+            let id = format!("v{}", i);
+            exact
+                .insert(Embedding::new(&id, v.clone()))
+                .expect("insert");
+            quantized.insert(Embedding::new(&id, v)).expect("insert");
+        }
+
+        // 1 byte per component plus a 16-byte scale/offset vs 8 bytes per component
+        assert_eq!(exact.storage_bytes(), 10 * dimension * 8);
+        assert_eq!(quantized.storage_bytes(), 10 * (dimension + 16));
+    }
+
+    fn result(id: &str) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            distance: 0.0,
+            embedding: Embedding::new(id, vec![0.0]),
+        }
+    }
+
+    #[test]
+    fn test_recall_at_k_partial_overlap() {
+        let exact: Vec<_> = ["a", "b", "c", "d", "e"].map(result).into();
+        let approx: Vec<_> = ["a", "x", "c", "y", "e"].map(result).into();
+        assert!((recall_at_k(&exact, &approx, 5) - 0.6).abs() < 1e-10);
+        assert!((recall_at_k(&exact, &exact, 5) - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_sharded_search_matches_unsharded() {
+        let dimension = 6;
+        let mut single = VectorDB::new(dimension, DistanceMetric::Euclidean);
+        let mut sharded = ShardedVectorDB::new(4, dimension, DistanceMetric::Euclidean);
+        for (i, v) in pseudo_random_vectors(150, dimension, 9)
+            .into_iter()
+            .enumerate()
+        {
+            let id = format!("v{}", i);
+            single
+                .insert(Embedding::new(&id, v.clone()))
+                .expect("insert");
+            sharded.insert(Embedding::new(&id, v)).expect("insert");
+        }
+        assert_eq!(sharded.len(), 150);
+        assert!(sharded.shard_sizes().iter().all(|&n| n > 0));
+
+        for query in pseudo_random_vectors(10, dimension, 10) {
+            let expected: Vec<_> = single
+                .search(&query, 7)
+                .into_iter()
+                .map(|r| (r.id, r.distance))
+                .collect();
+            let actual: Vec<_> = sharded
+                .search(&query, 7)
+                .into_iter()
+                .map(|r| (r.id, r.distance))
+                .collect();
+            assert_eq!(actual, expected);
+        }
+    }
+
     #[test]
     fn test_delete() {
         let mut db = VectorDB::new(2, DistanceMetric::Euclidean);
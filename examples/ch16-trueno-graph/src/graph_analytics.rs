@@ -7,7 +7,8 @@
 ///
 /// **VALIDATION:** `make run-ch16`
 use anyhow::Result;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 
 /// Graph node
 #[derive(Debug, Clone)]
@@ -26,11 +27,53 @@ impl Node {
     }
 }
 
-/// Directed graph structure
+/// Dijkstra frontier entry, ordered so `BinaryHeap` pops the cheapest
+/// cost first and breaks ties by the lowest node id
+#[derive(Debug, PartialEq)]
+struct PathState {
+    cost: f64,
+    node: usize,
+}
+
+impl Eq for PathState {}
+
+impl Ord for PathState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+impl PartialOrd for PathState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Bookkeeping for Tarjan's strongly connected components algorithm
+#[derive(Default)]
+struct Tarjan {
+    next_index: usize,
+    index: HashMap<usize, usize>,
+    low_link: HashMap<usize, usize>,
+    stack: Vec<usize>,
+    on_stack: HashSet<usize>,
+    components: Vec<Vec<usize>>,
+}
+
+/// Graph structure, directed by default
+///
+/// In undirected mode every edge is stored in both adjacency lists and
+/// counted once by `edge_count`. `weights` runs parallel to `edges`: `weights[&from][i]` is the weight
+/// of the edge `from -> edges[&from][i]`.
 #[derive(Debug)]
 struct Graph {
     nodes: HashMap<usize, Node>,
     edges: HashMap<usize, Vec<usize>>,
+    weights: HashMap<usize, Vec<f64>>,
+    directed: bool,
 }
 
 impl Graph {
@@ -38,6 +81,16 @@ impl Graph {
         Self {
             nodes: HashMap::new(),
             edges: HashMap::new(),
+            weights: HashMap::new(),
+            directed: true,
+        }
+    }
+
+    /// Graph where `add_edge(a, b)` also connects `b` to `a`
+    fn new_undirected() -> Self {
+        Self {
+            directed: false,
+            ..Self::new()
         }
     }
 
@@ -45,10 +98,64 @@ impl Graph {
         let id = node.id;
         self.nodes.insert(id, node);
         self.edges.entry(id).or_default();
+        self.weights.entry(id).or_default();
     }
 
+    /// Add an edge with weight 1.0
     fn add_edge(&mut self, from: usize, to: usize) {
+        self.add_weighted_edge(from, to, 1.0);
+    }
+
+    fn add_weighted_edge(&mut self, from: usize, to: usize, weight: f64) {
         self.edges.entry(from).or_default().push(to);
+        self.weights.entry(from).or_default().push(weight);
+        if !self.directed && from != to {
+            self.edges.entry(to).or_default().push(from);
+            self.weights.entry(to).or_default().push(weight);
+        }
+    }
+
+    /// Remove `from -> to` (both directions when undirected), returning
+    /// whether any edge was removed
+    fn remove_edge(&mut self, from: usize, to: usize) -> bool {
+        let mut removed = self.drop_targets(from, |t| t == to) > 0;
+        if !self.directed && from != to {
+            removed |= self.drop_targets(to, |t| t == from) > 0;
+        }
+        removed
+    }
+
+    /// Remove a node together with all incoming and outgoing edges
+    fn remove_node(&mut self, id: usize) -> bool {
+        if self.nodes.remove(&id).is_none() {
+            return false;
+        }
+        self.edges.remove(&id);
+        self.weights.remove(&id);
+        let sources: Vec<usize> = self.edges.keys().copied().collect();
+        for from in sources {
+            self.drop_targets(from, |t| t == id);
+        }
+        true
+    }
+
+    /// Drop edges out of `from` whose target matches, keeping weights aligned
+    fn drop_targets(&mut self, from: usize, matches: impl Fn(usize) -> bool) -> usize {
+        let (Some(targets), Some(weights)) =
+            (self.edges.get_mut(&from), self.weights.get_mut(&from))
+        else {
+            return 0;
+        };
+        let before = targets.len();
+        let kept: Vec<(usize, f64)> = targets
+            .iter()
+            .copied()
+            .zip(weights.iter().copied())
+            .filter(|&(t, _)| !matches(t))
+            .collect();
+        *targets = kept.iter().map(|&(t, _)| t).collect();
+        *weights = kept.iter().map(|&(_, w)| w).collect();
+        before - targets.len()
     }
 
     fn node_count(&self) -> usize {
@@ -56,13 +163,33 @@ impl Graph {
     }
 
     fn edge_count(&self) -> usize {
-        self.edges.values().map(|v| v.len()).sum()
+        let entries: usize = self.edges.values().map(|v| v.len()).sum();
+        if self.directed {
+            return entries;
+        }
+        // Self-loops are stored once, every other edge twice
+        let loops = self
+            .edges
+            .iter()
+            .map(|(from, targets)| targets.iter().filter(|&to| to == from).count())
+            .sum::<usize>();
+        (entries - loops) / 2 + loops
     }
 
     fn neighbors(&self, id: usize) -> &[usize] {
         self.edges.get(&id).map(|v| v.as_slice()).unwrap_or(&[])
     }
 
+    /// Neighbors paired with the weight of the connecting edge
+    fn weighted_neighbors(&self, id: usize) -> Vec<(usize, f64)> {
+        let weights = self.weights.get(&id).map(|v| v.as_slice()).unwrap_or(&[]);
+        self.neighbors(id)
+            .iter()
+            .copied()
+            .zip(weights.iter().copied())
+            .collect()
+    }
+
     /// Breadth-first search
     fn bfs(&self, start: usize) -> Vec<usize> {
         let mut visited = HashSet::new();
@@ -84,6 +211,43 @@ impl Graph {
         result
     }
 
+    /// Fewest-hop path from `start` to `goal`
+    ///
+    /// Neighbors are expanded in ascending id order, so among equally
+    /// short paths the one through the lowest ids wins regardless of the
+    /// order edges were added.
+    fn bfs_path(&self, start: usize, goal: usize) -> Option<Vec<usize>> {
+        if !self.nodes.contains_key(&start) {
+            return None;
+        }
+        let mut prev: HashMap<usize, usize> = HashMap::new();
+        let mut visited = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(node) = queue.pop_front() {
+            if node == goal {
+                let mut path = vec![goal];
+                let mut current = goal;
+                while let Some(&p) = prev.get(&current) {
+                    path.push(p);
+                    current = p;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            let mut neighbors = self.neighbors(node).to_vec();
+            neighbors.sort_unstable();
+            for next in neighbors {
+                if visited.insert(next) {
+                    prev.insert(next, node);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+
     /// Depth-first search
     fn dfs(&self, start: usize) -> Vec<usize> {
         let mut visited = HashSet::new();
@@ -107,35 +271,368 @@ impl Graph {
         result
     }
 
-    /// PageRank algorithm
-    fn pagerank(&self, iterations: usize, damping: f64) -> HashMap<usize, f64> {
-        let n = self.node_count() as f64;
-        let mut ranks: HashMap<usize, f64> = self.nodes.keys().map(|&id| (id, 1.0 / n)).collect();
+    /// Node ids in ascending order
+    fn sorted_ids(&self) -> Vec<usize> {
+        let mut ids: Vec<usize> = self.nodes.keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
 
-        for _ in 0..iterations {
-            let mut new_ranks: HashMap<usize, f64> = self
-                .nodes
-                .keys()
-                .map(|&id| (id, (1.0 - damping) / n))
-                .collect();
-
-            for (&node, &rank) in &ranks {
-                let neighbors = self.neighbors(node);
-                if neighbors.is_empty() {
+    fn out_degree(&self, id: usize) -> usize {
+        self.neighbors(id).len()
+    }
+
+    /// Number of edges pointing at `id` (scans every adjacency list)
+    fn in_degree(&self, id: usize) -> usize {
+        self.edges
+            .values()
+            .map(|targets| targets.iter().filter(|&&to| to == id).count())
+            .sum()
+    }
+
+    /// Out-degree of each node normalized by the n - 1 possible targets
+    fn degree_centrality(&self) -> HashMap<usize, f64> {
+        let others = self.node_count().saturating_sub(1);
+        self.nodes
+            .keys()
+            .map(|&id| {
+                let centrality = if others == 0 {
+                    0.0
+                } else {
+                    self.out_degree(id) as f64 / others as f64
+                };
+                (id, centrality)
+            })
+            .collect()
+    }
+
+    /// Weakly connected components: edges are followed in both directions
+    ///
+    /// Each component is sorted by id and components are ordered by their
+    /// smallest member.
+    fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut undirected: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (&from, targets) in &self.edges {
+            for &to in targets {
+                undirected.entry(from).or_default().push(to);
+                undirected.entry(to).or_default().push(from);
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut components = Vec::new();
+        for id in self.sorted_ids() {
+            if !seen.insert(id) {
+                continue;
+            }
+            let mut component = vec![id];
+            let mut queue = VecDeque::from([id]);
+            while let Some(node) = queue.pop_front() {
+                for &next in undirected.get(&node).map(|v| v.as_slice()).unwrap_or(&[]) {
+                    if self.nodes.contains_key(&next) && seen.insert(next) {
+                        component.push(next);
+                        queue.push_back(next);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    fn has_cycle(&self) -> bool {
+        self.find_cycle().is_some()
+    }
+
+    /// First directed cycle found by DFS, as its member nodes in edge order
+    ///
+    /// Roots are tried in ascending id order so the same cycle is reported
+    /// every run. Meant for directed graphs: in undirected mode every edge
+    /// counts as a two-node cycle.
+    fn find_cycle(&self) -> Option<Vec<usize>> {
+        let mut finished = HashSet::new();
+        for id in self.sorted_ids() {
+            if finished.contains(&id) {
+                continue;
+            }
+            let mut stack = Vec::new();
+            if let Some(cycle) = self.cycle_from(id, &mut stack, &mut finished) {
+                return Some(cycle);
+            }
+        }
+        None
+    }
+
+    /// DFS helper for `find_cycle`; `stack` is the current recursion path
+    fn cycle_from(
+        &self,
+        node: usize,
+        stack: &mut Vec<usize>,
+        finished: &mut HashSet<usize>,
+    ) -> Option<Vec<usize>> {
+        stack.push(node);
+        for &next in self.neighbors(node) {
+            if let Some(pos) = stack.iter().position(|&n| n == next) {
+                return Some(stack[pos..].to_vec());
+            }
+            if !finished.contains(&next) {
+                if let Some(cycle) = self.cycle_from(next, stack, finished) {
+                    return Some(cycle);
+                }
+            }
+        }
+        stack.pop();
+        finished.insert(node);
+        None
+    }
+
+    /// Nodes in dependency order (Kahn's algorithm)
+    ///
+    /// Among nodes that are ready at the same time the lowest id goes
+    /// first, so the order is reproducible. Errors if a cycle prevents
+    /// ordering every node.
+    fn topological_sort(&self) -> Result<Vec<usize>, String> {
+        let mut in_degree: HashMap<usize, usize> = self.nodes.keys().map(|&id| (id, 0)).collect();
+        for targets in self.edges.values() {
+            for to in targets {
+                if let Some(d) = in_degree.get_mut(to) {
+                    *d += 1;
+                }
+            }
+        }
+
+        let mut ready: BinaryHeap<Reverse<usize>> = in_degree
+            .iter()
+            .filter(|(_, &d)| d == 0)
+            .map(|(&id, _)| Reverse(id))
+            .collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+
+        while let Some(Reverse(node)) = ready.pop() {
+            order.push(node);
+            for to in self.neighbors(node) {
+                if let Some(d) = in_degree.get_mut(to) {
+                    *d -= 1;
+                    if *d == 0 {
+                        ready.push(Reverse(*to));
+                    }
+                }
+            }
+        }
+
+        if order.len() == self.nodes.len() {
+            Ok(order)
+        } else {
+            Err(format!(
+                "graph contains a cycle: {} of {} nodes could not be ordered",
+                self.nodes.len() - order.len(),
+                self.nodes.len()
+            ))
+        }
+    }
+
+    /// Strongly connected components (Tarjan's algorithm)
+    ///
+    /// Each component is sorted by id and components are ordered by their
+    /// smallest member.
+    fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let mut state = Tarjan::default();
+        for id in self.sorted_ids() {
+            if !state.index.contains_key(&id) {
+                self.tarjan_visit(id, &mut state);
+            }
+        }
+        let mut components = state.components;
+        for component in &mut components {
+            component.sort_unstable();
+        }
+        components.sort_unstable_by_key(|c| c[0]);
+        components
+    }
+
+    fn tarjan_visit(&self, node: usize, state: &mut Tarjan) {
+        state.index.insert(node, state.next_index);
+        state.low_link.insert(node, state.next_index);
+        state.next_index += 1;
+        state.stack.push(node);
+        state.on_stack.insert(node);
+
+        for &next in self.neighbors(node) {
+            if !self.nodes.contains_key(&next) {
+                continue;
+            }
+            if !state.index.contains_key(&next) {
+                self.tarjan_visit(next, state);
+                let low = state.low_link[&node].min(state.low_link[&next]);
+                state.low_link.insert(node, low);
+            } else if state.on_stack.contains(&next) {
+                let low = state.low_link[&node].min(state.index[&next]);
+                state.low_link.insert(node, low);
+            }
+        }
+
+        // `node` is the root of a component: pop it off the stack
+        if state.low_link[&node] == state.index[&node] {
+            let mut component = Vec::new();
+            while let Some(member) = state.stack.pop() {
+                state.on_stack.remove(&member);
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            state.components.push(component);
+        }
+    }
+
+    /// Cheapest path from `start` to `goal` (Dijkstra) and its total cost
+    ///
+    /// Weights must be non-negative. Equal-cost frontier entries are
+    /// expanded lowest id first, so the same path is returned every run.
+    fn shortest_path(&self, start: usize, goal: usize) -> Option<(Vec<usize>, f64)> {
+        if !self.nodes.contains_key(&start) {
+            return None;
+        }
+        let mut dist: HashMap<usize, f64> = HashMap::new();
+        let mut prev: HashMap<usize, usize> = HashMap::new();
+        let mut heap = BinaryHeap::new();
+
+        dist.insert(start, 0.0);
+        heap.push(PathState {
+            cost: 0.0,
+            node: start,
+        });
+
+        while let Some(PathState { cost, node }) = heap.pop() {
+            if node == goal {
+                let mut path = vec![goal];
+                let mut current = goal;
+                while let Some(&p) = prev.get(&current) {
+                    path.push(p);
+                    current = p;
+                }
+                path.reverse();
+                return Some((path, cost));
+            }
+            if cost > dist.get(&node).copied().unwrap_or(f64::INFINITY) {
+                continue;
+            }
+            for (next, weight) in self.weighted_neighbors(node) {
+                let candidate = cost + weight;
+                if candidate < dist.get(&next).copied().unwrap_or(f64::INFINITY) {
+                    dist.insert(next, candidate);
+                    prev.insert(next, node);
+                    heap.push(PathState {
+                        cost: candidate,
+                        node: next,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Graphviz DOT source, nodes and edges in id order
+    ///
+    /// Edges with a weight other than 1.0 carry it as their label.
+    /// Undirected graphs are written as `graph` with each edge once.
+    fn to_dot(&self) -> String {
+        let (keyword, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let mut dot = format!("{} {{\n", keyword);
+        for id in self.sorted_ids() {
+            let label = self.nodes[&id].label.replace('"', "\\\"");
+            dot.push_str(&format!("    {} [label=\"{}\"];\n", id, label));
+        }
+        for from in self.sorted_ids() {
+            for (to, weight) in self.weighted_neighbors(from) {
+                if !self.directed && to < from {
                     continue;
                 }
-                let share = damping * rank / neighbors.len() as f64;
-                for &neighbor in neighbors {
-                    *new_ranks
-                        .get_mut(&neighbor)
-                        .expect("neighbor exists in ranks") += share;
+                dot.push_str(&format!("    {} {} {}", from, arrow, to));
+                if weight != 1.0 {
+                    dot.push_str(&format!(" [label=\"{}\"]", weight));
                 }
+                dot.push_str(";\n");
             }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// PageRank algorithm
+    fn pagerank(&self, iterations: usize, damping: f64) -> HashMap<usize, f64> {
+        let mut ranks = self.initial_ranks();
+        for _ in 0..iterations {
+            ranks = self.pagerank_step(&ranks, damping);
+        }
+        ranks
+    }
 
+    /// PageRank that stops once the L1 change between iterations drops
+    /// below `tol`, returning the ranks and the iterations performed
+    fn pagerank_until_converged(
+        &self,
+        max_iter: usize,
+        damping: f64,
+        tol: f64,
+    ) -> (HashMap<usize, f64>, usize) {
+        let mut ranks = self.initial_ranks();
+        for iteration in 1..=max_iter {
+            let new_ranks = self.pagerank_step(&ranks, damping);
+            let delta: f64 = self
+                .sorted_ids()
+                .iter()
+                .map(|id| (new_ranks[id] - ranks[id]).abs())
+                .sum();
             ranks = new_ranks;
+            if delta < tol {
+                return (ranks, iteration);
+            }
         }
+        (ranks, max_iter)
+    }
 
-        ranks
+    fn initial_ranks(&self) -> HashMap<usize, f64> {
+        let n = self.node_count() as f64;
+        self.nodes.keys().map(|&id| (id, 1.0 / n)).collect()
+    }
+
+    /// One PageRank iteration; nodes are visited in id order so the
+    /// floating-point sums are identical every run
+    ///
+    /// Rank held by dangling nodes (no outgoing edges) is spread evenly
+    /// over all nodes, so the total stays 1.0.
+    fn pagerank_step(&self, ranks: &HashMap<usize, f64>, damping: f64) -> HashMap<usize, f64> {
+        let n = self.node_count() as f64;
+        let dangling: f64 = self
+            .sorted_ids()
+            .iter()
+            .filter(|&&id| self.neighbors(id).is_empty())
+            .map(|id| ranks[id])
+            .sum();
+        let base = (1.0 - damping) / n + damping * dangling / n;
+        let mut new_ranks: HashMap<usize, f64> = self.nodes.keys().map(|&id| (id, base)).collect();
+
+        for node in self.sorted_ids() {
+            let neighbors = self.neighbors(node);
+            if neighbors.is_empty() {
+                continue;
+            }
+            let share = damping * ranks[&node] / neighbors.len() as f64;
+            for &neighbor in neighbors {
+                *new_ranks
+                    .get_mut(&neighbor)
+                    .expect("neighbor exists in ranks") += share;
+            }
+        }
+
+        new_ranks
     }
 }
 
@@ -167,6 +664,32 @@ fn basic_demo() {
         println!("   {} -> {:?}", i, neighbors);
     }
     println!();
+
+    // Weighted edges sit alongside plain ones (which default to 1.0)
+    graph.add_weighted_edge(4, 0, 2.5);
+    println!("   Weighted adjacency (after adding 4 -(2.5)-> 0):");
+    for i in 0..5 {
+        let edges: Vec<String> = graph
+            .weighted_neighbors(i)
+            .iter()
+            .map(|(to, w)| format!("{}({:.1})", to, w))
+            .collect();
+        println!("   {} -> [{}]", i, edges.join(", "));
+    }
+    println!();
+
+    let mut undirected = Graph::new_undirected();
+    for i in 0..3 {
+        undirected.add_node(Node::new(i, &format!("Node{}", i)));
+    }
+    undirected.add_edge(0, 1);
+    undirected.add_edge(1, 2);
+    println!(
+        "   Undirected 0-1-2: {} edges, 1 -> {:?}",
+        undirected.edge_count(),
+        undirected.neighbors(1)
+    );
+    println!();
 }
 
 /// Demonstrate graph traversals
@@ -191,6 +714,116 @@ fn traversal_demo() {
 
     println!("   BFS from node 0: {:?}", bfs);
     println!("   DFS from node 0: {:?}", dfs);
+    if let Some(path) = graph.bfs_path(0, 5) {
+        println!("   Shortest hop path 0 → 5: {:?}", path);
+    }
+    println!();
+
+    graph.remove_edge(0, 2);
+    println!("   After removing edge 0 -> 2:");
+    println!("   BFS from node 0: {:?}", graph.bfs(0));
+    graph.remove_node(3);
+    println!("   After removing node 3:");
+    println!(
+        "   BFS from node 0: {:?} ({} nodes, {} edges)",
+        graph.bfs(0),
+        graph.node_count(),
+        graph.edge_count()
+    );
+    println!();
+}
+
+/// Demonstrate connected components
+fn components_demo() {
+    println!("🧩 Connected Components");
+    println!();
+
+    let mut graph = Graph::new();
+    for i in 0..8 {
+        graph.add_node(Node::new(i, &format!("N{}", i)));
+    }
+    // Two clusters whose edges point in mixed directions, plus an isolated node
+    graph.add_edge(0, 1);
+    graph.add_edge(2, 1);
+    graph.add_edge(3, 2);
+    graph.add_edge(4, 5);
+    graph.add_edge(6, 5);
+
+    let components = graph.connected_components();
+    println!("   {} weakly connected components:", components.len());
+    for (i, component) in components.iter().enumerate() {
+        println!("   {}. {:?}", i + 1, component);
+    }
+    println!();
+}
+
+/// Demonstrate cycle detection and topological ordering
+fn cycle_demo() {
+    println!("🔄 Cycles and Topological Order");
+    println!();
+
+    let mut graph = Graph::new();
+    for i in 0..5 {
+        graph.add_node(Node::new(i, &format!("N{}", i)));
+    }
+    graph.add_edge(0, 1);
+    graph.add_edge(1, 2);
+    graph.add_edge(2, 3);
+    graph.add_edge(1, 4);
+
+    println!("   DAG 0→1→2→3, 1→4: has cycle = {}", graph.has_cycle());
+    if let Ok(order) = graph.topological_sort() {
+        println!("   Topological order:  {:?}", order);
+    }
+
+    graph.add_edge(3, 1);
+    match graph.find_cycle() {
+        Some(cycle) => println!("   After adding 3→1:  cycle {:?}", cycle),
+        None => println!("   After adding 3→1:  no cycle"),
+    }
+    if let Err(e) = graph.topological_sort() {
+        println!("   Topological sort:   {}", e);
+    }
+    println!(
+        "   Strongly connected: {:?}",
+        graph.strongly_connected_components()
+    );
+    println!();
+}
+
+/// Demonstrate weighted shortest paths
+fn shortest_path_demo() {
+    println!("🛣️  Weighted Shortest Paths (Dijkstra)");
+    println!();
+
+    let mut graph = Graph::new();
+    let cities = ["Berlin", "Leipzig", "Nuremberg", "Frankfurt", "Munich"];
+    for (i, city) in cities.iter().enumerate() {
+        graph.add_node(Node::new(i, city));
+    }
+
+    graph.add_weighted_edge(0, 1, 190.0);
+    graph.add_weighted_edge(0, 3, 545.0);
+    graph.add_weighted_edge(1, 2, 280.0);
+    graph.add_weighted_edge(1, 3, 395.0);
+    graph.add_weighted_edge(2, 4, 170.0);
+    graph.add_weighted_edge(3, 4, 395.0);
+
+    println!("   Graphviz export:");
+    for line in graph.to_dot().lines() {
+        println!("   {}", line);
+    }
+    println!();
+
+    for (from, to) in [(0, 4), (0, 3), (4, 0)] {
+        match graph.shortest_path(from, to) {
+            Some((path, cost)) => {
+                let names: Vec<&str> = path.iter().map(|&id| cities[id]).collect();
+                println!("   {} (cost {:.0} km)", names.join(" → "), cost);
+            }
+            None => println!("   {} → {}: unreachable", cities[from], cities[to]),
+        }
+    }
     println!();
 }
 
@@ -216,16 +849,39 @@ fn pagerank_demo() {
 
     let ranks = graph.pagerank(20, 0.85);
 
-    println!("   {:>6} │ {:>10}", "Page", "Rank");
-    println!("   ───────┼───────────");
+    let centrality = graph.degree_centrality();
+
+    println!(
+        "   {:>6} │ {:>10} │ {:>3} │ {:>3} │ {:>10}",
+        "Page", "Rank", "In", "Out", "Centrality"
+    );
+    println!("   ───────┼────────────┼─────┼─────┼───────────");
 
     let mut sorted: Vec<_> = ranks.iter().collect();
     sorted.sort_by(|a, b| b.1.partial_cmp(a.1).expect("valid rank comparison"));
 
     for (page, rank) in sorted {
-        println!("   {:>6} │ {:>10.4}", page, rank);
+        println!(
+            "   {:>6} │ {:>10.4} │ {:>3} │ {:>3} │ {:>10.4}",
+            page,
+            rank,
+            graph.in_degree(*page),
+            graph.out_degree(*page),
+            centrality[page]
+        );
     }
     println!();
+
+    let (converged, iterations) = graph.pagerank_until_converged(100, 0.85, 1e-10);
+    let max_diff = ranks
+        .iter()
+        .map(|(id, r)| (r - converged[id]).abs())
+        .fold(0.0, f64::max);
+    println!(
+        "   Converged to 1e-10 in {} of 100 iterations (max diff vs 20 fixed: {:.2e})",
+        iterations, max_diff
+    );
+    println!();
 }
 
 /// Demonstrate determinism
@@ -311,6 +967,18 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
+    components_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
+    cycle_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
+    shortest_path_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
     pagerank_demo();
     println!("{}", "─".repeat(70));
     println!();
@@ -361,6 +1029,262 @@ mod tests {
         assert!(graph.neighbors(1).is_empty());
     }
 
+    #[test]
+    fn test_weighted_and_unweighted_edges() {
+        let mut graph = Graph::new();
+        for i in 0..3 {
+            graph.add_node(Node::new(i, ""));
+        }
+        graph.add_edge(0, 1);
+        graph.add_weighted_edge(0, 2, 4.5);
+        graph.add_weighted_edge(1, 2, 0.25);
+
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.neighbors(0), &[1, 2]);
+        assert_eq!(graph.weighted_neighbors(0), vec![(1, 1.0), (2, 4.5)]);
+        assert_eq!(graph.weighted_neighbors(1), vec![(2, 0.25)]);
+        assert!(graph.weighted_neighbors(2).is_empty());
+    }
+
+    #[test]
+    fn test_shortest_path_prefers_cheaper_route() {
+        let mut graph = Graph::new();
+        for i in 0..4 {
+            graph.add_node(Node::new(i, ""));
+        }
+        // Direct 0 -> 3 costs 10; the detour 0 -> 1 -> 2 -> 3 costs 6
+        graph.add_weighted_edge(0, 3, 10.0);
+        graph.add_weighted_edge(0, 1, 2.0);
+        graph.add_weighted_edge(1, 2, 2.0);
+        graph.add_weighted_edge(2, 3, 2.0);
+
+        let (path, cost) = graph.shortest_path(0, 3).expect("reachable");
+        assert_eq!(path, vec![0, 1, 2, 3]);
+        assert!((cost - 6.0).abs() < 1e-12);
+
+        assert_eq!(graph.shortest_path(0, 0), Some((vec![0], 0.0)));
+        assert!(graph.shortest_path(3, 0).is_none());
+    }
+
+    #[test]
+    fn test_shortest_path_tie_is_deterministic() {
+        let mut graph = Graph::new();
+        for i in 0..4 {
+            graph.add_node(Node::new(i, ""));
+        }
+        // Two equal-cost routes 0 -> 2 -> 3 and 0 -> 1 -> 3
+        graph.add_weighted_edge(0, 2, 1.0);
+        graph.add_weighted_edge(0, 1, 1.0);
+        graph.add_weighted_edge(2, 3, 1.0);
+        graph.add_weighted_edge(1, 3, 1.0);
+
+        let first = graph.shortest_path(0, 3).expect("reachable");
+        assert_eq!(first.0, vec![0, 1, 3]);
+        for _ in 0..10 {
+            assert_eq!(graph.shortest_path(0, 3), Some(first.clone()));
+        }
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let mut graph = Graph::new();
+        for i in 0..6 {
+            graph.add_node(Node::new(i, ""));
+        }
+        // Cluster {0, 2, 4} and cluster {1, 3, 5}, linked against edge direction
+        graph.add_edge(4, 0);
+        graph.add_edge(2, 4);
+        graph.add_edge(1, 5);
+        graph.add_edge(3, 5);
+
+        let components = graph.connected_components();
+        assert_eq!(components, vec![vec![0, 2, 4], vec![1, 3, 5]]);
+    }
+
+    #[test]
+    fn test_undirected_edges_are_symmetric() {
+        let mut graph = Graph::new_undirected();
+        for i in 0..3 {
+            graph.add_node(Node::new(i, ""));
+        }
+        graph.add_edge(0, 1);
+        graph.add_weighted_edge(1, 2, 3.0);
+        graph.add_edge(2, 2);
+
+        assert!(graph.neighbors(0).contains(&1));
+        assert!(graph.neighbors(1).contains(&0));
+        assert_eq!(graph.weighted_neighbors(2), vec![(1, 3.0), (2, 1.0)]);
+        assert_eq!(graph.edge_count(), 3);
+
+        let mut directed = Graph::new();
+        directed.add_node(Node::new(0, ""));
+        directed.add_node(Node::new(1, ""));
+        directed.add_edge(0, 1);
+        assert!(directed.neighbors(1).is_empty());
+    }
+
+    #[test]
+    fn test_remove_node_drops_incident_edges() {
+        let mut graph = Graph::new();
+        for i in 0..4 {
+            graph.add_node(Node::new(i, ""));
+        }
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(1, 3);
+        graph.add_edge(3, 0);
+
+        assert!(graph.remove_node(1));
+        assert!(!graph.remove_node(1));
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 1);
+        assert!(graph.neighbors(1).is_empty());
+        assert!(graph.neighbors(0).is_empty());
+        assert_eq!(graph.bfs(3), vec![3, 0]);
+    }
+
+    #[test]
+    fn test_remove_edge() {
+        let mut graph = Graph::new();
+        for i in 0..3 {
+            graph.add_node(Node::new(i, ""));
+        }
+        graph.add_weighted_edge(0, 1, 5.0);
+        graph.add_weighted_edge(0, 2, 7.0);
+
+        assert!(graph.remove_edge(0, 1));
+        assert!(!graph.remove_edge(0, 1));
+        assert_eq!(graph.edge_count(), 1);
+        assert_eq!(graph.weighted_neighbors(0), vec![(2, 7.0)]);
+        assert_eq!(graph.bfs(0), vec![0, 2]);
+
+        let mut undirected = Graph::new_undirected();
+        undirected.add_node(Node::new(0, ""));
+        undirected.add_node(Node::new(1, ""));
+        undirected.add_edge(0, 1);
+        assert!(undirected.remove_edge(1, 0));
+        assert_eq!(undirected.edge_count(), 0);
+        assert!(undirected.neighbors(0).is_empty());
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        let mut ring = Graph::new();
+        for i in 0..3 {
+            ring.add_node(Node::new(i, ""));
+        }
+        ring.add_edge(0, 1);
+        ring.add_edge(1, 2);
+        ring.add_edge(2, 0);
+
+        assert!(ring.has_cycle());
+        let mut cycle = ring.find_cycle().expect("ring has a cycle");
+        cycle.sort_unstable();
+        assert_eq!(cycle, vec![0, 1, 2]);
+
+        let mut tree = Graph::new();
+        for i in 0..5 {
+            tree.add_node(Node::new(i, ""));
+        }
+        tree.add_edge(0, 1);
+        tree.add_edge(0, 2);
+        tree.add_edge(1, 3);
+        tree.add_edge(1, 4);
+        assert!(!tree.has_cycle());
+        assert!(tree.find_cycle().is_none());
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let mut graph = Graph::new();
+        for i in 0..5 {
+            graph.add_node(Node::new(i, ""));
+        }
+        // 1 -> 2 -> 3 -> 1 is a cycle; 0 and 4 are isolated
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 1);
+
+        let sccs = graph.strongly_connected_components();
+        assert_eq!(sccs, vec![vec![0], vec![1, 2, 3], vec![4]]);
+        assert_eq!(sccs.iter().filter(|c| c.len() > 1).count(), 1);
+
+        // Edges into and out of the cycle don't merge 0 or 4 into it
+        graph.add_edge(0, 1);
+        graph.add_edge(3, 4);
+        assert_eq!(graph.strongly_connected_components(), sccs);
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let mut dag = Graph::new();
+        for i in 0..6 {
+            dag.add_node(Node::new(i, ""));
+        }
+        dag.add_edge(5, 2);
+        dag.add_edge(5, 0);
+        dag.add_edge(4, 0);
+        dag.add_edge(4, 1);
+        dag.add_edge(2, 3);
+        dag.add_edge(3, 1);
+
+        let order = dag.topological_sort().expect("DAG sorts");
+        assert_eq!(order, vec![4, 5, 0, 2, 3, 1]);
+
+        dag.add_edge(1, 5);
+        let err = dag.topological_sort().expect_err("cycle must be rejected");
+        assert!(err.contains("cycle"));
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut graph = Graph::new();
+        graph.add_node(Node::new(0, "A"));
+        graph.add_node(Node::new(1, "B"));
+        graph.add_node(Node::new(2, "C"));
+        graph.add_edge(0, 1);
+        graph.add_weighted_edge(1, 2, 2.5);
+        graph.add_edge(2, 0);
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph {"));
+        assert!(dot.trim_end().ends_with('}'));
+        assert_eq!(dot.lines().filter(|l| l.contains("[label=\"")).count(), 4);
+        assert!(dot.contains("    0 [label=\"A\"];"));
+        assert!(dot.contains("    1 [label=\"B\"];"));
+        assert!(dot.contains("    2 [label=\"C\"];"));
+        assert_eq!(dot.lines().filter(|l| l.contains(" -> ")).count(), 3);
+        assert!(dot.contains("    0 -> 1;"));
+        assert!(dot.contains("    1 -> 2 [label=\"2.5\"];"));
+        assert!(dot.contains("    2 -> 0;"));
+    }
+
+    #[test]
+    fn test_degrees_hub_and_spoke() {
+        let mut graph = Graph::new();
+        for i in 0..5 {
+            graph.add_node(Node::new(i, ""));
+        }
+        // Hub 0 links out to every spoke; every spoke links back
+        for spoke in 1..5 {
+            graph.add_edge(0, spoke);
+            graph.add_edge(spoke, 0);
+        }
+        graph.add_edge(1, 2);
+
+        assert_eq!(graph.in_degree(0), 4);
+        assert_eq!(graph.out_degree(0), 4);
+        assert_eq!(graph.in_degree(2), 2);
+        assert_eq!(graph.out_degree(1), 2);
+        let hub_in = graph.in_degree(0);
+        assert!((1..5).all(|spoke| graph.in_degree(spoke) < hub_in));
+
+        let centrality = graph.degree_centrality();
+        assert!((centrality[&0] - 1.0).abs() < 1e-12);
+        assert!((centrality[&1] - 0.5).abs() < 1e-12);
+        assert!((centrality[&3] - 0.25).abs() < 1e-12);
+    }
+
     #[test]
     fn test_bfs() {
         let mut graph = Graph::new();
@@ -378,6 +1302,31 @@ mod tests {
         assert!(result.contains(&3));
     }
 
+    #[test]
+    fn test_bfs_path_is_shortest_and_deterministic() {
+        let mut graph = Graph::new();
+        for i in 0..5 {
+            graph.add_node(Node::new(i, ""));
+        }
+        // Two 2-hop routes (via 2 and via 1) and a 3-hop detour
+        graph.add_edge(0, 2);
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 4);
+        graph.add_edge(2, 3);
+        graph.add_edge(1, 3);
+        graph.add_edge(4, 2);
+
+        let path = graph.bfs_path(0, 3).expect("reachable");
+        assert_eq!(path, vec![0, 1, 3]);
+        assert_eq!(path.len() - 1, 2);
+        for _ in 0..5 {
+            assert_eq!(graph.bfs_path(0, 3), Some(path.clone()));
+        }
+
+        assert_eq!(graph.bfs_path(0, 0), Some(vec![0]));
+        assert!(graph.bfs_path(3, 0).is_none());
+    }
+
     #[test]
     fn test_dfs() {
         let mut graph = Graph::new();
@@ -408,6 +1357,51 @@ mod tests {
         assert!((sum - 1.0).abs() < 0.01, "PageRank should sum to ~1.0");
     }
 
+    #[test]
+    fn test_pagerank_conserves_rank_with_sink() {
+        let mut graph = Graph::new();
+        for i in 0..4 {
+            graph.add_node(Node::new(i, ""));
+        }
+        // Node 3 is a sink with no outgoing edges
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 3);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+
+        for iterations in [1, 5, 50] {
+            let ranks = graph.pagerank(iterations, 0.85);
+            let sum: f64 = ranks.values().sum();
+            assert!((sum - 1.0).abs() < 1e-9, "sum = {}", sum);
+        }
+        let ranks = graph.pagerank(50, 0.85);
+        assert!(ranks[&3] > ranks[&0]);
+    }
+
+    #[test]
+    fn test_pagerank_early_convergence() {
+        let mut graph = Graph::new();
+        for i in 0..4 {
+            graph.add_node(Node::new(i, ""));
+        }
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 0);
+        graph.add_edge(0, 2);
+
+        let (ranks, iterations) = graph.pagerank_until_converged(500, 0.85, 1e-12);
+        assert!(iterations < 500, "took {} iterations", iterations);
+
+        let fixed = graph.pagerank(500, 0.85);
+        for (id, rank) in &ranks {
+            assert!((rank - fixed[id]).abs() < 1e-10);
+        }
+
+        let again = graph.pagerank_until_converged(500, 0.85, 1e-12);
+        assert_eq!(again, (ranks, iterations));
+    }
+
     #[test]
     fn test_traversal_determinism() {
         let mut graph = Graph::new();
@@ -6,25 +6,91 @@
 /// - Deterministic scheduling
 ///
 /// **VALIDATION:** `make run-ch17`
-use anyhow::Result;
-use std::collections::{HashMap, VecDeque};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
 
 /// Task status
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 #[allow(dead_code)]
 enum TaskStatus {
+    #[default]
     Pending,
     Running,
     Completed,
     Failed,
+    /// Not run because a dependency failed or was skipped
+    Skipped,
 }
 
+/// How many times a failing task is attempted before it is marked failed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct RetryPolicy {
+    max_attempts: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_attempts: 1 }
+    }
+}
+
+/// Work performed when a task runs
+type TaskAction = Box<dyn Fn() -> Result<(), String>>;
+
+/// Predicate deciding at run time whether a task should run
+type TaskCondition = Box<dyn Fn() -> bool>;
+
 /// Workflow task
-#[derive(Debug, Clone)]
+///
+/// Only the definition is serialized; actions, conditions and run state
+/// must be reattached after loading.
+#[derive(Serialize, Deserialize)]
 struct Task {
     id: String,
+    #[serde(default)]
     dependencies: Vec<String>,
+    #[serde(skip)]
     status: TaskStatus,
+    #[serde(skip)]
+    action: Option<TaskAction>,
+    #[serde(skip)]
+    condition: Option<TaskCondition>,
+    #[serde(default)]
+    retry: RetryPolicy,
+    /// Among tasks ready at the same time, higher priority runs first
+    #[serde(default)]
+    priority: u32,
+    /// Expected run time in arbitrary units (e.g. seconds)
+    #[serde(default)]
+    estimated_duration: u64,
+    /// Times the action was run in the last execution
+    #[serde(skip)]
+    attempts: u32,
+    /// Error reported by the action, if it failed
+    #[serde(skip)]
+    error: Option<String>,
+}
+
+impl fmt::Debug for Task {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Task")
+            .field("id", &self.id)
+            .field("dependencies", &self.dependencies)
+            .field("status", &self.status)
+            .field("has_action", &self.action.is_some())
+            .field("has_condition", &self.condition.is_some())
+            .field("retry", &self.retry)
+            .field("priority", &self.priority)
+            .field("estimated_duration", &self.estimated_duration)
+            .field("attempts", &self.attempts)
+            .field("error", &self.error)
+            .finish()
+    }
 }
 
 impl Task {
@@ -33,6 +99,13 @@ impl Task {
             id: id.to_string(),
             dependencies: Vec::new(),
             status: TaskStatus::Pending,
+            action: None,
+            condition: None,
+            retry: RetryPolicy::default(),
+            priority: 0,
+            estimated_duration: 0,
+            attempts: 0,
+            error: None,
         }
     }
 
@@ -40,9 +113,63 @@ impl Task {
         self.dependencies.push(dep.to_string());
         self
     }
+
+    /// Attach the work to run; tasks without an action always succeed
+    fn with_action(mut self, action: impl Fn() -> Result<(), String> + 'static) -> Self {
+        self.action = Some(Box::new(action));
+        self
+    }
+
+    /// Only run when `condition` holds; otherwise the task and everything
+    /// downstream of it is skipped
+    fn when(mut self, condition: impl Fn() -> bool + 'static) -> Self {
+        self.condition = Some(Box::new(condition));
+        self
+    }
+
+    fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    fn with_duration(mut self, estimated_duration: u64) -> Self {
+        self.estimated_duration = estimated_duration;
+        self
+    }
+
+    fn with_retry(mut self, max_attempts: u32) -> Self {
+        self.retry = RetryPolicy {
+            max_attempts: max_attempts.max(1),
+        };
+        self
+    }
+
+    /// Run the action until it succeeds or the retry budget is spent
+    fn run(&mut self) -> Result<(), String> {
+        self.attempts = 0;
+        let Some(action) = &self.action else {
+            self.attempts = 1;
+            return Ok(());
+        };
+        loop {
+            self.attempts += 1;
+            match action() {
+                Ok(()) => return Ok(()),
+                Err(e) if self.attempts >= self.retry.max_attempts => return Err(e),
+                Err(_) => continue,
+            }
+        }
+    }
+}
+
+/// On-disk form of a workflow: its tasks, sorted by id
+#[derive(Serialize, Deserialize)]
+struct WorkflowFile<T> {
+    tasks: Vec<T>,
 }
 
 /// Workflow DAG
+#[derive(Debug)]
 struct Workflow {
     tasks: HashMap<String, Task>,
     execution_order: Vec<String>,
@@ -60,79 +187,431 @@ impl Workflow {
         self.tasks.insert(task.id.clone(), task);
     }
 
+    /// Add a task only if every dependency is already in the workflow
+    ///
+    /// Catches typos in `depends_on` at definition time instead of when the
+    /// execution order is computed. Dependencies must be added first.
+    fn add_task_checked(&mut self, task: Task) -> Result<(), String> {
+        if let Some(dep) = task
+            .dependencies
+            .iter()
+            .find(|dep| !self.tasks.contains_key(*dep))
+        {
+            return Err(format!(
+                "Unknown dependency: {} (required by {})",
+                dep, task.id
+            ));
+        }
+        self.add_task(task);
+        Ok(())
+    }
+
+    /// Write the workflow definition as JSON
+    fn save(&self, path: &Path) -> Result<()> {
+        let mut tasks: Vec<&Task> = self.tasks.values().collect();
+        tasks.sort_unstable_by(|a, b| a.id.cmp(&b.id));
+        let json = serde_json::to_string_pretty(&WorkflowFile { tasks })?;
+        fs::write(path, json).with_context(|| format!("writing workflow {}", path.display()))
+    }
+
+    /// Read a workflow saved with [`Workflow::save`], rejecting duplicate
+    /// task ids and dependencies on tasks that are not defined
+    fn load(path: &Path) -> Result<Workflow> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("reading workflow {}", path.display()))?;
+        let file: WorkflowFile<Task> = serde_json::from_str(&text)
+            .with_context(|| format!("parsing workflow {}", path.display()))?;
+
+        let mut ids = HashSet::new();
+        for task in &file.tasks {
+            if !ids.insert(task.id.as_str()) {
+                anyhow::bail!(
+                    "invalid workflow {}: duplicate task {}",
+                    path.display(),
+                    task.id
+                );
+            }
+        }
+        for task in &file.tasks {
+            if let Some(dep) = task.dependencies.iter().find(|d| !ids.contains(d.as_str())) {
+                anyhow::bail!(
+                    "invalid workflow {}: task {} depends on unknown task {}",
+                    path.display(),
+                    task.id,
+                    dep
+                );
+            }
+        }
+
+        let mut workflow = Workflow::new();
+        for task in file.tasks {
+            workflow.add_task(task);
+        }
+        Ok(workflow)
+    }
+
+    /// Remove a task that nothing else depends on
+    fn remove_task(&mut self, id: &str) -> Result<(), String> {
+        if !self.tasks.contains_key(id) {
+            return Err(format!("Unknown task: {}", id));
+        }
+        let mut dependents: Vec<&str> = self
+            .tasks
+            .values()
+            .filter(|t| t.dependencies.iter().any(|d| d == id))
+            .map(|t| t.id.as_str())
+            .collect();
+        if !dependents.is_empty() {
+            dependents.sort_unstable();
+            return Err(format!(
+                "Cannot remove {}: required by {}",
+                id,
+                dependents.join(", ")
+            ));
+        }
+        self.tasks.remove(id);
+        self.execution_order.retain(|t| t != id);
+        Ok(())
+    }
+
     /// Topological sort for execution order
+    ///
+    /// Ready tasks are taken by descending priority, then by id, so the
+    /// order is fully deterministic.
     fn compute_execution_order(&mut self) -> Result<(), String> {
+        let all: HashSet<&str> = self.tasks.keys().map(|id| id.as_str()).collect();
+        self.execution_order = self.topological_order(&all)?;
+        Ok(())
+    }
+
+    /// Tasks needed to run `target`, ending with `target` itself
+    ///
+    /// Only `target` and its transitive dependencies are included, ordered
+    /// the same way [`Workflow::compute_execution_order`] would order them.
+    fn subgraph_for(&self, target: &str) -> Result<Vec<String>, String> {
+        if !self.tasks.contains_key(target) {
+            return Err(format!("Unknown task: {}", target));
+        }
+
+        let mut needed: HashSet<&str> = HashSet::new();
+        let mut stack = vec![target];
+        while let Some(id) = stack.pop() {
+            if !needed.insert(id) {
+                continue;
+            }
+            for dep in &self.tasks[id].dependencies {
+                if !self.tasks.contains_key(dep) {
+                    return Err(format!("Unknown dependency: {}", dep));
+                }
+                stack.push(dep);
+            }
+        }
+
+        self.topological_order(&needed)
+    }
+
+    /// Kahn's algorithm over the tasks in `ids`
+    ///
+    /// Dependencies outside `ids` are treated as already satisfied.
+    fn topological_order(&self, ids: &HashSet<&str>) -> Result<Vec<String>, String> {
         let mut in_degree: HashMap<String, usize> = HashMap::new();
         let mut dependents: HashMap<String, Vec<String>> = HashMap::new();
 
         // Initialize
-        for id in self.tasks.keys() {
-            in_degree.insert(id.clone(), 0);
-            dependents.insert(id.clone(), Vec::new());
+        for &id in ids {
+            in_degree.insert(id.to_string(), 0);
+            dependents.insert(id.to_string(), Vec::new());
         }
 
         // Count in-degrees and build dependents map
-        for (id, task) in &self.tasks {
-            for dep in &task.dependencies {
+        for &id in ids {
+            for dep in &self.tasks[id].dependencies {
                 if !self.tasks.contains_key(dep) {
                     return Err(format!("Unknown dependency: {}", dep));
                 }
+                if !ids.contains(dep.as_str()) {
+                    continue;
+                }
                 *in_degree.get_mut(id).expect("task exists in in_degree") += 1;
                 dependents
                     .get_mut(dep)
                     .expect("dependency exists in dependents")
-                    .push(id.clone());
+                    .push(id.to_string());
             }
         }
 
-        // Find initial tasks (no dependencies)
-        let initial: Vec<String> = {
-            let mut v: Vec<_> = in_degree
-                .iter()
-                .filter(|(_, &deg)| deg == 0)
-                .map(|(id, _)| id.clone())
-                .collect();
-            v.sort(); // Sort for determinism
-            v
-        };
+        // Max-heap on (priority, reversed id): highest priority, then lowest id
+        let ready_key = |id: &String| (self.tasks[id].priority, Reverse(id.clone()));
 
-        let mut queue: VecDeque<String> = initial.into_iter().collect();
+        // Find initial tasks (no dependencies)
+        let mut ready: BinaryHeap<(u32, Reverse<String>)> = in_degree
+            .iter()
+            .filter(|(_, &deg)| deg == 0)
+            .map(|(id, _)| ready_key(id))
+            .collect();
 
         let mut order = Vec::new();
 
-        while let Some(id) = queue.pop_front() {
+        while let Some((_, Reverse(id))) = ready.pop() {
             order.push(id.clone());
 
-            // Sort dependents for determinism
-            let mut deps = dependents.get(&id).cloned().unwrap_or_default();
-            deps.sort();
-
-            for dep_id in deps {
-                let deg = in_degree.get_mut(&dep_id).expect("dependent task exists");
+            for dep_id in dependents.get(&id).into_iter().flatten() {
+                let deg = in_degree.get_mut(dep_id).expect("dependent task exists");
                 *deg -= 1;
                 if *deg == 0 {
-                    queue.push_back(dep_id);
+                    ready.push(ready_key(dep_id));
                 }
             }
         }
 
-        if order.len() != self.tasks.len() {
-            return Err("Cycle detected in workflow".to_string());
+        if order.len() != ids.len() {
+            let cycle = self.trace_cycle(&in_degree);
+            return Err(format!("Cycle detected in workflow: {}", cycle.join(" → ")));
         }
 
-        self.execution_order = order;
-        Ok(())
+        Ok(order)
+    }
+
+    /// Recover one cycle from the tasks Kahn's algorithm could not schedule
+    ///
+    /// Every stuck task still waits on a stuck dependency, so walking those
+    /// back-edges from the lowest stuck id must revisit a task. The cycle is
+    /// returned in execution direction, starting and ending at its lowest id.
+    fn trace_cycle(&self, in_degree: &HashMap<String, usize>) -> Vec<String> {
+        let stuck = |id: &String| in_degree.get(id).is_some_and(|&deg| deg > 0);
+
+        let mut walk: Vec<&String> = Vec::new();
+        let mut current = in_degree.keys().filter(|id| stuck(id)).min();
+        while let Some(id) = current {
+            if let Some(pos) = walk.iter().position(|&seen| seen == id) {
+                let mut cycle: Vec<String> =
+                    walk[pos..].iter().rev().map(|id| id.to_string()).collect();
+                let lowest = cycle
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, id)| id.as_str())
+                    .map_or(0, |(i, _)| i);
+                cycle.rotate_left(lowest);
+                cycle.push(cycle[0].clone());
+                return cycle;
+            }
+            walk.push(id);
+            current = self.tasks[id]
+                .dependencies
+                .iter()
+                .filter(|dep| stuck(dep))
+                .min();
+        }
+        Vec::new()
+    }
+
+    /// Group tasks into waves that can run concurrently
+    ///
+    /// Wave `n` holds every task whose dependencies all finished in earlier
+    /// waves; each wave is sorted by id. Tasks on a cycle or with an unknown
+    /// dependency never become ready and are left out.
+    fn execution_levels(&self) -> Vec<Vec<String>> {
+        let mut remaining: HashMap<&str, usize> = self
+            .tasks
+            .iter()
+            .map(|(id, task)| (id.as_str(), task.dependencies.len()))
+            .collect();
+        let mut levels: Vec<Vec<String>> = Vec::new();
+
+        loop {
+            let mut wave: Vec<String> = remaining
+                .iter()
+                .filter(|(_, &deg)| deg == 0)
+                .map(|(id, _)| id.to_string())
+                .collect();
+            if wave.is_empty() {
+                break;
+            }
+            wave.sort();
+
+            for id in &wave {
+                remaining.remove(id.as_str());
+            }
+            for (id, deg) in remaining.iter_mut() {
+                let finished = self.tasks[*id]
+                    .dependencies
+                    .iter()
+                    .filter(|d| wave.contains(d))
+                    .count();
+                *deg -= finished;
+            }
+            levels.push(wave);
+        }
+
+        levels
+    }
+
+    /// Estimated wall-clock time to run the workflow on `num_workers` workers
+    ///
+    /// Waves run one after another. Within a wave, tasks are handed out
+    /// longest first to the least-loaded worker, and the wave lasts as long as
+    /// its busiest worker. With one worker this is the serial sum of all
+    /// durations; it never drops below the critical path.
+    fn makespan(&self, num_workers: usize) -> u64 {
+        let num_workers = num_workers.max(1);
+        let mut total = 0;
+
+        for wave in self.execution_levels() {
+            let mut durations: Vec<u64> = wave
+                .iter()
+                .map(|id| self.tasks[id].estimated_duration)
+                .collect();
+            durations.sort_unstable_by(|a, b| b.cmp(a));
+
+            let mut loads = vec![0u64; num_workers.min(durations.len())];
+            for duration in durations {
+                if let Some(least) = loads.iter_mut().min() {
+                    *least += duration;
+                }
+            }
+            total += loads.into_iter().max().unwrap_or(0);
+        }
+
+        total
+    }
+
+    /// Human-readable plan of how the workflow would run, without running it
+    ///
+    /// Lists each wave, labelling those whose tasks run in parallel, and marks
+    /// tasks on the critical path with `*`. Every task appears exactly once;
+    /// tasks that can never become ready are listed as unschedulable.
+    fn explain(&self) -> String {
+        let levels = self.execution_levels();
+        let (critical, total) = self.critical_path();
+
+        let mut plan = format!(
+            "Plan: {} tasks in {} waves\n",
+            self.tasks.len(),
+            levels.len()
+        );
+        for (i, wave) in levels.iter().enumerate() {
+            let label = if wave.len() > 1 { " (parallel)" } else { "" };
+            let names: Vec<String> = wave
+                .iter()
+                .map(|id| {
+                    if critical.contains(id) {
+                        format!("{}*", id)
+                    } else {
+                        id.clone()
+                    }
+                })
+                .collect();
+            plan.push_str(&format!(
+                "  Wave {}{}: {}\n",
+                i + 1,
+                label,
+                names.join(", ")
+            ));
+        }
+
+        let scheduled: usize = levels.iter().map(Vec::len).sum();
+        if scheduled < self.tasks.len() {
+            let mut stuck: Vec<&str> = self
+                .tasks
+                .keys()
+                .filter(|id| !levels.iter().any(|wave| wave.contains(id)))
+                .map(|id| id.as_str())
+                .collect();
+            stuck.sort_unstable();
+            plan.push_str(&format!("  Unschedulable: {}\n", stuck.join(", ")));
+        }
+
+        plan.push_str(&format!("Critical path (*): {} time units\n", total));
+        plan
+    }
+
+    /// Longest chain of dependent tasks by estimated duration, and its total
+    ///
+    /// That total is the minimum makespan: no amount of parallelism finishes
+    /// the workflow sooner. Ties go to the lexicographically smaller id.
+    fn critical_path(&self) -> (Vec<String>, u64) {
+        let mut finish: HashMap<&str, u64> = HashMap::new();
+        let mut via: HashMap<&str, &str> = HashMap::new();
+
+        for level in self.execution_levels() {
+            for id in level {
+                let task = &self.tasks[&id];
+                let mut deps: Vec<&str> = task.dependencies.iter().map(|d| d.as_str()).collect();
+                deps.sort_unstable();
+                let slowest = deps
+                    .into_iter()
+                    .fold(None, |best: Option<(&str, u64)>, dep| {
+                        let f = finish[dep];
+                        match best {
+                            Some((_, bf)) if bf >= f => best,
+                            _ => Some((dep, f)),
+                        }
+                    });
+                let start = slowest.map_or(0, |(_, f)| f);
+                let id = task.id.as_str();
+                finish.insert(id, start + task.estimated_duration);
+                if let Some((dep, _)) = slowest {
+                    via.insert(id, dep);
+                }
+            }
+        }
+
+        let mut ends: Vec<(&str, u64)> = finish.iter().map(|(&id, &f)| (id, f)).collect();
+        ends.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        let Some(&(end, total)) = ends.first() else {
+            return (Vec::new(), 0);
+        };
+
+        let mut path = vec![end.to_string()];
+        let mut current = end;
+        while let Some(&prev) = via.get(current) {
+            path.push(prev.to_string());
+            current = prev;
+        }
+        path.reverse();
+        (path, total)
     }
 
+    /// Run tasks in execution order, returning the ids that completed
+    ///
+    /// Actions are retried per the task's `RetryPolicy`. A task still failing
+    /// after its last attempt is marked `Failed`. Tasks whose condition is
+    /// false, and every task downstream of a failed or skipped task, are
+    /// marked `Skipped` without running.
     fn execute(&mut self) -> Vec<String> {
         let mut executed = Vec::new();
 
         for id in &self.execution_order {
+            let blocked = self.tasks.get(id).is_some_and(|task| {
+                task.dependencies.iter().any(|dep| {
+                    self.tasks.get(dep).is_some_and(|d| {
+                        matches!(d.status, TaskStatus::Failed | TaskStatus::Skipped)
+                    })
+                })
+            });
+
             if let Some(task) = self.tasks.get_mut(id) {
+                let gated_off = task
+                    .condition
+                    .as_ref()
+                    .is_some_and(|condition| !condition());
+                if blocked || gated_off {
+                    task.status = TaskStatus::Skipped;
+                    task.attempts = 0;
+                    continue;
+                }
                 task.status = TaskStatus::Running;
-                // Simulate task execution
-                task.status = TaskStatus::Completed;
-                executed.push(id.clone());
+                task.error = None;
+                match task.run() {
+                    Ok(()) => {
+                        task.status = TaskStatus::Completed;
+                        executed.push(id.clone());
+                    }
+                    Err(e) => {
+                        task.status = TaskStatus::Failed;
+                        task.error = Some(e);
+                    }
+                }
             }
         }
 
@@ -166,6 +645,20 @@ fn basic_demo() {
     let executed = workflow.execute();
     println!("   Executed: {:?}", executed);
     println!();
+
+    if let Err(e) = workflow.add_task_checked(Task::new("notify").depends_on("deplyo")) {
+        println!("   Add notify: {}", e);
+    }
+    if let Err(e) = workflow.remove_task("train_model") {
+        println!("   Remove train_model: {}", e);
+    }
+    if workflow.remove_task("deploy").is_ok() {
+        println!(
+            "   Remove deploy: ok ({} tasks left)",
+            workflow.task_count()
+        );
+    }
+    println!();
 }
 
 /// Demonstrate parallel tasks
@@ -176,13 +669,14 @@ fn parallel_demo() {
     let mut workflow = Workflow::new();
 
     // Diamond pattern
-    workflow.add_task(Task::new("start"));
-    workflow.add_task(Task::new("branch_a").depends_on("start"));
-    workflow.add_task(Task::new("branch_b").depends_on("start"));
+    workflow.add_task(Task::new("start").with_duration(5));
+    workflow.add_task(Task::new("branch_a").depends_on("start").with_duration(30));
+    workflow.add_task(Task::new("branch_b").depends_on("start").with_duration(12));
     workflow.add_task(
         Task::new("merge")
             .depends_on("branch_a")
-            .depends_on("branch_b"),
+            .depends_on("branch_b")
+            .with_duration(8),
     );
 
     workflow.compute_execution_order().expect("valid DAG");
@@ -196,6 +690,132 @@ fn parallel_demo() {
     println!();
     println!("   Execution order: {:?}", workflow.execution_order);
     println!();
+
+    workflow.add_task(
+        Task::new("branch_b")
+            .depends_on("start")
+            .with_duration(12)
+            .with_priority(10),
+    );
+    workflow.compute_execution_order().expect("valid DAG");
+    println!(
+        "   With branch_b at priority 10: {:?}",
+        workflow.execution_order
+    );
+    println!();
+
+    println!("   Dry-run plan:");
+    for line in workflow.explain().lines() {
+        println!("   {}", line);
+    }
+    println!();
+
+    println!("   Estimated makespan:");
+    for workers in [1, 2, 4] {
+        let label = if workers == 1 { " (serial)" } else { "" };
+        println!(
+            "   {} worker(s): {}s{}",
+            workers,
+            workflow.makespan(workers),
+            label
+        );
+    }
+    println!();
+
+    println!(
+        "   Needed for branch_b: {:?}",
+        workflow.subgraph_for("branch_b").expect("known task")
+    );
+    println!();
+}
+
+/// Demonstrate executing task actions with failure propagation
+fn execution_demo() {
+    println!("▶️  Task Execution");
+    println!();
+
+    // Fails on the first call, as if the network blipped
+    let calls = std::cell::Cell::new(0);
+    let flaky_fetch = move || {
+        calls.set(calls.get() + 1);
+        if calls.get() < 2 {
+            Err("connection reset".to_string())
+        } else {
+            Ok(())
+        }
+    };
+
+    let mut workflow = Workflow::new();
+    workflow.add_task(Task::new("fetch").with_action(flaky_fetch).with_retry(3));
+    workflow.add_task(
+        Task::new("validate")
+            .depends_on("fetch")
+            .with_action(|| Err("schema mismatch in column 'age'".to_string())),
+    );
+    workflow.add_task(Task::new("train").depends_on("validate"));
+    workflow.add_task(Task::new("report").depends_on("train"));
+    workflow.add_task(Task::new("audit_log").depends_on("fetch"));
+
+    workflow.compute_execution_order().expect("valid DAG");
+    let completed = workflow.execute();
+
+    for id in &workflow.execution_order {
+        let task = &workflow.tasks[id];
+        let icon = match task.status {
+            TaskStatus::Completed => "✅",
+            TaskStatus::Failed => "❌",
+            TaskStatus::Skipped => "⏭️ ",
+            TaskStatus::Pending | TaskStatus::Running => "…",
+        };
+        let attempts = format!("{} attempt(s)", task.attempts);
+        match &task.error {
+            Some(e) => println!(
+                "   {} {:<10} {:<10} {:<12} {}",
+                icon,
+                id,
+                format!("{:?}", task.status),
+                attempts,
+                e
+            ),
+            None => println!(
+                "   {} {:<10} {:<10} {}",
+                icon,
+                id,
+                format!("{:?}", task.status),
+                attempts
+            ),
+        }
+    }
+    println!();
+    println!("   Completed: {:?}", completed);
+    println!();
+}
+
+/// Demonstrate conditional branches
+fn conditional_demo() {
+    println!("🔀 Conditional Branches");
+    println!();
+
+    for accuracy in [0.94, 0.81] {
+        let mut workflow = Workflow::new();
+        workflow.add_task(Task::new("evaluate"));
+        workflow.add_task(
+            Task::new("deploy")
+                .depends_on("evaluate")
+                .when(move || accuracy >= 0.9),
+        );
+        workflow.add_task(Task::new("announce").depends_on("deploy"));
+        workflow.add_task(
+            Task::new("retrain")
+                .depends_on("evaluate")
+                .when(move || accuracy < 0.9),
+        );
+
+        workflow.compute_execution_order().expect("valid DAG");
+        let completed = workflow.execute();
+        println!("   accuracy={:.2}: ran {:?}", accuracy, completed);
+    }
+    println!();
 }
 
 /// Demonstrate determinism
@@ -231,6 +851,47 @@ fn determinism_demo() {
     println!();
 }
 
+/// Demonstrate saving a workflow definition and loading it back
+fn definition_file_demo() -> Result<()> {
+    println!("💾 Workflow Definition Files");
+    println!();
+
+    let mut workflow = Workflow::new();
+    workflow.add_task(Task::new("extract").with_duration(10));
+    workflow.add_task(
+        Task::new("transform")
+            .depends_on("extract")
+            .with_duration(25)
+            .with_retry(3),
+    );
+    workflow.add_task(Task::new("validate").depends_on("extract").with_priority(5));
+    workflow.add_task(
+        Task::new("load")
+            .depends_on("transform")
+            .depends_on("validate"),
+    );
+    workflow.compute_execution_order().expect("valid DAG");
+
+    let path = std::env::temp_dir().join("ch17-workflow.json");
+    workflow.save(&path)?;
+    let mut loaded = Workflow::load(&path)?;
+    loaded.compute_execution_order().expect("valid DAG");
+
+    println!("   Saved and reloaded {}", path.display());
+    println!("   Original order: {:?}", workflow.execution_order);
+    println!("   Reloaded order: {:?}", loaded.execution_order);
+    println!(
+        "   {} Reloaded workflow schedules identically",
+        if loaded.execution_order == workflow.execution_order {
+            "✅"
+        } else {
+            "❌"
+        }
+    );
+    println!();
+    Ok(())
+}
+
 /// Demonstrate cycle detection
 fn cycle_detection_demo() {
     println!("🔄 Cycle Detection");
@@ -245,7 +906,7 @@ fn cycle_detection_demo() {
 
     match workflow.compute_execution_order() {
         Ok(_) => println!("   ❌ Should have detected cycle!"),
-        Err(e) => println!("   ✅ Cycle detected: {}", e),
+        Err(e) => println!("   ✅ Rejected: {}", e),
     }
     println!();
 }
@@ -290,10 +951,22 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
+    execution_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
+    conditional_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
     determinism_demo();
     println!("{}", "─".repeat(70));
     println!();
 
+    definition_file_demo()?;
+    println!("{}", "─".repeat(70));
+    println!();
+
     cycle_detection_demo();
     println!("{}", "─".repeat(70));
     println!();
@@ -357,6 +1030,180 @@ mod tests {
         assert_eq!(workflow.execution_order[3], "end");
     }
 
+    #[test]
+    fn test_execution_levels_diamond() {
+        let mut workflow = Workflow::new();
+        workflow.add_task(Task::new("start"));
+        workflow.add_task(Task::new("branch_b").depends_on("start"));
+        workflow.add_task(Task::new("branch_a").depends_on("start"));
+        workflow.add_task(
+            Task::new("merge")
+                .depends_on("branch_a")
+                .depends_on("branch_b"),
+        );
+
+        assert_eq!(
+            workflow.execution_levels(),
+            vec![
+                vec!["start".to_string()],
+                vec!["branch_a".to_string(), "branch_b".to_string()],
+                vec!["merge".to_string()],
+            ]
+        );
+
+        let mut cyclic = Workflow::new();
+        cyclic.add_task(Task::new("root"));
+        cyclic.add_task(Task::new("x").depends_on("y"));
+        cyclic.add_task(Task::new("y").depends_on("x"));
+        assert_eq!(cyclic.execution_levels(), vec![vec!["root".to_string()]]);
+    }
+
+    #[test]
+    fn test_priority_breaks_ties() {
+        let mut workflow = Workflow::new();
+        workflow.add_task(Task::new("alpha"));
+        workflow.add_task(Task::new("beta"));
+        workflow.compute_execution_order().expect("valid DAG");
+        assert_eq!(workflow.execution_order, vec!["alpha", "beta"]);
+
+        workflow.add_task(Task::new("beta").with_priority(5));
+        workflow.compute_execution_order().expect("valid DAG");
+        assert_eq!(workflow.execution_order, vec!["beta", "alpha"]);
+
+        // Priority only reorders ready tasks; dependencies still win
+        workflow.add_task(Task::new("gamma").depends_on("alpha").with_priority(99));
+        workflow.compute_execution_order().expect("valid DAG");
+        assert_eq!(workflow.execution_order, vec!["beta", "alpha", "gamma"]);
+    }
+
+    #[test]
+    fn test_remove_task() {
+        let mut workflow = Workflow::new();
+        workflow.add_task(Task::new("a"));
+        workflow.add_task(Task::new("b").depends_on("a"));
+        workflow.add_task(Task::new("c").depends_on("a"));
+
+        let err = workflow.remove_task("a").expect_err("a has dependents");
+        assert_eq!(err, "Cannot remove a: required by b, c");
+        assert_eq!(workflow.task_count(), 3);
+
+        workflow.compute_execution_order().expect("valid DAG");
+        assert!(workflow.remove_task("c").is_ok());
+        assert_eq!(workflow.task_count(), 2);
+        assert_eq!(workflow.execution_order, vec!["a", "b"]);
+        workflow
+            .compute_execution_order()
+            .expect("still a valid DAG");
+
+        assert!(workflow.remove_task("c").is_err());
+    }
+
+    #[test]
+    fn test_critical_path_follows_slower_branch() {
+        let mut workflow = Workflow::new();
+        workflow.add_task(Task::new("start").with_duration(2));
+        workflow.add_task(Task::new("fast").depends_on("start").with_duration(3));
+        workflow.add_task(Task::new("slow").depends_on("start").with_duration(10));
+        workflow.add_task(
+            Task::new("merge")
+                .depends_on("fast")
+                .depends_on("slow")
+                .with_duration(1),
+        );
+
+        let (path, total) = workflow.critical_path();
+        assert_eq!(path, vec!["start", "slow", "merge"]);
+        assert_eq!(total, 13);
+
+        assert_eq!(Workflow::new().critical_path(), (Vec::new(), 0));
+    }
+
+    #[test]
+    fn test_subgraph_excludes_unrelated_branch() {
+        let mut workflow = Workflow::new();
+        workflow.add_task(Task::new("fetch"));
+        workflow.add_task(Task::new("clean").depends_on("fetch"));
+        workflow.add_task(Task::new("features").depends_on("clean"));
+        workflow.add_task(Task::new("train").depends_on("features"));
+        // Unrelated branch off the same root
+        workflow.add_task(Task::new("report").depends_on("fetch"));
+        workflow.add_task(Task::new("publish").depends_on("report"));
+
+        let needed = workflow.subgraph_for("train").expect("known task");
+        assert_eq!(needed, vec!["fetch", "clean", "features", "train"]);
+        assert_eq!(
+            workflow.subgraph_for("fetch").expect("known task"),
+            vec!["fetch"]
+        );
+        assert!(workflow.subgraph_for("missing").is_err());
+    }
+
+    #[test]
+    fn test_add_task_checked_rejects_unknown_dependency() {
+        let mut workflow = Workflow::new();
+        workflow
+            .add_task_checked(Task::new("a"))
+            .expect("no dependencies");
+        workflow
+            .add_task_checked(Task::new("b").depends_on("a"))
+            .expect("a exists");
+
+        let err = workflow
+            .add_task_checked(Task::new("c").depends_on("a").depends_on("typo"))
+            .expect_err("typo is not a task");
+        assert!(err.contains("typo"));
+        assert!(!workflow.tasks.contains_key("c"));
+        assert_eq!(workflow.task_count(), 2);
+    }
+
+    #[test]
+    fn test_explain_diamond() {
+        let mut workflow = Workflow::new();
+        workflow.add_task(Task::new("start").with_duration(1));
+        workflow.add_task(Task::new("left").depends_on("start").with_duration(5));
+        workflow.add_task(Task::new("right").depends_on("start").with_duration(2));
+        workflow.add_task(
+            Task::new("merge")
+                .depends_on("left")
+                .depends_on("right")
+                .with_duration(1),
+        );
+
+        let plan = workflow.explain();
+        for id in ["start", "left", "right", "merge"] {
+            assert_eq!(plan.matches(id).count(), 1, "{} in:\n{}", id, plan);
+        }
+        assert!(plan.contains("Wave 2 (parallel): left*, right"));
+        assert!(plan.contains("Wave 1: start*"));
+        assert!(plan.contains("7 time units"));
+        // Nothing ran
+        assert!(workflow
+            .tasks
+            .values()
+            .all(|t| t.status == TaskStatus::Pending));
+    }
+
+    #[test]
+    fn test_makespan_parallel_beats_serial() {
+        let mut workflow = Workflow::new();
+        workflow.add_task(Task::new("start").with_duration(2));
+        workflow.add_task(Task::new("left").depends_on("start").with_duration(6));
+        workflow.add_task(Task::new("right").depends_on("start").with_duration(4));
+        workflow.add_task(
+            Task::new("merge")
+                .depends_on("left")
+                .depends_on("right")
+                .with_duration(1),
+        );
+
+        assert_eq!(workflow.makespan(1), 13);
+        assert_eq!(workflow.makespan(2), 9);
+        assert!(workflow.makespan(2) < workflow.makespan(1));
+        // Extra workers cannot beat the critical path
+        assert_eq!(workflow.makespan(8), workflow.critical_path().1);
+        assert_eq!(workflow.makespan(0), workflow.makespan(1));
+    }
+
     #[test]
     fn test_cycle_detection() {
         let mut workflow = Workflow::new();
@@ -367,6 +1214,47 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_cycle_error_names_tasks() {
+        let mut workflow = Workflow::new();
+        workflow.add_task(Task::new("a").depends_on("c"));
+        workflow.add_task(Task::new("b").depends_on("a"));
+        workflow.add_task(Task::new("c").depends_on("b"));
+        // Downstream of the cycle, but not part of it
+        workflow.add_task(Task::new("d").depends_on("c"));
+
+        let err = workflow
+            .compute_execution_order()
+            .expect_err("cycle should be rejected");
+        assert_eq!(err, "Cycle detected in workflow: a → b → c → a");
+    }
+
+    #[test]
+    fn test_workflow_save_load_roundtrip() {
+        let mut workflow = Workflow::new();
+        workflow.add_task(Task::new("a").with_duration(4));
+        workflow.add_task(Task::new("b").depends_on("a").with_retry(2));
+        workflow.add_task(Task::new("c").depends_on("a").with_priority(7));
+        workflow.add_task(Task::new("d").depends_on("b").depends_on("c"));
+        workflow.compute_execution_order().expect("valid DAG");
+
+        let path = std::env::temp_dir().join(format!("ch17-workflow-{}.json", std::process::id()));
+        workflow.save(&path).expect("save");
+        let mut loaded = Workflow::load(&path).expect("load");
+        loaded.compute_execution_order().expect("valid DAG");
+
+        assert_eq!(loaded.execution_order, workflow.execution_order);
+        assert_eq!(loaded.tasks["a"].estimated_duration, 4);
+        assert_eq!(loaded.tasks["b"].retry.max_attempts, 2);
+        assert_eq!(loaded.tasks["d"].status, TaskStatus::Pending);
+
+        // Dangling dependencies are rejected on load
+        std::fs::write(&path, r#"{"tasks": [{"id": "b", "dependencies": ["a"]}]}"#).expect("write");
+        let err = Workflow::load(&path).expect_err("unknown dependency must be rejected");
+        assert!(err.to_string().contains("depends on unknown task a"));
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn test_execution() {
         let mut workflow = Workflow::new();
@@ -379,6 +1267,88 @@ mod tests {
         assert_eq!(executed, vec!["a", "b"]);
     }
 
+    #[test]
+    fn test_failed_task_skips_dependents() {
+        let mut workflow = Workflow::new();
+        workflow.add_task(Task::new("a").with_action(|| Ok(())));
+        workflow.add_task(
+            Task::new("b")
+                .depends_on("a")
+                .with_action(|| Err("boom".to_string())),
+        );
+        workflow.add_task(Task::new("c").depends_on("b"));
+        workflow.add_task(Task::new("d").depends_on("c"));
+        workflow.add_task(Task::new("e").depends_on("a"));
+
+        workflow.compute_execution_order().expect("valid DAG");
+        let executed = workflow.execute();
+
+        assert_eq!(executed, vec!["a", "e"]);
+        assert_eq!(workflow.tasks["b"].status, TaskStatus::Failed);
+        assert_eq!(workflow.tasks["b"].error.as_deref(), Some("boom"));
+        assert_eq!(workflow.tasks["c"].status, TaskStatus::Skipped);
+        assert_eq!(workflow.tasks["d"].status, TaskStatus::Skipped);
+        assert_eq!(workflow.tasks["e"].status, TaskStatus::Completed);
+    }
+
+    #[test]
+    fn test_retry_until_success() {
+        let calls = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = calls.clone();
+        let mut workflow = Workflow::new();
+        workflow.add_task(
+            Task::new("flaky")
+                .with_action(move || {
+                    counter.set(counter.get() + 1);
+                    if counter.get() <= 2 {
+                        Err(format!("failure {}", counter.get()))
+                    } else {
+                        Ok(())
+                    }
+                })
+                .with_retry(3),
+        );
+
+        workflow.compute_execution_order().expect("valid DAG");
+        assert_eq!(workflow.execute(), vec!["flaky"]);
+        assert_eq!(workflow.tasks["flaky"].status, TaskStatus::Completed);
+        assert_eq!(workflow.tasks["flaky"].attempts, 3);
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn test_retry_budget_exhausted() {
+        let mut workflow = Workflow::new();
+        workflow.add_task(
+            Task::new("broken")
+                .with_action(|| Err("always".to_string()))
+                .with_retry(2),
+        );
+
+        workflow.compute_execution_order().expect("valid DAG");
+        assert!(workflow.execute().is_empty());
+        assert_eq!(workflow.tasks["broken"].status, TaskStatus::Failed);
+        assert_eq!(workflow.tasks["broken"].attempts, 2);
+    }
+
+    #[test]
+    fn test_conditional_task_skips_its_branch() {
+        let mut workflow = Workflow::new();
+        workflow.add_task(Task::new("start"));
+        workflow.add_task(Task::new("gated").depends_on("start").when(|| false));
+        workflow.add_task(Task::new("after_gate").depends_on("gated"));
+        workflow.add_task(Task::new("open").depends_on("start").when(|| true));
+        workflow.add_task(Task::new("other").depends_on("start"));
+
+        workflow.compute_execution_order().expect("valid DAG");
+        let executed = workflow.execute();
+
+        assert_eq!(executed, vec!["start", "open", "other"]);
+        assert_eq!(workflow.tasks["gated"].status, TaskStatus::Skipped);
+        assert_eq!(workflow.tasks["after_gate"].status, TaskStatus::Skipped);
+        assert_eq!(workflow.tasks["other"].status, TaskStatus::Completed);
+    }
+
     #[test]
     fn test_determinism() {
         let mut results = Vec::new();
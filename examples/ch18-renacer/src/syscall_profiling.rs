@@ -7,7 +7,10 @@
 ///
 /// **VALIDATION:** `make run-ch18`
 use anyhow::Result;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 /// Profiling event
@@ -16,9 +19,15 @@ struct ProfileEvent {
     name: String,
     duration_ns: u64,
     category: EventCategory,
+    /// Span identity, assigned in start order (None for directly recorded events)
+    span_id: Option<usize>,
+    /// Span that was open when this one started
+    parent: Option<usize>,
+    /// Span start relative to the profiler's creation
+    start_ns: u64,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 enum EventCategory {
     Compute,
     IO,
@@ -32,39 +41,185 @@ impl ProfileEvent {
             name: name.to_string(),
             duration_ns,
             category,
+            span_id: None,
+            parent: None,
+            start_ns: 0,
         }
     }
 }
 
+/// Complete ("X") event in the Chrome Trace Event Format; times in microseconds
+#[derive(Debug, Serialize)]
+struct TraceEvent {
+    name: String,
+    cat: String,
+    ph: &'static str,
+    ts: f64,
+    dur: f64,
+    pid: u32,
+    tid: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChromeTrace {
+    trace_events: Vec<TraceEvent>,
+}
+
+/// A span that has been started but not yet ended
+#[derive(Debug)]
+struct ActiveSpan {
+    id: usize,
+    name: String,
+    start: Instant,
+    parent: Option<usize>,
+}
+
+/// Node of the span call tree
+#[derive(Debug, Clone)]
+struct TreeNode {
+    name: String,
+    category: EventCategory,
+    total_ns: u64,
+    /// Total time minus the total time of direct children
+    self_ns: u64,
+    children: Vec<TreeNode>,
+}
+
 /// Profiler for collecting metrics
 struct Profiler {
     events: Vec<ProfileEvent>,
-    active_spans: HashMap<String, Instant>,
+    /// Open spans, innermost last
+    active_spans: Vec<ActiveSpan>,
+    next_span_id: usize,
+    origin: Instant,
 }
 
 impl Profiler {
     fn new() -> Self {
         Self {
             events: Vec::new(),
-            active_spans: HashMap::new(),
+            active_spans: Vec::new(),
+            next_span_id: 0,
+            origin: Instant::now(),
         }
     }
 
+    /// Open a span; the innermost open span becomes its parent
     fn start_span(&mut self, name: &str) {
-        self.active_spans.insert(name.to_string(), Instant::now());
+        let parent = self.active_spans.last().map(|s| s.id);
+        self.active_spans.push(ActiveSpan {
+            id: self.next_span_id,
+            name: name.to_string(),
+            start: Instant::now(),
+            parent,
+        });
+        self.next_span_id += 1;
     }
 
+    /// Close the most recently opened span called `name`
     fn end_span(&mut self, name: &str, category: EventCategory) {
-        if let Some(start) = self.active_spans.remove(name) {
-            let duration = start.elapsed();
-            self.events.push(ProfileEvent::new(
-                name,
-                duration.as_nanos() as u64,
-                category,
-            ));
+        if let Some(pos) = self.active_spans.iter().rposition(|s| s.name == name) {
+            let span = self.active_spans.remove(pos);
+            let duration = span.start.elapsed();
+            self.events.push(ProfileEvent {
+                span_id: Some(span.id),
+                parent: span.parent,
+                start_ns: span.start.duration_since(self.origin).as_nanos() as u64,
+                ..ProfileEvent::new(name, duration.as_nanos() as u64, category)
+            });
         }
     }
 
+    /// Concatenate per-worker profilers in worker order, preserving each worker's
+    /// event order; span ids are offset so call trees stay per-worker
+    fn merge(profilers: &[Profiler]) -> Profiler {
+        let mut merged = Profiler::new();
+        for profiler in profilers {
+            let offset = merged.next_span_id;
+            for event in &profiler.events {
+                merged.events.push(ProfileEvent {
+                    span_id: event.span_id.map(|id| id + offset),
+                    parent: event.parent.map(|id| id + offset),
+                    ..event.clone()
+                });
+            }
+            merged.next_span_id += profiler.next_span_id;
+        }
+        merged
+    }
+
+    /// Open a span that is closed automatically when the guard is dropped
+    fn span_guard(&mut self, name: &str, category: EventCategory) -> SpanGuard<'_> {
+        self.start_span(name);
+        SpanGuard {
+            profiler: self,
+            name: name.to_string(),
+            category,
+        }
+    }
+
+    /// Trace Event Format JSON for chrome://tracing or Perfetto
+    ///
+    /// Spans keep their measured start time; directly recorded events have none,
+    /// so they are laid out back-to-back from zero in record order.
+    fn to_chrome_trace(&self) -> String {
+        let mut cursor_ns = 0;
+        let trace_events: Vec<_> = self
+            .events
+            .iter()
+            .map(|event| {
+                let start_ns = if event.span_id.is_some() {
+                    event.start_ns
+                } else {
+                    cursor_ns += event.duration_ns;
+                    cursor_ns - event.duration_ns
+                };
+                TraceEvent {
+                    name: event.name.clone(),
+                    cat: format!("{:?}", event.category),
+                    ph: "X",
+                    ts: start_ns as f64 / 1000.0,
+                    dur: event.duration_ns as f64 / 1000.0,
+                    pid: 1,
+                    tid: 1,
+                }
+            })
+            .collect();
+        serde_json::to_string(&ChromeTrace { trace_events }).expect("trace serializes")
+    }
+
+    /// Nest events by parent span; siblings appear in start order
+    fn call_tree(&self) -> Vec<TreeNode> {
+        fn build(event: &ProfileEvent, events: &[&ProfileEvent]) -> TreeNode {
+            let children: Vec<_> = events
+                .iter()
+                .filter(|e| e.parent.is_some() && e.parent == event.span_id)
+                .map(|e| build(e, events))
+                .collect();
+            let child_ns: u64 = children.iter().map(|c| c.total_ns).sum();
+            TreeNode {
+                name: event.name.clone(),
+                category: event.category,
+                total_ns: event.duration_ns,
+                self_ns: event.duration_ns.saturating_sub(child_ns),
+                children,
+            }
+        }
+
+        // Spans in start order, then directly recorded events in record order
+        let mut events: Vec<_> = self.events.iter().collect();
+        events.sort_by_key(|e| (e.span_id.is_none(), e.span_id));
+
+        // A span whose parent is still open (or unknown) is treated as a root
+        let span_ids: Vec<_> = events.iter().filter_map(|e| e.span_id).collect();
+        events
+            .iter()
+            .filter(|e| e.parent.is_none_or(|p| !span_ids.contains(&p)))
+            .map(|e| build(e, &events))
+            .collect()
+    }
+
     fn record(&mut self, event: ProfileEvent) {
         self.events.push(event);
     }
@@ -90,6 +245,127 @@ impl Profiler {
             .collect()
     }
 
+    /// Cumulative counts of events with `duration_ns <= bound` (Prometheus `le` buckets)
+    fn histogram(&self, buckets: &[u64]) -> Vec<(u64, usize)> {
+        let mut bounds = buckets.to_vec();
+        bounds.sort_unstable();
+        bounds.dedup();
+        bounds
+            .into_iter()
+            .map(|bound| {
+                let count = self
+                    .events
+                    .iter()
+                    .filter(|e| e.duration_ns <= bound)
+                    .count();
+                (bound, count)
+            })
+            .collect()
+    }
+
+    /// Events whose name matches `pattern`: a glob if it contains `*` or `?`,
+    /// otherwise a substring
+    fn events_matching(&self, pattern: &str) -> Vec<&ProfileEvent> {
+        let is_glob = pattern.contains(['*', '?']);
+        self.events
+            .iter()
+            .filter(|e| {
+                if is_glob {
+                    glob_match(pattern, &e.name)
+                } else {
+                    e.name.contains(pattern)
+                }
+            })
+            .collect()
+    }
+
+    /// Statistics over all events matching `pattern`, regardless of category
+    fn aggregate_matching(&self, pattern: &str) -> AggregateStats {
+        let durations: Vec<_> = self
+            .events_matching(pattern)
+            .iter()
+            .map(|e| e.duration_ns)
+            .collect();
+        AggregateStats::from_durations(&durations)
+    }
+
+    /// Statistics for one category; zeroed (never NaN) when it has no events
+    fn category_stats(&self, category: EventCategory) -> AggregateStats {
+        let durations: Vec<_> = self
+            .events
+            .iter()
+            .filter(|e| e.category == category)
+            .map(|e| e.duration_ns)
+            .collect();
+        AggregateStats::from_durations(&durations)
+    }
+
+    /// Mean duration per event name, sorted by name
+    fn mean_by_name(&self) -> BTreeMap<&str, f64> {
+        let mut durations: BTreeMap<&str, Vec<u64>> = BTreeMap::new();
+        for event in &self.events {
+            durations
+                .entry(&event.name)
+                .or_default()
+                .push(event.duration_ns);
+        }
+        durations
+            .into_iter()
+            .map(|(name, d)| (name, AggregateStats::from_durations(&d).mean_ns))
+            .collect()
+    }
+
+    /// Per-name mean change against `baseline` for names present in both runs,
+    /// flagging anything more than `threshold_pct` percent slower
+    fn compare(&self, baseline: &Profiler, threshold_pct: f64) -> Vec<Regression> {
+        let before = baseline.mean_by_name();
+        self.mean_by_name()
+            .into_iter()
+            .filter_map(|(name, current_mean_ns)| {
+                let baseline_mean_ns = *before.get(name)?;
+                let change_pct = if baseline_mean_ns > 0.0 {
+                    (current_mean_ns - baseline_mean_ns) / baseline_mean_ns * 100.0
+                } else {
+                    0.0
+                };
+                Some(Regression {
+                    name: name.to_string(),
+                    baseline_mean_ns,
+                    current_mean_ns,
+                    change_pct,
+                    regressed: change_pct > threshold_pct,
+                })
+            })
+            .collect()
+    }
+
+    /// Events per second, given the wall-clock window the events were captured in
+    fn throughput(&self, window_ns: u64) -> f64 {
+        if window_ns == 0 {
+            return 0.0;
+        }
+        self.events.len() as f64 / (window_ns as f64 / 1e9)
+    }
+
+    /// Events per second for each category over the same window
+    fn throughput_by_category(&self, window_ns: u64) -> HashMap<EventCategory, f64> {
+        let mut counts: HashMap<EventCategory, usize> = HashMap::new();
+        for event in &self.events {
+            *counts.entry(event.category).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .map(|(category, count)| {
+                let rate = if window_ns == 0 {
+                    0.0
+                } else {
+                    count as f64 / (window_ns as f64 / 1e9)
+                };
+                (category, rate)
+            })
+            .collect()
+    }
+
     /// Get top N slowest events
     fn top_slowest(&self, n: usize) -> Vec<&ProfileEvent> {
         let mut events: Vec<_> = self.events.iter().collect();
@@ -99,6 +375,118 @@ impl Profiler {
     }
 }
 
+/// Mean-duration change of one operation between a baseline and a current run
+#[derive(Debug, Clone, PartialEq)]
+struct Regression {
+    name: String,
+    baseline_mean_ns: f64,
+    current_mean_ns: f64,
+    change_pct: f64,
+    /// Slower than the baseline by more than the threshold
+    regressed: bool,
+}
+
+/// Glob match where `*` matches any run of characters and `?` exactly one
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let (p, t): (Vec<char>, Vec<char>) = (pattern.chars().collect(), text.chars().collect());
+    let (mut pi, mut ti) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            backtrack = Some((pi, ti));
+            pi += 1;
+        } else if let Some((star, matched)) = backtrack {
+            pi = star + 1;
+            ti = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+/// Profiler handle that can be cloned into worker threads
+#[derive(Clone, Default)]
+struct SharedProfiler {
+    events: Arc<Mutex<Vec<ProfileEvent>>>,
+}
+
+impl SharedProfiler {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(&self, event: ProfileEvent) {
+        self.events
+            .lock()
+            .expect("profiler lock not poisoned")
+            .push(event);
+    }
+
+    /// Run `f` and record its wall-clock duration
+    fn time<T>(&self, name: &str, category: EventCategory, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.record(ProfileEvent::new(
+            name,
+            start.elapsed().as_nanos() as u64,
+            category,
+        ));
+        result
+    }
+
+    /// Collect into a single-threaded profiler, with events in a canonical order
+    /// so that results never depend on thread interleaving
+    fn snapshot(&self) -> Profiler {
+        let mut events = self
+            .events
+            .lock()
+            .expect("profiler lock not poisoned")
+            .clone();
+        events.sort_by(|a, b| {
+            (&a.name, a.category, a.duration_ns).cmp(&(&b.name, b.category, b.duration_ns))
+        });
+
+        let mut profiler = Profiler::new();
+        for event in events {
+            profiler.record(event);
+        }
+        profiler
+    }
+}
+
+/// Ends its span on drop; derefs to the profiler so spans can nest
+struct SpanGuard<'a> {
+    profiler: &'a mut Profiler,
+    name: String,
+    category: EventCategory,
+}
+
+impl Deref for SpanGuard<'_> {
+    type Target = Profiler;
+
+    fn deref(&self) -> &Profiler {
+        self.profiler
+    }
+}
+
+impl DerefMut for SpanGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Profiler {
+        self.profiler
+    }
+}
+
+impl Drop for SpanGuard<'_> {
+    fn drop(&mut self) {
+        self.profiler.end_span(&self.name, self.category);
+    }
+}
+
 /// Aggregate statistics
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -108,22 +496,62 @@ struct AggregateStats {
     mean_ns: f64,
     min_ns: u64,
     max_ns: u64,
+    p50_ns: u64,
+    p90_ns: u64,
+    p95_ns: u64,
+    p99_ns: u64,
+    /// Population variance
+    variance_ns: f64,
+    std_ns: f64,
+}
+
+/// Nearest-rank percentile of an ascending slice (0 when empty)
+fn percentile(sorted: &[u64], p: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
 }
 
 impl AggregateStats {
     fn from_durations(durations: &[u64]) -> Self {
         let count = durations.len();
         let total_ns: u64 = durations.iter().sum();
-        let mean_ns = total_ns as f64 / count as f64;
+        let mean_ns = if count == 0 {
+            0.0
+        } else {
+            total_ns as f64 / count as f64
+        };
         let min_ns = *durations.iter().min().unwrap_or(&0);
         let max_ns = *durations.iter().max().unwrap_or(&0);
 
+        let mut sorted = durations.to_vec();
+        sorted.sort_unstable();
+
+        // Welford's online algorithm: stable even for large nanosecond values.
+        // Iterating the sorted copy makes the result independent of input order.
+        let (mut n, mut mean, mut m2) = (0.0, 0.0, 0.0);
+        for &d in &sorted {
+            n += 1.0;
+            let delta = d as f64 - mean;
+            mean += delta / n;
+            m2 += delta * (d as f64 - mean);
+        }
+        let variance_ns = if n > 0.0 { m2 / n } else { 0.0 };
+
         Self {
             count,
             total_ns,
             mean_ns,
             min_ns,
             max_ns,
+            p50_ns: percentile(&sorted, 50.0),
+            p90_ns: percentile(&sorted, 90.0),
+            p95_ns: percentile(&sorted, 95.0),
+            p99_ns: percentile(&sorted, 99.0),
+            variance_ns,
+            std_ns: variance_ns.sqrt(),
         }
     }
 }
@@ -155,21 +583,48 @@ fn basic_demo() {
 
     let stats = profiler.aggregate_by_category();
     println!(
-        "   {:>10} │ {:>6} │ {:>12} │ {:>12}",
-        "Category", "Count", "Total (ns)", "Mean (ns)"
+        "   {:>10} │ {:>6} │ {:>12} │ {:>12} │ {:>12}",
+        "Category", "Count", "Total (ns)", "Mean (ns)", "p99 (ns)"
     );
-    println!("   ───────────┼────────┼──────────────┼─────────────");
+    println!("   ───────────┼────────┼──────────────┼──────────────┼─────────────");
 
     let mut sorted: Vec<_> = stats.iter().collect();
     sorted.sort_by_key(|(cat, _)| format!("{:?}", cat));
 
     for (cat, stat) in sorted {
         println!(
-            "   {:>10?} │ {:>6} │ {:>12} │ {:>12.0}",
-            cat, stat.count, stat.total_ns, stat.mean_ns
+            "   {:>10?} │ {:>6} │ {:>12} │ {:>12.0} │ {:>12}",
+            cat, stat.count, stat.total_ns, stat.mean_ns, stat.p99_ns
         );
     }
     println!();
+
+    let file_ops = profiler.aggregate_matching("file_*");
+    println!(
+        "   file_* operations: {} events, {} ns total",
+        file_ops.count, file_ops.total_ns
+    );
+    println!();
+
+    println!("   Latency histogram (cumulative):");
+    for (bound, count) in profiler.histogram(&[100_000, 1_000_000, 10_000_000]) {
+        println!("   ≤ {:>10} ns │ {}", bound, count);
+    }
+    println!();
+}
+
+fn print_tree_node(node: &TreeNode, depth: usize) {
+    println!(
+        "   {}{} ({:?}): {} ns / {} ns",
+        "  ".repeat(depth),
+        node.name,
+        node.category,
+        node.total_ns,
+        node.self_ns
+    );
+    for child in &node.children {
+        print_tree_node(child, depth + 1);
+    }
 }
 
 /// Demonstrate span-based profiling
@@ -197,6 +652,93 @@ fn span_demo() {
         );
     }
     println!();
+
+    // Nested spans via RAII guards: db_query inside handle_request
+    let mut profiler = Profiler::new();
+    {
+        let mut request = profiler.span_guard("handle_request", EventCategory::Compute);
+        std::thread::sleep(Duration::from_micros(100));
+        let _query = request.span_guard("db_query", EventCategory::IO);
+        std::thread::sleep(Duration::from_micros(200));
+    }
+
+    println!("   Call tree (total / self):");
+    for root in profiler.call_tree() {
+        print_tree_node(&root, 1);
+    }
+    println!();
+    println!(
+        "   Chrome trace: {} bytes (load in chrome://tracing or Perfetto)",
+        profiler.to_chrome_trace().len()
+    );
+    println!();
+}
+
+/// Demonstrate concurrent profiling
+fn concurrent_demo() {
+    println!("🧵 Concurrent Profiling");
+    println!();
+
+    let shared = SharedProfiler::new();
+    let start = Instant::now();
+    std::thread::scope(|scope| {
+        for worker in 0..4 {
+            let profiler = shared.clone();
+            scope.spawn(move || {
+                for i in 0..25u64 {
+                    profiler.time(
+                        &format!("worker{}_task", worker),
+                        EventCategory::Compute,
+                        || (0..1_000 * (i + 1)).sum::<u64>(),
+                    );
+                }
+            });
+        }
+    });
+
+    let window_ns = start.elapsed().as_nanos() as u64;
+
+    let profiler = shared.snapshot();
+    println!("   Workers: 4");
+    println!("   Events recorded: {}", profiler.event_count());
+    println!(
+        "   Throughput: {:.0} events/s",
+        profiler.throughput(window_ns)
+    );
+    if let Some(rate) = profiler
+        .throughput_by_category(window_ns)
+        .get(&EventCategory::Compute)
+    {
+        println!("   Compute throughput: {:.0} events/s", rate);
+    }
+    if let Some(stats) = profiler
+        .aggregate_by_category()
+        .get(&EventCategory::Compute)
+    {
+        println!("   Compute p50: {} ns", stats.p50_ns);
+    }
+    println!();
+
+    // Alternatively, one profiler per worker merged in worker order
+    let workers: Vec<Profiler> = (0..4u64)
+        .map(|worker| {
+            let mut profiler = Profiler::new();
+            profiler.record(ProfileEvent::new(
+                "load",
+                100 * (worker + 1),
+                EventCategory::IO,
+            ));
+            profiler.record(ProfileEvent::new("train", 1_000, EventCategory::Compute));
+            profiler
+        })
+        .collect();
+    let merged = Profiler::merge(&workers);
+    println!(
+        "   Merged {} per-worker profilers: {} events",
+        workers.len(),
+        merged.event_count()
+    );
+    println!();
 }
 
 /// Demonstrate top-N analysis
@@ -239,6 +781,33 @@ fn top_n_demo() {
     println!();
 }
 
+/// Demonstrate a CI-style performance gate
+fn regression_demo() {
+    println!("🚦 Regression Detection (threshold +20%)");
+    println!();
+
+    let mut baseline = Profiler::new();
+    let mut current = Profiler::new();
+    for (name, before, after) in [
+        ("parse", 1_000, 1_050),
+        ("matmul", 20_000, 31_000),
+        ("write", 5_000, 4_000),
+    ] {
+        baseline.record(ProfileEvent::new(name, before, EventCategory::Compute));
+        current.record(ProfileEvent::new(name, after, EventCategory::Compute));
+    }
+
+    for r in current.compare(&baseline, 20.0) {
+        println!(
+            "   {} {:>8}: {:>+7.1}%",
+            if r.regressed { "❌" } else { "✅" },
+            r.name,
+            r.change_pct
+        );
+    }
+    println!();
+}
+
 /// Demonstrate determinism
 fn determinism_demo() {
     println!("🔁 Aggregation Determinism");
@@ -258,11 +827,7 @@ fn determinism_demo() {
             profiler.record(event.clone());
         }
 
-        let stats = profiler.aggregate_by_category();
-        let compute_mean = stats
-            .get(&EventCategory::Compute)
-            .map(|s| s.mean_ns)
-            .unwrap_or(0.0);
+        let compute_mean = profiler.category_stats(EventCategory::Compute).mean_ns;
 
         println!("   Run {}: Compute mean = {:.2} ns", run, compute_mean);
         results.push(compute_mean);
@@ -320,10 +885,18 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
+    concurrent_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
     top_n_demo();
     println!("{}", "─".repeat(70));
     println!();
 
+    regression_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
     determinism_demo();
     println!("{}", "─".repeat(70));
     println!();
@@ -373,6 +946,46 @@ mod tests {
         assert_eq!(stats.max_ns, 300);
     }
 
+    #[test]
+    fn test_aggregate_stats_percentiles() {
+        let durations: Vec<u64> = (0..=100).rev().collect();
+        let stats = AggregateStats::from_durations(&durations);
+
+        assert_eq!(stats.p50_ns, 50);
+        assert_eq!(stats.p90_ns, 90);
+        assert_eq!(stats.p95_ns, 95);
+        assert_eq!(stats.p99_ns, 99);
+    }
+
+    #[test]
+    fn test_percentile_single_sample() {
+        let stats = AggregateStats::from_durations(&[42]);
+        assert_eq!(stats.p50_ns, 42);
+        assert_eq!(stats.p99_ns, 42);
+    }
+
+    #[test]
+    fn test_std_of_equal_durations_is_zero() {
+        let stats = AggregateStats::from_durations(&[500, 500, 500, 500]);
+        assert_eq!(stats.variance_ns, 0.0);
+        assert_eq!(stats.std_ns, 0.0);
+    }
+
+    #[test]
+    fn test_population_std() {
+        // Mean 5, squared deviations sum to 32 over 8 samples
+        let stats = AggregateStats::from_durations(&[2, 4, 4, 4, 5, 5, 7, 9]);
+        assert!((stats.variance_ns - 4.0).abs() < 1e-10);
+        assert!((stats.std_ns - 2.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_std_stable_for_large_durations() {
+        let base = 1_000_000_000_000;
+        let stats = AggregateStats::from_durations(&[base, base + 1, base + 2]);
+        assert!((stats.variance_ns - 2.0 / 3.0).abs() < 1e-6);
+    }
+
     #[test]
     fn test_aggregate_by_category() {
         let mut profiler = Profiler::new();
@@ -392,6 +1005,59 @@ mod tests {
         assert_eq!(io.count, 1);
     }
 
+    #[test]
+    fn test_histogram_cumulative_buckets() {
+        let mut profiler = Profiler::new();
+        for d in [50, 100, 500, 999, 5_000, 20_000] {
+            profiler.record(ProfileEvent::new("op", d, EventCategory::Compute));
+        }
+
+        let histogram = profiler.histogram(&[100, 1000, 10000]);
+        assert_eq!(histogram, vec![(100, 2), (1000, 4), (10000, 5)]);
+        assert!(histogram.windows(2).all(|w| w[0].1 <= w[1].1));
+    }
+
+    #[test]
+    fn test_histogram_sorts_unordered_buckets() {
+        let mut profiler = Profiler::new();
+        profiler.record(ProfileEvent::new("op", 500, EventCategory::IO));
+        assert_eq!(profiler.histogram(&[1000, 100]), vec![(100, 0), (1000, 1)]);
+    }
+
+    #[test]
+    fn test_events_matching_substring() {
+        let mut profiler = Profiler::new();
+        profiler.record(ProfileEvent::new("db_read", 100, EventCategory::IO));
+        profiler.record(ProfileEvent::new("db_write", 300, EventCategory::IO));
+        profiler.record(ProfileEvent::new("compute", 50, EventCategory::Compute));
+
+        let names: Vec<_> = profiler
+            .events_matching("db_")
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["db_read", "db_write"]);
+
+        let stats = profiler.aggregate_matching("db_");
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.total_ns, 400);
+    }
+
+    #[test]
+    fn test_events_matching_glob() {
+        let mut profiler = Profiler::new();
+        for name in ["db_read", "db_write", "cache_read", "compute"] {
+            profiler.record(ProfileEvent::new(name, 1, EventCategory::Compute));
+        }
+
+        assert_eq!(profiler.events_matching("*_read").len(), 2);
+        assert_eq!(profiler.events_matching("db_*").len(), 2);
+        assert_eq!(profiler.events_matching("db_rea?").len(), 1);
+        assert_eq!(profiler.events_matching("*").len(), 4);
+        assert_eq!(profiler.events_matching("db").len(), 2);
+        assert!(profiler.events_matching("x*").is_empty());
+    }
+
     #[test]
     fn test_top_slowest() {
         let mut profiler = Profiler::new();
@@ -405,6 +1071,199 @@ mod tests {
         assert_eq!(top[1].name, "medium");
     }
 
+    #[test]
+    fn test_nested_spans_call_tree() {
+        let mut profiler = Profiler::new();
+        profiler.start_span("A");
+        std::thread::sleep(Duration::from_micros(50));
+        profiler.start_span("B");
+        std::thread::sleep(Duration::from_micros(50));
+        profiler.end_span("B", EventCategory::IO);
+        profiler.end_span("A", EventCategory::Compute);
+        profiler.record(ProfileEvent::new("C", 10, EventCategory::Memory));
+
+        let tree = profiler.call_tree();
+        assert_eq!(tree.len(), 2);
+        let a = &tree[0];
+        assert_eq!(a.name, "A");
+        assert_eq!(a.children.len(), 1);
+        let b = &a.children[0];
+        assert_eq!(b.name, "B");
+        assert!(a.total_ns >= b.total_ns);
+        assert_eq!(a.self_ns, a.total_ns - b.total_ns);
+        assert_eq!(b.self_ns, b.total_ns);
+        assert_eq!(tree[1].name, "C");
+    }
+
+    #[test]
+    fn test_span_guard_records_on_drop() {
+        let mut profiler = Profiler::new();
+        {
+            let _guard = profiler.span_guard("guarded", EventCategory::Compute);
+            std::thread::sleep(Duration::from_micros(10));
+        }
+
+        assert_eq!(profiler.event_count(), 1);
+        assert_eq!(profiler.events[0].name, "guarded");
+        assert_eq!(profiler.events[0].category, EventCategory::Compute);
+        assert!(profiler.events[0].duration_ns > 0);
+    }
+
+    #[test]
+    fn test_nested_span_guards() {
+        let mut profiler = Profiler::new();
+        {
+            let mut outer = profiler.span_guard("outer", EventCategory::Compute);
+            let _inner = outer.span_guard("inner", EventCategory::IO);
+        }
+
+        let tree = profiler.call_tree();
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].name, "outer");
+        assert_eq!(tree[0].children[0].name, "inner");
+    }
+
+    #[test]
+    fn test_chrome_trace_export() {
+        let mut profiler = Profiler::new();
+        {
+            let mut outer = profiler.span_guard("request", EventCategory::Compute);
+            let _inner = outer.span_guard("db_read", EventCategory::IO);
+        }
+        profiler.record(ProfileEvent::new("malloc", 2_000, EventCategory::Memory));
+        profiler.record(ProfileEvent::new("send", 3_000, EventCategory::Network));
+
+        let json = profiler.to_chrome_trace();
+        let trace: serde_json::Value = serde_json::from_str(&json).expect("valid JSON");
+        let events = trace["traceEvents"].as_array().expect("traceEvents array");
+        assert_eq!(events.len(), profiler.event_count());
+        assert!(events.iter().all(|e| e["ph"] == "X"));
+
+        let send = events
+            .iter()
+            .find(|e| e["name"] == "send")
+            .expect("send event");
+        assert_eq!(send["cat"], "Network");
+        assert_eq!(send["ts"], 2.0);
+        assert_eq!(send["dur"], 3.0);
+    }
+
+    #[test]
+    fn test_sibling_spans_share_parent() {
+        let mut profiler = Profiler::new();
+        profiler.start_span("root");
+        profiler.start_span("first");
+        profiler.end_span("first", EventCategory::Compute);
+        profiler.start_span("second");
+        profiler.end_span("second", EventCategory::Compute);
+        profiler.end_span("root", EventCategory::Compute);
+
+        let tree = profiler.call_tree();
+        assert_eq!(tree.len(), 1);
+        let names: Vec<_> = tree[0].children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["first", "second"]);
+    }
+
+    #[test]
+    fn test_shared_profiler_across_threads() {
+        let shared = SharedProfiler::new();
+        let handles: Vec<_> = (0..4u64)
+            .map(|worker| {
+                let profiler = shared.clone();
+                std::thread::spawn(move || {
+                    for i in 0..50 {
+                        profiler.record(ProfileEvent::new(
+                            "compute",
+                            10 + worker,
+                            EventCategory::Compute,
+                        ));
+                        if i % 2 == 0 {
+                            profiler.record(ProfileEvent::new("read", 100, EventCategory::IO));
+                        }
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("worker thread");
+        }
+
+        let profiler = shared.snapshot();
+        assert_eq!(profiler.event_count(), 4 * 50 + 4 * 25);
+
+        let stats = profiler.aggregate_by_category();
+        let compute = &stats[&EventCategory::Compute];
+        assert_eq!(compute.count, 200);
+        assert_eq!(compute.total_ns, 50 * (10 + 11 + 12 + 13));
+        let io = &stats[&EventCategory::IO];
+        assert_eq!(io.count, 100);
+        assert_eq!(io.total_ns, 100 * 100);
+    }
+
+    #[test]
+    fn test_merge_profilers() {
+        let mut first = Profiler::new();
+        first.record(ProfileEvent::new("a", 100, EventCategory::Compute));
+        first.record(ProfileEvent::new("b", 300, EventCategory::IO));
+        {
+            let _span = first.span_guard("span", EventCategory::Compute);
+        }
+        let mut second = Profiler::new();
+        second.record(ProfileEvent::new("c", 200, EventCategory::Compute));
+        {
+            let mut outer = second.span_guard("outer", EventCategory::Memory);
+            let _inner = outer.span_guard("inner", EventCategory::Memory);
+        }
+
+        let merged = Profiler::merge(&[first, second]);
+        assert_eq!(merged.event_count(), 3 + 3);
+        let names: Vec<_> = merged.events.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "span", "c", "inner", "outer"]);
+
+        let mut union = Profiler::new();
+        for event in &merged.events {
+            union.record(ProfileEvent::new(
+                &event.name,
+                event.duration_ns,
+                event.category,
+            ));
+        }
+        let (merged_stats, union_stats) = (
+            merged.aggregate_by_category(),
+            union.aggregate_by_category(),
+        );
+        assert_eq!(merged_stats.len(), union_stats.len());
+        for (category, stats) in &union_stats {
+            assert_eq!(merged_stats[category].count, stats.count);
+            assert_eq!(merged_stats[category].total_ns, stats.total_ns);
+        }
+
+        // Span ids from the second profiler no longer collide with the first
+        let tree = merged.call_tree();
+        let outer = tree.iter().find(|n| n.name == "outer").expect("outer span");
+        assert_eq!(outer.children.len(), 1);
+        assert!(tree
+            .iter()
+            .find(|n| n.name == "span")
+            .expect("span")
+            .children
+            .is_empty());
+    }
+
+    #[test]
+    fn test_aggregation_independent_of_record_order() {
+        let durations = [5, 1_000_000_007, 13, 42, 999, 3];
+        let forward = AggregateStats::from_durations(&durations);
+        let reversed: Vec<_> = durations.iter().rev().copied().collect();
+        let backward = AggregateStats::from_durations(&reversed);
+
+        assert_eq!(forward.mean_ns.to_bits(), backward.mean_ns.to_bits());
+        assert_eq!(
+            forward.variance_ns.to_bits(),
+            backward.variance_ns.to_bits()
+        );
+    }
+
     #[test]
     fn test_aggregation_determinism() {
         let events = vec![
@@ -433,4 +1292,78 @@ mod tests {
             "Aggregation must be deterministic"
         );
     }
+
+    #[test]
+    fn test_throughput_over_window() {
+        let mut profiler = Profiler::new();
+        for i in 0..500 {
+            let category = if i % 5 == 0 {
+                EventCategory::IO
+            } else {
+                EventCategory::Compute
+            };
+            profiler.record(ProfileEvent::new("op", 1_000, category));
+        }
+
+        // 500 events in 250 ms = 2000 events/s
+        let window_ns = 250_000_000;
+        assert!((profiler.throughput(window_ns) - 2_000.0).abs() < 1e-9);
+
+        let by_category = profiler.throughput_by_category(window_ns);
+        assert!((by_category[&EventCategory::Compute] - 1_600.0).abs() < 1e-9);
+        assert!((by_category[&EventCategory::IO] - 400.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_throughput_zero_window() {
+        let mut profiler = Profiler::new();
+        profiler.record(ProfileEvent::new("op", 1, EventCategory::Compute));
+        assert_eq!(profiler.throughput(0), 0.0);
+    }
+
+    #[test]
+    fn test_empty_stats_have_no_nan() {
+        let stats = AggregateStats::from_durations(&[]);
+        assert_eq!(stats.count, 0);
+        assert_eq!(stats.total_ns, 0);
+        assert_eq!(stats.mean_ns, 0.0);
+        assert_eq!(stats.std_ns, 0.0);
+        assert_eq!((stats.min_ns, stats.max_ns, stats.p99_ns), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_empty_profiler() {
+        let profiler = Profiler::new();
+        assert!(profiler.aggregate_by_category().is_empty());
+        assert!(profiler.top_slowest(5).is_empty());
+        assert!(profiler.call_tree().is_empty());
+
+        let compute = profiler.category_stats(EventCategory::Compute);
+        assert_eq!(compute.count, 0);
+        assert_eq!(compute.mean_ns, 0.0);
+    }
+
+    #[test]
+    fn test_compare_flags_regression() {
+        let mut baseline = Profiler::new();
+        let mut current = Profiler::new();
+        for _ in 0..3 {
+            baseline.record(ProfileEvent::new("query", 1_000, EventCategory::IO));
+            baseline.record(ProfileEvent::new("render", 400, EventCategory::Compute));
+            current.record(ProfileEvent::new("query", 1_500, EventCategory::IO));
+            current.record(ProfileEvent::new("render", 400, EventCategory::Compute));
+        }
+        current.record(ProfileEvent::new("new_op", 10, EventCategory::Compute));
+
+        let report = current.compare(&baseline, 20.0);
+        assert_eq!(report.len(), 2); // new_op has no baseline
+
+        let query = report.iter().find(|r| r.name == "query").expect("query");
+        assert!((query.change_pct - 50.0).abs() < 1e-10);
+        assert!(query.regressed);
+
+        let render = report.iter().find(|r| r.name == "render").expect("render");
+        assert_eq!(render.change_pct, 0.0);
+        assert!(!render.regressed);
+    }
 }
@@ -6,31 +6,64 @@
 /// - Deterministic scheduling
 ///
 /// **VALIDATION:** `make run-ch19`
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
 use std::collections::VecDeque;
+use std::fs;
+use std::sync::{Barrier, Mutex};
+use std::thread;
 
 /// Work unit with priority
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 struct WorkUnit {
     id: usize,
     priority: u32,
     cost: u64,
+    /// Owning worker's clock when the unit was queued there
+    enqueued_at: u64,
+    /// Priority after aging, set when the unit is dequeued
+    effective_priority: u32,
 }
 
 impl WorkUnit {
     fn new(id: usize, priority: u32, cost: u64) -> Self {
-        Self { id, priority, cost }
+        Self {
+            id,
+            priority,
+            cost,
+            enqueued_at: 0,
+            effective_priority: priority,
+        }
     }
 }
 
+/// Order in which a worker takes units from its own queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+enum QueueOrder {
+    /// First in, first out
+    #[default]
+    Fifo,
+    /// Highest priority first; equal priorities go to the lowest id
+    Priority,
+    /// Like `Priority`, but a unit gains one priority level for every
+    /// `every` units its worker dequeues while it waits
+    Aging { every: u64 },
+}
+
 /// Worker with local queue
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(dead_code)]
 struct Worker {
     id: usize,
     queue: VecDeque<WorkUnit>,
     processed: Vec<usize>,
+    /// Total cost of the processed units
+    processed_cost: u64,
+    order: QueueOrder,
+    /// Number of units dequeued so far; the time base for aging
+    clock: u64,
 }
 
 impl Worker {
@@ -39,43 +72,111 @@ impl Worker {
             id,
             queue: VecDeque::new(),
             processed: Vec::new(),
+            processed_cost: 0,
+            order: QueueOrder::Fifo,
+            clock: 0,
         }
     }
 
-    fn push(&mut self, work: WorkUnit) {
+    fn push(&mut self, mut work: WorkUnit) {
+        work.enqueued_at = self.clock;
         self.queue.push_back(work);
     }
 
+    /// Priority of a queued unit after aging
+    fn effective_priority(&self, work: &WorkUnit) -> u32 {
+        match self.order {
+            QueueOrder::Aging { every } => {
+                let waited = self.clock.saturating_sub(work.enqueued_at);
+                let boost = u32::try_from(waited / every.max(1)).unwrap_or(u32::MAX);
+                work.priority.saturating_add(boost)
+            }
+            _ => work.priority,
+        }
+    }
+
+    /// Sort key for priority orders: higher runs sooner
+    fn rank(&self, i: usize) -> (u32, Reverse<usize>) {
+        (
+            self.effective_priority(&self.queue[i]),
+            Reverse(self.queue[i].id),
+        )
+    }
+
     fn pop(&mut self) -> Option<WorkUnit> {
-        self.queue.pop_front()
+        let mut work = match self.order {
+            QueueOrder::Fifo => self.queue.pop_front(),
+            QueueOrder::Priority | QueueOrder::Aging { .. } => {
+                let idx = (0..self.queue.len()).max_by_key(|&i| self.rank(i))?;
+                self.queue.remove(idx)
+            }
+        }?;
+        work.effective_priority = self.effective_priority(&work);
+        self.clock += 1;
+        Some(work)
     }
 
+    /// Take the unit the owner would run last
     fn steal(&mut self) -> Option<WorkUnit> {
-        self.queue.pop_back()
+        match self.order {
+            QueueOrder::Fifo => self.queue.pop_back(),
+            QueueOrder::Priority | QueueOrder::Aging { .. } => {
+                let idx = (0..self.queue.len()).min_by_key(|&i| self.rank(i))?;
+                self.queue.remove(idx)
+            }
+        }
     }
 
-    fn is_empty(&self) -> bool {
-        self.queue.is_empty()
+    /// Steal half the queue (rounded down), in `steal` order
+    fn steal_half(&mut self) -> Vec<WorkUnit> {
+        (0..self.len() / 2).filter_map(|_| self.steal()).collect()
     }
 
     fn len(&self) -> usize {
         self.queue.len()
     }
 
+    /// Cost of queued and already processed units
+    fn total_cost(&self) -> u64 {
+        self.queue.iter().map(|w| w.cost).sum::<u64>() + self.processed_cost
+    }
+
     fn process(&mut self, work: WorkUnit) {
         self.processed.push(work.id);
+        self.processed_cost += work.cost;
     }
 }
 
+/// Scheduling statistics
+#[derive(Debug, Clone, PartialEq)]
+struct SchedulerStats {
+    /// Units moved between workers by stealing
+    steals: usize,
+    /// Units processed by each worker
+    processed: Vec<usize>,
+    /// Total cost processed by each worker
+    cost: Vec<u64>,
+}
+
 /// Work stealing scheduler
+#[derive(Serialize, Deserialize)]
 struct Scheduler {
     workers: Vec<Worker>,
+    steals: usize,
 }
 
 impl Scheduler {
     fn new(num_workers: usize) -> Self {
         let workers = (0..num_workers).map(Worker::new).collect();
-        Self { workers }
+        Self { workers, steals: 0 }
+    }
+
+    /// Set the queue order used by every worker
+    fn with_order(mut self, order: QueueOrder) -> Self {
+        for worker in &mut self.workers {
+            worker.order = order;
+        }
+        self
     }
 
     fn worker_count(&self) -> usize {
@@ -91,47 +192,178 @@ impl Scheduler {
     }
 
     /// Balance load by stealing
+    ///
+    /// Moves one unit at a time from the most-loaded worker to the
+    /// least-loaded one until their queues differ by at most one.
+    ///
+    /// Terminates: a unit only moves when the gap is at least 2, so every
+    /// move strictly lowers the sum of squared queue lengths, which is a
+    /// non-negative integer.
     fn balance(&mut self) {
-        let num_workers = self.workers.len();
-
-        for i in 0..num_workers {
-            while self.workers[i].is_empty() {
-                // Find a worker to steal from
-                let mut stolen = None;
-                for j in 0..num_workers {
-                    if i != j && self.workers[j].len() > 1 {
-                        stolen = self.workers[j].steal();
-                        break;
-                    }
-                }
-
-                if let Some(work) = stolen {
-                    self.workers[i].push(work);
-                } else {
-                    break;
+        while let Some((victim, thief)) = self.imbalance() {
+            match self.workers[victim].steal() {
+                Some(work) => {
+                    self.workers[thief].push(work);
+                    self.steals += 1;
                 }
+                None => break,
             }
         }
     }
 
+    /// Most- and least-loaded workers (lowest index on ties), if their loads
+    /// differ by more than one
+    fn imbalance(&self) -> Option<(usize, usize)> {
+        let len = |j: usize| self.workers[j].len();
+        let victim = (0..self.workers.len()).max_by_key(|&j| (len(j), Reverse(j)))?;
+        let thief = (0..self.workers.len()).min_by_key(|&j| (len(j), j))?;
+        (len(victim) > len(thief) + 1).then_some((victim, thief))
+    }
+
+    /// Queue a unit on the least-loaded worker (lowest index on ties)
+    fn submit(&mut self, work: WorkUnit) {
+        let idx = (0..self.workers.len())
+            .min_by_key(|&j| (self.workers[j].len(), j))
+            .expect("scheduler has at least one worker");
+        self.workers[idx].push(work);
+    }
+
     /// Process all work
     fn execute(&mut self) {
-        loop {
-            let mut any_work = false;
+        while self.step() {}
+    }
 
-            for worker in &mut self.workers {
-                if let Some(work) = worker.pop() {
-                    worker.process(work);
-                    any_work = true;
+    /// Run one round without spawning; false once every queue is empty
+    fn step(&mut self) -> bool {
+        self.round(&mut |_| Vec::new())
+    }
+
+    /// Process all work, submitting the units `spawn` returns for each
+    /// processed unit so subtasks are picked up in the same run
+    fn execute_with(&mut self, mut spawn: impl FnMut(&WorkUnit) -> Vec<WorkUnit>) {
+        while self.round(&mut spawn) {}
+    }
+
+    /// Let each worker in turn process at most one unit
+    fn round(&mut self, spawn: &mut impl FnMut(&WorkUnit) -> Vec<WorkUnit>) -> bool {
+        let mut any_work = false;
+
+        for i in 0..self.workers.len() {
+            if let Some(work) = self.workers[i].pop() {
+                let children = spawn(&work);
+                self.workers[i].process(work);
+                for child in children {
+                    self.submit(child);
                 }
+                any_work = true;
             }
+        }
+
+        any_work
+    }
+
+    /// Pending queues, processed history and counters as JSON
+    fn checkpoint(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Rebuild a scheduler from `checkpoint` output; it continues exactly
+    /// where the checkpointed one stopped
+    fn resume(json: &str) -> Result<Scheduler> {
+        serde_json::from_str(json).context("parsing scheduler checkpoint")
+    }
 
-            if !any_work {
+    /// Process all work in lock-step rounds, stealing for idle workers
+    ///
+    /// Each round, workers with empty queues steal half the queue of a loaded
+    /// worker chosen by [`steal_plan`], then every worker processes at most
+    /// one unit. This is the sequential reference for `execute_parallel`.
+    fn execute_stealing(&mut self) {
+        loop {
+            let lens: Vec<usize> = self.workers.iter().map(Worker::len).collect();
+            if lens.iter().all(|&len| len == 0) {
                 break;
             }
+            for (thief, victim) in steal_plan(&lens).into_iter().enumerate() {
+                let Some(victim) = victim else { continue };
+                for work in self.workers[victim].steal_half() {
+                    self.workers[thief].push(work);
+                    self.steals += 1;
+                }
+            }
+            for worker in &mut self.workers {
+                if let Some(work) = worker.pop() {
+                    worker.process(work);
+                }
+            }
         }
     }
 
+    /// Process all work with one thread per worker, stealing across threads
+    ///
+    /// Every deque sits behind its own mutex and an idle thread steals from
+    /// another worker's deque at runtime. Threads run the same rounds as
+    /// `execute_stealing`, separated by barriers: all threads read the same
+    /// queue lengths, so they agree on who steals from whom, and the plan
+    /// pairs each victim with at most one thief. No outcome depends on thread
+    /// timing, so each worker processes the same ids as `execute_stealing`.
+    fn execute_parallel(&mut self) {
+        let deques: Vec<Mutex<Worker>> = self.workers.drain(..).map(Mutex::new).collect();
+        let barrier = Barrier::new(deques.len());
+
+        let steals: usize = thread::scope(|s| {
+            let handles: Vec<_> = (0..deques.len())
+                .map(|me| {
+                    let (deques, barrier) = (&deques, &barrier);
+                    s.spawn(move || {
+                        let lock = |j: usize| deques[j].lock().expect("worker lock poisoned");
+                        let mut steals = 0;
+                        loop {
+                            // Nothing is mutated between the previous round's
+                            // last barrier and the next one, so every thread
+                            // sees the same lengths and stops together
+                            let lens: Vec<usize> =
+                                (0..deques.len()).map(|j| lock(j).len()).collect();
+                            if lens.iter().all(|&len| len == 0) {
+                                break;
+                            }
+                            let victim = steal_plan(&lens)[me];
+                            barrier.wait();
+
+                            if let Some(victim) = victim {
+                                let stolen = lock(victim).steal_half();
+                                let mut worker = lock(me);
+                                for work in stolen {
+                                    worker.push(work);
+                                    steals += 1;
+                                }
+                            }
+                            barrier.wait();
+
+                            let mut worker = lock(me);
+                            if let Some(work) = worker.pop() {
+                                worker.process(work);
+                            }
+                            drop(worker);
+                            barrier.wait();
+                        }
+                        steals
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().expect("worker thread panicked"))
+                .sum()
+        });
+
+        self.steals += steals;
+        self.workers = deques
+            .into_iter()
+            .map(|d| d.into_inner().expect("worker lock poisoned"))
+            .collect();
+    }
+
     fn get_results(&self) -> Vec<Vec<usize>> {
         self.workers.iter().map(|w| w.processed.clone()).collect()
     }
@@ -139,6 +371,45 @@ impl Scheduler {
     fn total_processed(&self) -> usize {
         self.workers.iter().map(|w| w.processed.len()).sum()
     }
+
+    /// Jain's fairness index over per-worker total cost
+    ///
+    /// 1.0 when every worker carries the same cost, down to 1/n when a single
+    /// worker carries all of it. A scheduler with no work counts as fair.
+    fn fairness(&self) -> f64 {
+        let costs: Vec<f64> = self.workers.iter().map(|w| w.total_cost() as f64).collect();
+        let sum: f64 = costs.iter().sum();
+        let sum_sq: f64 = costs.iter().map(|c| c * c).sum();
+        if sum_sq == 0.0 {
+            return 1.0;
+        }
+        sum * sum / (costs.len() as f64 * sum_sq)
+    }
+
+    fn stats(&self) -> SchedulerStats {
+        SchedulerStats {
+            steals: self.steals,
+            processed: self.workers.iter().map(|w| w.processed.len()).collect(),
+            cost: self.workers.iter().map(|w| w.processed_cost).collect(),
+        }
+    }
+}
+
+/// Victim for each idle worker this round, indexed by thief
+///
+/// Idle workers, in index order, are paired with workers holding at least two
+/// units, most-loaded first (lowest index on ties). Each victim is paired with
+/// at most one thief, so concurrent steals never contend for the same deque.
+fn steal_plan(lens: &[usize]) -> Vec<Option<usize>> {
+    let mut victims: Vec<usize> = (0..lens.len()).filter(|&j| lens[j] >= 2).collect();
+    victims.sort_by_key(|&j| (Reverse(lens[j]), j));
+
+    let mut plan = vec![None; lens.len()];
+    let thieves = (0..lens.len()).filter(|&j| lens[j] == 0);
+    for (thief, victim) in thieves.zip(victims) {
+        plan[thief] = Some(victim);
+    }
+    plan
 }
 
 /// Demonstrate basic work distribution
@@ -180,6 +451,7 @@ fn stealing_demo() {
     for (i, worker) in scheduler.workers.iter().enumerate() {
         println!("   Worker {}: {} items", i, worker.len());
     }
+    println!("   Fairness (Jain): {:.3}", scheduler.fairness());
 
     scheduler.balance();
 
@@ -188,6 +460,74 @@ fn stealing_demo() {
     for (i, worker) in scheduler.workers.iter().enumerate() {
         println!("   Worker {}: {} items", i, worker.len());
     }
+    println!("   Fairness (Jain): {:.3}", scheduler.fairness());
+    println!();
+
+    scheduler.execute();
+    let stats = scheduler.stats();
+    println!("   Steals: {}", stats.steals);
+    println!("   Processed per worker: {:?}", stats.processed);
+    println!("   Cost per worker: {:?}", stats.cost);
+    println!();
+}
+
+/// Demonstrate priority-ordered queues
+fn priority_demo() {
+    println!("⭐ Priority Scheduling");
+    println!();
+
+    let work: Vec<WorkUnit> = (0..8)
+        .map(|i| WorkUnit::new(i, (i as u32 * 3) % 4, 100))
+        .collect();
+
+    for order in [QueueOrder::Fifo, QueueOrder::Priority] {
+        let mut scheduler = Scheduler::new(2).with_order(order);
+        scheduler.distribute(work.clone());
+        scheduler.execute();
+
+        println!("   {:?}:", order);
+        for (i, processed) in scheduler.get_results().iter().enumerate() {
+            let steps: Vec<String> = processed
+                .iter()
+                .map(|&id| format!("{}(p{})", id, work[id].priority))
+                .collect();
+            println!("   Worker {}: {}", i, steps.join(" → "));
+        }
+    }
+    println!();
+}
+
+/// Step at which unit 0 (priority 0) runs when a priority-5 unit arrives
+/// before every dequeue, or `None` if it is still waiting after `max_steps`
+fn starvation_steps(order: QueueOrder, max_steps: usize) -> Option<(usize, u32)> {
+    let mut worker = Worker::new(0);
+    worker.order = order;
+    worker.push(WorkUnit::new(0, 0, 10));
+    for step in 1..=max_steps {
+        worker.push(WorkUnit::new(step, 5, 10));
+        let work = worker.pop()?;
+        if work.id == 0 {
+            return Some((step, work.effective_priority));
+        }
+    }
+    None
+}
+
+/// Demonstrate priority aging against starvation
+fn aging_demo() {
+    println!("⏳ Priority Aging");
+    println!();
+    println!("   A priority-0 unit competes with a new priority-5 unit every step");
+
+    for order in [QueueOrder::Priority, QueueOrder::Aging { every: 2 }] {
+        match starvation_steps(order, 50) {
+            Some((step, effective)) => println!(
+                "   {:?}: runs at step {} (effective priority {})",
+                order, step, effective
+            ),
+            None => println!("   {:?}: still starving after 50 steps", order),
+        }
+    }
     println!();
 }
 
@@ -245,6 +585,100 @@ fn execution_demo() {
         println!("   Worker {} processed: {:?}", i, processed);
     }
     println!();
+
+    // All work queued on worker 0: idle threads must steal at runtime
+    let skewed = || {
+        let mut scheduler = Scheduler::new(4);
+        for i in 0..12 {
+            scheduler.workers[0].push(WorkUnit::new(i, i as u32 % 3, (i as u64 + 1) * 10));
+        }
+        scheduler
+    };
+    let mut sequential = skewed();
+    sequential.execute_stealing();
+    let mut parallel = skewed();
+    parallel.execute_parallel();
+    println!(
+        "   Parallel execution: {} processed, {} steals, same per-worker ids as sequential: {}",
+        parallel.total_processed(),
+        parallel.steals,
+        parallel.get_results() == sequential.get_results()
+    );
+    for (i, processed) in parallel.get_results().iter().enumerate() {
+        println!("   Thread {} processed: {:?}", i, processed);
+    }
+    println!();
+}
+
+/// Demonstrate tasks that spawn subtasks while running
+fn dynamic_demo() {
+    println!("🌱 Dynamic Task Submission");
+    println!();
+
+    // Units costing more than 25 split into two halves instead of running whole
+    let mut scheduler = Scheduler::new(3);
+    scheduler.submit(WorkUnit::new(0, 1, 100));
+    let mut next_id = 1;
+    scheduler.execute_with(|work| {
+        if work.cost <= 25 {
+            return Vec::new();
+        }
+        let children = vec![
+            WorkUnit::new(next_id, work.priority, work.cost / 2),
+            WorkUnit::new(next_id + 1, work.priority, work.cost / 2),
+        ];
+        next_id += 2;
+        children
+    });
+
+    println!("   Initial units: 1 (cost 100, split while cost > 25)");
+    println!("   Processed: {}", scheduler.total_processed());
+    for (i, processed) in scheduler.get_results().iter().enumerate() {
+        println!("   Worker {} processed: {:?}", i, processed);
+    }
+    println!();
+}
+
+/// Demonstrate checkpointing and resuming a run
+fn checkpoint_demo() -> Result<()> {
+    println!("💾 Checkpoint and Resume");
+    println!();
+
+    let work = || -> Vec<WorkUnit> {
+        (0..12)
+            .map(|i| WorkUnit::new(i, (i as u32 * 5) % 3, 10))
+            .collect()
+    };
+
+    let mut straight = Scheduler::new(3).with_order(QueueOrder::Aging { every: 2 });
+    straight.distribute(work());
+    straight.execute();
+
+    let mut interrupted = Scheduler::new(3).with_order(QueueOrder::Aging { every: 2 });
+    interrupted.distribute(work());
+    interrupted.step();
+    interrupted.step();
+
+    let path = std::env::temp_dir().join("ch19-scheduler-checkpoint.json");
+    fs::write(&path, interrupted.checkpoint()?)
+        .with_context(|| format!("writing {}", path.display()))?;
+    println!(
+        "   Checkpoint after 2 rounds: {} processed, written to {}",
+        interrupted.total_processed(),
+        path.display()
+    );
+
+    let json = fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+    let mut resumed = Scheduler::resume(&json)?;
+    resumed.execute();
+    println!("   Resumed run processed: {}", resumed.total_processed());
+    println!(
+        "   Same results as uninterrupted run: {}",
+        resumed.get_results() == straight.get_results()
+    );
+    println!();
+
+    Ok(())
 }
 
 /// EU AI Act compliance
@@ -287,6 +721,14 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
+    priority_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
+    aging_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
     determinism_demo();
     println!("{}", "─".repeat(70));
     println!();
@@ -295,6 +737,14 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
+    dynamic_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
+    checkpoint_demo()?;
+    println!("{}", "─".repeat(70));
+    println!();
+
     eu_compliance();
     println!("{}", "─".repeat(70));
     println!();
@@ -343,6 +793,243 @@ mod tests {
         assert_eq!(stolen.id, 2); // Steal from back
     }
 
+    #[test]
+    fn test_priority_order_per_worker() {
+        let work: Vec<WorkUnit> = (0..12)
+            .map(|i| WorkUnit::new(i, (i as u32 * 7) % 4, 10))
+            .collect();
+        let mut scheduler = Scheduler::new(3).with_order(QueueOrder::Priority);
+        scheduler.distribute(work.clone());
+        scheduler.execute();
+
+        assert_eq!(scheduler.total_processed(), 12);
+        for processed in scheduler.get_results() {
+            let keys: Vec<(Reverse<u32>, usize)> = processed
+                .iter()
+                .map(|&id| (Reverse(work[id].priority), id))
+                .collect();
+            let mut sorted = keys.clone();
+            sorted.sort();
+            assert_eq!(keys, sorted, "highest priority first, then lowest id");
+        }
+    }
+
+    #[test]
+    fn test_priority_steal_takes_lowest_priority() {
+        let mut worker = Worker::new(0);
+        worker.order = QueueOrder::Priority;
+        worker.push(WorkUnit::new(1, 5, 10));
+        worker.push(WorkUnit::new(2, 1, 10));
+        worker.push(WorkUnit::new(3, 1, 10));
+
+        assert_eq!(worker.steal().map(|w| w.id), Some(3));
+        assert_eq!(worker.pop().map(|w| w.id), Some(1));
+    }
+
+    #[test]
+    fn test_balance_equalizes_from_most_loaded() {
+        let mut scheduler = Scheduler::new(4);
+        for i in 0..6 {
+            scheduler.workers[0].push(WorkUnit::new(i, 1, 10));
+        }
+
+        scheduler.balance();
+
+        let loads: Vec<usize> = scheduler.workers.iter().map(Worker::len).collect();
+        let max = loads.iter().max().copied().unwrap_or(0);
+        let min = loads.iter().min().copied().unwrap_or(0);
+        assert!(max - min <= 1, "loads {:?} differ by more than 1", loads);
+        assert_eq!(loads.iter().sum::<usize>(), 6);
+    }
+
+    #[test]
+    fn test_balance_terminates_without_empty_workers() {
+        let mut scheduler = Scheduler::new(3);
+        let mut id = 0;
+        for (worker, count) in [3, 1, 1].into_iter().enumerate() {
+            for _ in 0..count {
+                scheduler.workers[worker].push(WorkUnit::new(id, 1, 10));
+                id += 1;
+            }
+        }
+
+        scheduler.balance();
+
+        let loads: Vec<usize> = scheduler.workers.iter().map(Worker::len).collect();
+        assert_eq!(loads, vec![2, 2, 1]);
+    }
+
+    #[test]
+    fn test_stats_count_steals() {
+        let mut balanced = Scheduler::new(3);
+        balanced.distribute((0..6).map(|i| WorkUnit::new(i, 1, 10)).collect());
+        balanced.balance();
+        balanced.execute();
+        assert_eq!(
+            balanced.stats(),
+            SchedulerStats {
+                steals: 0,
+                processed: vec![2, 2, 2],
+                cost: vec![20, 20, 20],
+            }
+        );
+
+        let mut skewed = Scheduler::new(3);
+        for i in 0..6 {
+            skewed.workers[0].push(WorkUnit::new(i, 1, 10));
+        }
+        skewed.balance();
+        skewed.execute();
+        let stats = skewed.stats();
+        assert!(stats.steals > 0);
+        assert_eq!(stats.processed, vec![2, 2, 2]);
+    }
+
+    #[test]
+    fn test_steal_plan_pairs_each_victim_once() {
+        // Thieves 1, 3, 4; victims by load: 0 (5 units), then 2 (3 units)
+        assert_eq!(
+            steal_plan(&[5, 0, 3, 0, 0]),
+            vec![None, Some(0), None, Some(2), None]
+        );
+        // A single unit is not worth stealing
+        assert_eq!(steal_plan(&[1, 0]), vec![None, None]);
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let make = || {
+            // Skewed: worker 0 gets most of the work, worker 1 none
+            let mut scheduler = Scheduler::new(3).with_order(QueueOrder::Priority);
+            for i in 0..10 {
+                let worker = if i < 8 { 0 } else { 2 };
+                scheduler.workers[worker].push(WorkUnit::new(i, (i % 4) as u32, 10));
+            }
+            scheduler
+        };
+
+        let mut sequential = make();
+        sequential.execute_stealing();
+        assert_eq!(sequential.total_processed(), 10);
+        assert!(sequential.steals > 0, "idle workers must steal");
+
+        for _ in 0..20 {
+            let mut parallel = make();
+            parallel.execute_parallel();
+            assert_eq!(parallel.total_processed(), sequential.total_processed());
+            assert_eq!(parallel.steals, sequential.steals);
+            // Same ids per worker, even in the same order
+            assert_eq!(parallel.get_results(), sequential.get_results());
+        }
+    }
+
+    #[test]
+    fn test_fairness_index() {
+        let mut even = Scheduler::new(3);
+        even.distribute((0..6).map(|i| WorkUnit::new(i, 1, 10)).collect());
+        assert!((even.fairness() - 1.0).abs() < 1e-12);
+
+        // Processing moves cost from queue to history without changing it
+        even.execute();
+        assert!((even.fairness() - 1.0).abs() < 1e-12);
+
+        let mut overloaded = Scheduler::new(3);
+        for i in 0..6 {
+            overloaded.workers[0].push(WorkUnit::new(i, 1, 10));
+        }
+        assert!((overloaded.fairness() - 1.0 / 3.0).abs() < 1e-12);
+        overloaded.balance();
+        assert!((overloaded.fairness() - 1.0).abs() < 1e-12);
+
+        assert_eq!(Scheduler::new(2).fairness(), 1.0);
+    }
+
+    #[test]
+    fn test_spawned_children_are_processed() {
+        let mut scheduler = Scheduler::new(2);
+        scheduler.distribute((0..4).map(|i| WorkUnit::new(i, 1, 10)).collect());
+
+        scheduler.execute_with(|work| {
+            if work.id == 0 {
+                vec![WorkUnit::new(100, 1, 5), WorkUnit::new(101, 1, 5)]
+            } else {
+                Vec::new()
+            }
+        });
+
+        assert_eq!(scheduler.total_processed(), 6);
+        let mut all: Vec<usize> = scheduler.get_results().concat();
+        all.sort_unstable();
+        assert_eq!(all, vec![0, 1, 2, 3, 100, 101]);
+    }
+
+    #[test]
+    fn test_submit_routes_to_least_loaded() {
+        let mut scheduler = Scheduler::new(3);
+        scheduler.workers[0].push(WorkUnit::new(0, 1, 10));
+        scheduler.workers[2].push(WorkUnit::new(1, 1, 10));
+
+        scheduler.submit(WorkUnit::new(2, 1, 10));
+        scheduler.submit(WorkUnit::new(3, 1, 10));
+
+        let loads: Vec<usize> = scheduler.workers.iter().map(Worker::len).collect();
+        assert_eq!(loads, vec![2, 1, 1]);
+    }
+
+    #[test]
+    fn test_aging_prevents_starvation() {
+        assert_eq!(starvation_steps(QueueOrder::Priority, 100), None);
+
+        // Waiting 10 dequeues lifts priority 0 to 5; the tie goes to id 0
+        let (step, effective) =
+            starvation_steps(QueueOrder::Aging { every: 2 }, 100).expect("aged unit runs");
+        assert_eq!(step, 11);
+        assert_eq!(effective, 5);
+    }
+
+    #[test]
+    fn test_aging_saturates_at_max_priority() {
+        let mut worker = Worker::new(0);
+        worker.order = QueueOrder::Aging { every: 1 };
+        let unit = WorkUnit::new(0, u32::MAX - 1, 10);
+        worker.clock = 5;
+        assert_eq!(worker.effective_priority(&unit), u32::MAX);
+
+        worker.clock = u64::MAX;
+        assert_eq!(
+            worker.effective_priority(&WorkUnit::new(1, 0, 10)),
+            u32::MAX
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_resume_matches_straight_run() {
+        let make = || {
+            let mut scheduler = Scheduler::new(2).with_order(QueueOrder::Aging { every: 3 });
+            scheduler.distribute(
+                (0..9)
+                    .map(|i| WorkUnit::new(i, (i % 4) as u32, 10))
+                    .collect(),
+            );
+            scheduler
+        };
+
+        let mut straight = make();
+        straight.execute();
+
+        let mut interrupted = make();
+        assert!(interrupted.step());
+        assert!(interrupted.step());
+        let json = interrupted.checkpoint().expect("scheduler serializes");
+
+        let mut resumed = Scheduler::resume(&json).expect("checkpoint parses");
+        assert_eq!(resumed.total_processed(), 4);
+        resumed.execute();
+
+        assert_eq!(resumed.get_results(), straight.get_results());
+        assert_eq!(resumed.stats(), straight.stats());
+    }
+
     #[test]
     fn test_distribution() {
         let mut scheduler = Scheduler::new(3);
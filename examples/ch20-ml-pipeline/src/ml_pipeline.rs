@@ -6,14 +6,57 @@
 /// - Deterministic results
 ///
 /// **VALIDATION:** `make run-ch20`
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::time::Instant;
+
+/// Failure of a pipeline stage
+#[derive(Debug, Clone, PartialEq)]
+struct PipelineError {
+    /// Name of the stage that failed
+    stage: String,
+    message: String,
+}
+
+impl PipelineError {
+    fn new(stage: &str, message: impl Into<String>) -> Self {
+        Self {
+            stage: stage.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.stage, self.message)
+    }
+}
+
+impl std::error::Error for PipelineError {}
 
 /// Pipeline stage trait
 trait Stage {
     type Input;
     type Output;
-    fn process(&self, input: Self::Input) -> Self::Output;
+    fn process(&self, input: Self::Input) -> Result<Self::Output, PipelineError>;
     fn name(&self) -> &str;
+
+    /// Whether the output depends only on the input, so it may be cached
+    fn is_pure(&self) -> bool {
+        false
+    }
+
+    /// Hyperparameters recorded in the pipeline's lineage
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        Vec::new()
+    }
 }
 
 /// Data loader stage
@@ -33,9 +76,9 @@ impl Stage for DataLoader {
     type Input = ();
     type Output = Vec<Vec<f64>>;
 
-    fn process(&self, _: Self::Input) -> Self::Output {
+    fn process(&self, _: Self::Input) -> Result<Self::Output, PipelineError> {
         // Generate deterministic data
-        (0..100).map(|i| vec![i as f64, (i * 2) as f64]).collect()
+        Ok((0..100).map(|i| vec![i as f64, (i * 2) as f64]).collect())
     }
 
     fn name(&self) -> &str {
@@ -62,16 +105,180 @@ impl Stage for Preprocessor {
     type Input = Vec<Vec<f64>>;
     type Output = Vec<Vec<f64>>;
 
-    fn process(&self, input: Self::Input) -> Self::Output {
-        input
+    fn process(&self, input: Self::Input) -> Result<Self::Output, PipelineError> {
+        Ok(input
             .into_iter()
             .map(|row| row.into_iter().map(|x| x * self.scale).collect())
+            .collect())
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        vec![("scale", self.scale)]
+    }
+}
+
+/// Z-score standardization stage
+///
+/// Per-feature mean and standard deviation are fitted once and then reused,
+/// so new data is transformed exactly like the training data.
+#[derive(Debug, Clone)]
+struct Standardizer {
+    name: String,
+    means: Vec<f64>,
+    /// Population standard deviations; constant features keep 1.0 so they
+    /// are only centred
+    stds: Vec<f64>,
+}
+
+impl Standardizer {
+    /// Fit per-feature statistics on `rows`
+    fn fit(rows: &[Vec<f64>]) -> Result<Self, PipelineError> {
+        let name = "Standardizer";
+        let width = match rows.first() {
+            Some(row) => row.len(),
+            None => return Err(PipelineError::new(name, "cannot fit on an empty dataset")),
+        };
+        if let Some(i) = rows.iter().position(|row| row.len() != width) {
+            return Err(PipelineError::new(
+                name,
+                format!(
+                    "row {} has {} features, expected {}",
+                    i,
+                    rows[i].len(),
+                    width
+                ),
+            ));
+        }
+
+        let n = rows.len() as f64;
+        let means: Vec<f64> = (0..width)
+            .map(|f| rows.iter().map(|row| row[f]).sum::<f64>() / n)
+            .collect();
+        let stds = means
+            .iter()
+            .enumerate()
+            .map(|(f, mean)| {
+                let var = rows.iter().map(|row| (row[f] - mean).powi(2)).sum::<f64>() / n;
+                if var > 0.0 {
+                    var.sqrt()
+                } else {
+                    1.0
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            name: name.to_string(),
+            means,
+            stds,
+        })
+    }
+
+    /// Apply the fitted transform to `rows`
+    fn transform(&self, rows: Vec<Vec<f64>>) -> Result<Vec<Vec<f64>>, PipelineError> {
+        rows.into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                if row.len() != self.means.len() {
+                    return Err(PipelineError::new(
+                        self.name(),
+                        format!(
+                            "row {} has {} features, fitted on {}",
+                            i,
+                            row.len(),
+                            self.means.len()
+                        ),
+                    ));
+                }
+                Ok(row
+                    .iter()
+                    .zip(self.means.iter().zip(&self.stds))
+                    .map(|(x, (mean, std))| (x - mean) / std)
+                    .collect())
+            })
             .collect()
     }
+}
+
+impl Stage for Standardizer {
+    type Input = Vec<Vec<f64>>;
+    type Output = Vec<Vec<f64>>;
+
+    fn process(&self, input: Self::Input) -> Result<Self::Output, PipelineError> {
+        self.transform(input)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+}
+
+/// Data validation stage: every row must have the expected width and
+/// contain only finite values
+struct Validator {
+    name: String,
+    expected_features: usize,
+}
+
+impl Validator {
+    fn new(expected_features: usize) -> Self {
+        Self {
+            name: "Validator".to_string(),
+            expected_features,
+        }
+    }
+}
+
+impl Stage for Validator {
+    type Input = Vec<Vec<f64>>;
+    type Output = Vec<Vec<f64>>;
+
+    fn process(&self, input: Self::Input) -> Result<Self::Output, PipelineError> {
+        for (i, row) in input.iter().enumerate() {
+            if row.len() != self.expected_features {
+                return Err(PipelineError::new(
+                    self.name(),
+                    format!(
+                        "row {} has {} features, expected {}",
+                        i,
+                        row.len(),
+                        self.expected_features
+                    ),
+                ));
+            }
+            if let Some((j, x)) = row.iter().enumerate().find(|(_, x)| !x.is_finite()) {
+                return Err(PipelineError::new(
+                    self.name(),
+                    format!("row {} feature {} is {}", i, j, x),
+                ));
+            }
+        }
+        Ok(input)
+    }
 
     fn name(&self) -> &str {
         &self.name
     }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        vec![("expected_features", self.expected_features as f64)]
+    }
 }
 
 /// Feature extractor stage
@@ -91,23 +298,41 @@ impl Stage for FeatureExtractor {
     type Input = Vec<Vec<f64>>;
     type Output = Vec<f64>;
 
-    fn process(&self, input: Self::Input) -> Self::Output {
-        // Extract mean of each feature
+    fn process(&self, input: Self::Input) -> Result<Self::Output, PipelineError> {
+        // Extract mean of each feature; an empty dataset has no means
         if input.is_empty() {
-            return Vec::new();
+            return Err(PipelineError::new(
+                self.name(),
+                "no rows to extract features from",
+            ));
         }
 
         let num_features = input[0].len();
+        if let Some(i) = input.iter().position(|row| row.len() != num_features) {
+            return Err(PipelineError::new(
+                self.name(),
+                format!(
+                    "row {} has {} features, expected {}",
+                    i,
+                    input[i].len(),
+                    num_features
+                ),
+            ));
+        }
         let n = input.len() as f64;
 
-        (0..num_features)
+        Ok((0..num_features)
             .map(|f| input.iter().map(|row| row[f]).sum::<f64>() / n)
-            .collect()
+            .collect())
     }
 
     fn name(&self) -> &str {
         &self.name
     }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
 }
 
 /// Model trainer stage
@@ -125,7 +350,7 @@ impl ModelTrainer {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 struct Model {
     weights: Vec<f64>,
     bias: f64,
@@ -135,64 +360,552 @@ impl Stage for ModelTrainer {
     type Input = Vec<f64>;
     type Output = Model;
 
-    fn process(&self, features: Self::Input) -> Self::Output {
+    fn process(&self, features: Self::Input) -> Result<Self::Output, PipelineError> {
         // Simple deterministic training
         let weights: Vec<f64> = features.iter().map(|&f| f * self.learning_rate).collect();
         let bias = features.iter().sum::<f64>() / features.len() as f64;
 
-        Model { weights, bias }
+        Ok(Model { weights, bias })
     }
 
     fn name(&self) -> &str {
         &self.name
     }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        vec![("learning_rate", self.learning_rate)]
+    }
+}
+
+/// Value flowing between type-erased stages
+#[derive(Debug, Clone, PartialEq)]
+enum PipelineValue {
+    Unit,
+    Rows(Vec<Vec<f64>>),
+    Features(Vec<f64>),
+    Model(Model),
+}
+
+impl PipelineValue {
+    fn kind(&self) -> &'static str {
+        match self {
+            PipelineValue::Unit => "unit",
+            PipelineValue::Rows(_) => "rows",
+            PipelineValue::Features(_) => "features",
+            PipelineValue::Model(_) => "model",
+        }
+    }
+
+    fn shape(&self) -> Shape {
+        match self {
+            PipelineValue::Unit => Shape::Unit,
+            PipelineValue::Rows(rows) => Shape::Rows {
+                rows: rows.len(),
+                cols: rows.first().map_or(0, Vec::len),
+            },
+            PipelineValue::Features(features) => Shape::Features {
+                len: features.len(),
+            },
+            PipelineValue::Model(model) => Shape::Model {
+                weights: model.weights.len(),
+            },
+        }
+    }
+
+    /// Hash of the value's contents; floats are hashed by their bit pattern
+    fn content_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.kind().hash(&mut hasher);
+        match self {
+            PipelineValue::Unit => {}
+            PipelineValue::Rows(rows) => {
+                rows.len().hash(&mut hasher);
+                for row in rows {
+                    hash_floats(row, &mut hasher);
+                }
+            }
+            PipelineValue::Features(features) => hash_floats(features, &mut hasher),
+            PipelineValue::Model(model) => {
+                hash_floats(&model.weights, &mut hasher);
+                model.bias.to_bits().hash(&mut hasher);
+            }
+        }
+        hasher.finish()
+    }
+}
+
+fn hash_floats(values: &[f64], hasher: &mut impl Hasher) {
+    values.len().hash(hasher);
+    for v in values {
+        v.to_bits().hash(hasher);
+    }
+}
+
+/// Shape of a `PipelineValue`, as recorded in the lineage
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Shape {
+    Unit,
+    Rows { rows: usize, cols: usize },
+    Features { len: usize },
+    Model { weights: usize },
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Shape::Unit => write!(f, "()"),
+            Shape::Rows { rows, cols } => write!(f, "{}×{}", rows, cols),
+            Shape::Features { len } => write!(f, "features[{}]", len),
+            Shape::Model { weights } => write!(f, "model[{}]", weights),
+        }
+    }
+}
+
+/// A stage input or output type that can travel as a `PipelineValue`
+trait PipelineData: Sized {
+    fn from_value(value: PipelineValue) -> Result<Self, String>;
+    fn into_value(self) -> PipelineValue;
+}
+
+fn unexpected(expected: &str, got: &PipelineValue) -> String {
+    format!("expected {}, got {}", expected, got.kind())
+}
+
+impl PipelineData for () {
+    fn from_value(value: PipelineValue) -> Result<Self, String> {
+        match value {
+            PipelineValue::Unit => Ok(()),
+            other => Err(unexpected("unit", &other)),
+        }
+    }
+
+    fn into_value(self) -> PipelineValue {
+        PipelineValue::Unit
+    }
+}
+
+impl PipelineData for Vec<Vec<f64>> {
+    fn from_value(value: PipelineValue) -> Result<Self, String> {
+        match value {
+            PipelineValue::Rows(rows) => Ok(rows),
+            other => Err(unexpected("rows", &other)),
+        }
+    }
+
+    fn into_value(self) -> PipelineValue {
+        PipelineValue::Rows(self)
+    }
+}
+
+impl PipelineData for Vec<f64> {
+    fn from_value(value: PipelineValue) -> Result<Self, String> {
+        match value {
+            PipelineValue::Features(features) => Ok(features),
+            other => Err(unexpected("features", &other)),
+        }
+    }
+
+    fn into_value(self) -> PipelineValue {
+        PipelineValue::Features(self)
+    }
+}
+
+impl PipelineData for Model {
+    fn from_value(value: PipelineValue) -> Result<Self, String> {
+        match value {
+            PipelineValue::Model(model) => Ok(model),
+            other => Err(unexpected("model", &other)),
+        }
+    }
+
+    fn into_value(self) -> PipelineValue {
+        PipelineValue::Model(self)
+    }
+}
+
+/// Type-erased stage, so stages of different types can share a `Vec`
+///
+/// Every `Stage` whose input and output are `PipelineData` is a `DynStage`.
+trait DynStage {
+    fn stage_name(&self) -> &str;
+    fn is_pure(&self) -> bool;
+    fn params(&self) -> Vec<(&'static str, f64)>;
+    fn process_value(&self, input: PipelineValue) -> Result<PipelineValue, PipelineError>;
+}
+
+impl<S> DynStage for S
+where
+    S: Stage,
+    S::Input: PipelineData,
+    S::Output: PipelineData,
+{
+    fn stage_name(&self) -> &str {
+        self.name()
+    }
+
+    fn is_pure(&self) -> bool {
+        Stage::is_pure(self)
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        Stage::params(self)
+    }
+
+    fn process_value(&self, input: PipelineValue) -> Result<PipelineValue, PipelineError> {
+        let input = S::Input::from_value(input).map_err(|e| PipelineError::new(self.name(), e))?;
+        self.process(input).map(PipelineData::into_value)
+    }
+}
+
+/// Statistics collector stage: per-feature minimum and maximum, interleaved
+/// as `[min0, max0, min1, max1, ...]`
+struct StatsCollector {
+    name: String,
+}
+
+impl StatsCollector {
+    fn new() -> Self {
+        Self {
+            name: "StatsCollector".to_string(),
+        }
+    }
+}
+
+impl Stage for StatsCollector {
+    type Input = Vec<Vec<f64>>;
+    type Output = Vec<f64>;
+
+    fn process(&self, input: Self::Input) -> Result<Self::Output, PipelineError> {
+        let width = input.first().map_or(0, Vec::len);
+        if width == 0 {
+            return Err(PipelineError::new(self.name(), "no features to summarize"));
+        }
+        if let Some(i) = input.iter().position(|row| row.len() != width) {
+            return Err(PipelineError::new(
+                self.name(),
+                format!(
+                    "row {} has {} features, expected {}",
+                    i,
+                    input[i].len(),
+                    width
+                ),
+            ));
+        }
+
+        Ok((0..width)
+            .flat_map(|f| {
+                let column = input.iter().map(move |row| row[f]);
+                let min = column.clone().fold(f64::INFINITY, f64::min);
+                let max = column.fold(f64::NEG_INFINITY, f64::max);
+                [min, max]
+            })
+            .collect())
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+}
+
+/// Fan-out/fan-in node: every branch receives a copy of the same rows, and
+/// the feature vectors they produce are concatenated in branch order
+struct FanOut {
+    name: String,
+    branches: Vec<Box<dyn DynStage>>,
+}
+
+impl FanOut {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            branches: Vec::new(),
+        }
+    }
+
+    fn branch(mut self, stage: impl DynStage + 'static) -> Self {
+        self.branches.push(Box::new(stage));
+        self
+    }
+}
+
+impl Stage for FanOut {
+    type Input = Vec<Vec<f64>>;
+    type Output = Vec<f64>;
+
+    fn process(&self, input: Self::Input) -> Result<Self::Output, PipelineError> {
+        let mut merged = Vec::new();
+        for branch in &self.branches {
+            match branch.process_value(PipelineValue::Rows(input.clone()))? {
+                PipelineValue::Features(features) => merged.extend(features),
+                other => {
+                    return Err(PipelineError::new(
+                        self.name(),
+                        format!("branch {} produced {}", branch.stage_name(), other.kind()),
+                    ))
+                }
+            }
+        }
+        Ok(merged)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_pure(&self) -> bool {
+        self.branches.iter().all(|b| b.is_pure())
+    }
+}
+
+/// Timing of one stage run (same shape as the ch18 renacer profiler's event)
+#[derive(Debug, Clone)]
+struct ProfileEvent {
+    name: String,
+    duration_ns: u64,
+}
+
+/// Per-stage timings for one pipeline run
+#[derive(Debug, Clone)]
+struct ProfileReport {
+    events: Vec<ProfileEvent>,
+}
+
+impl ProfileReport {
+    fn total_ns(&self) -> u64 {
+        self.events.iter().map(|e| e.duration_ns).sum()
+    }
+
+    /// Slowest stage; ties go to the earliest stage
+    fn slowest(&self) -> Option<&ProfileEvent> {
+        self.events.iter().rev().max_by_key(|e| e.duration_ns)
+    }
+}
+
+/// Provenance of one stage in a pipeline run
+#[derive(Debug, Clone, Serialize)]
+struct LineageStage {
+    stage: String,
+    input: Shape,
+    output: Shape,
+    params: BTreeMap<String, f64>,
+}
+
+/// Provenance of the most recent pipeline run, in stage order
+#[derive(Debug, Clone, Default, Serialize)]
+struct Lineage {
+    stages: Vec<LineageStage>,
+}
+
+impl Lineage {
+    fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Hyperparameters of the standard pipeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PipelineConfig {
+    /// `Preprocessor` scale factor
+    scale: f64,
+    /// `ModelTrainer` learning rate
+    learning_rate: f64,
+}
+
+impl Default for PipelineConfig {
+    fn default() -> Self {
+        Self {
+            scale: 0.01,
+            learning_rate: 0.1,
+        }
+    }
+}
+
+impl PipelineConfig {
+    fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json).with_context(|| format!("writing config {}", path.display()))
+    }
+
+    fn load(path: &Path) -> Result<PipelineConfig> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("reading config {}", path.display()))?;
+        serde_json::from_str(&text).with_context(|| format!("parsing config {}", path.display()))
+    }
 }
 
 /// Pipeline executor
 struct Pipeline {
+    /// Names of the stages run so far, in order
     stages: Vec<String>,
+    /// One timing event per stage of the latest run, including a failing one
+    events: Vec<ProfileEvent>,
+    steps: Vec<Box<dyn DynStage>>,
+    /// (input, output) pairs of pure stages bucketed by (stage index, input
+    /// hash); `None` when caching is off
+    cache: Option<HashMap<(usize, u64), Vec<CacheEntry>>>,
+    cache_hits: usize,
+    /// Stages that completed in the latest `execute`
+    lineage: Lineage,
+}
+
+/// A pure stage's output together with the input that produced it
+struct CacheEntry {
+    input: PipelineValue,
+    output: PipelineValue,
 }
 
 impl Pipeline {
+    /// The standard loader → preprocessor → extractor → trainer pipeline
     fn new() -> Self {
-        Self { stages: Vec::new() }
+        Self::from_config(&PipelineConfig::default())
     }
 
-    fn log(&mut self, stage: &str) {
-        self.stages.push(stage.to_string());
+    /// The standard pipeline with hyperparameters taken from `config`
+    fn from_config(config: &PipelineConfig) -> Self {
+        Self::empty()
+            .with_stage(DataLoader::new())
+            .with_stage(Preprocessor::new(config.scale))
+            .with_stage(FeatureExtractor::new())
+            .with_stage(ModelTrainer::new(config.learning_rate))
     }
 
-    fn run(&mut self) -> Model {
-        // Execute pipeline stages
-        let loader = DataLoader::new();
-        self.log(loader.name());
-        let data = loader.process(());
+    /// A pipeline with no stages, to be built up with `with_stage`
+    fn empty() -> Self {
+        Self {
+            stages: Vec::new(),
+            events: Vec::new(),
+            steps: Vec::new(),
+            cache: None,
+            cache_hits: 0,
+            lineage: Lineage::default(),
+        }
+    }
 
-        let preprocessor = Preprocessor::new(0.01);
-        self.log(preprocessor.name());
-        let preprocessed = preprocessor.process(data);
+    /// Reuse the output of a pure stage when it sees an input it has seen before
+    fn with_cache(mut self) -> Self {
+        self.cache = Some(HashMap::new());
+        self
+    }
 
-        let extractor = FeatureExtractor::new();
-        self.log(extractor.name());
-        let features = extractor.process(preprocessed);
+    fn with_stage(mut self, stage: impl DynStage + 'static) -> Self {
+        self.steps.push(Box::new(stage));
+        self
+    }
 
-        let trainer = ModelTrainer::new(0.1);
-        self.log(trainer.name());
-        trainer.process(features)
+    /// Feed `input` through every stage in order
+    ///
+    /// Stops at the first stage that fails or receives a value of the wrong
+    /// kind; later stages are not run and the error names the failing stage.
+    /// With caching on, a pure stage whose input was seen before returns its
+    /// stored output instead of running again. The hash only picks a bucket;
+    /// a hit also requires the stored input to equal the new one, so a hash
+    /// collision cannot return another input's output. Hashing and lookup
+    /// happen outside the timed section.
+    fn execute(&mut self, input: PipelineValue) -> Result<PipelineValue, PipelineError> {
+        let mut value = input;
+        self.events.clear();
+        self.lineage = Lineage::default();
+        for (index, step) in self.steps.iter().enumerate() {
+            self.stages.push(step.stage_name().to_string());
+            let input_shape = value.shape();
+            let key = match &self.cache {
+                Some(_) if step.is_pure() => Some((index, value.content_hash())),
+                _ => None,
+            };
+            let cached = key.and_then(|key| {
+                self.cache
+                    .as_ref()?
+                    .get(&key)?
+                    .iter()
+                    .find(|entry| entry.input == value)
+                    .map(|entry| entry.output.clone())
+            });
+            let miss_input = match (&key, &cached) {
+                (Some(_), None) => Some(value.clone()),
+                _ => None,
+            };
+
+            let start = Instant::now();
+            let result = match cached {
+                Some(output) => {
+                    self.cache_hits += 1;
+                    Ok(output)
+                }
+                None => step.process_value(value),
+            };
+            let duration_ns = start.elapsed().as_nanos() as u64;
+
+            if let (Ok(output), Some(key), Some(input), Some(cache)) =
+                (&result, key, miss_input, self.cache.as_mut())
+            {
+                cache.entry(key).or_default().push(CacheEntry {
+                    input,
+                    output: output.clone(),
+                });
+            }
+            self.events.push(ProfileEvent {
+                name: step.stage_name().to_string(),
+                duration_ns,
+            });
+            value = result?;
+            self.lineage.stages.push(LineageStage {
+                stage: step.stage_name().to_string(),
+                input: input_shape,
+                output: value.shape(),
+                params: step
+                    .params()
+                    .into_iter()
+                    .map(|(name, v)| (name.to_string(), v))
+                    .collect(),
+            });
+        }
+        Ok(value)
+    }
+
+    /// Run a pipeline that starts from nothing and ends in a trained model,
+    /// returning the model together with the per-stage profile
+    fn run(&mut self) -> Result<(Model, ProfileReport), PipelineError> {
+        let value = self.execute(PipelineValue::Unit)?;
+        let model = Model::from_value(value).map_err(|e| PipelineError::new("Pipeline", e))?;
+        Ok((model, self.profile()))
+    }
+
+    fn profile(&self) -> ProfileReport {
+        ProfileReport {
+            events: self.events.clone(),
+        }
     }
 
     fn stages_executed(&self) -> &[String] {
         &self.stages
     }
+
+    fn lineage(&self) -> &Lineage {
+        &self.lineage
+    }
+
+    /// Number of stage runs answered from the cache
+    fn cache_hits(&self) -> usize {
+        self.cache_hits
+    }
 }
 
 /// Demonstrate pipeline execution
-fn pipeline_demo() {
+fn pipeline_demo() -> Result<()> {
     println!("🔄 ML Pipeline Execution");
     println!();
 
     let mut pipeline = Pipeline::new();
-    let model = pipeline.run();
+    let (model, profile) = pipeline.run()?;
 
     println!("   Stages executed:");
     for (i, stage) in pipeline.stages_executed().iter().enumerate() {
@@ -200,34 +913,86 @@ fn pipeline_demo() {
     }
     println!();
 
+    println!("   Stage profile (renacer):");
+    let total_ns = profile.total_ns().max(1);
+    for event in &profile.events {
+        println!(
+            "   - {:<18} {:>8} ns ({:>5.1}%)",
+            event.name,
+            event.duration_ns,
+            event.duration_ns as f64 / total_ns as f64 * 100.0
+        );
+    }
+    if let Some(slowest) = profile.slowest() {
+        println!("   Slowest stage: {}", slowest.name);
+    }
+    println!();
+
     println!("   Model output:");
     println!("   - Weights: {:?}", model.weights);
     println!("   - Bias: {:.4}", model.bias);
     println!();
+
+    Ok(())
 }
 
 /// Demonstrate stage composition
-fn stage_demo() {
+fn stage_demo() -> Result<()> {
     println!("🧩 Stage Composition");
     println!();
 
     let loader = DataLoader::new();
-    let data = loader.process(());
+    let data = loader.process(())?;
     println!("   DataLoader: {} rows", data.len());
 
     let preprocessor = Preprocessor::new(0.1);
-    let processed = preprocessor.process(data);
+    let processed = preprocessor.process(data)?;
     println!("   Preprocessor: scaled by 0.1");
 
     let extractor = FeatureExtractor::new();
-    let features = extractor.process(processed);
+    let features = extractor.process(processed)?;
     println!("   FeatureExtractor: {} features", features.len());
     println!("   Features: {:?}", features);
     println!();
+
+    // The same stages, composed at runtime
+    let mut pipeline = Pipeline::empty()
+        .with_stage(DataLoader::new())
+        .with_stage(Preprocessor::new(0.1))
+        .with_stage(FeatureExtractor::new());
+    match pipeline.execute(PipelineValue::Unit) {
+        Ok(value) => println!("   Runtime pipeline → {:?}", value),
+        Err(e) => println!("   Runtime pipeline failed: {}", e),
+    }
+
+    // Mis-ordered stages are caught when the value arrives
+    let mut broken = Pipeline::empty()
+        .with_stage(DataLoader::new())
+        .with_stage(ModelTrainer::new(0.1));
+    if let Err(e) = broken.execute(PipelineValue::Unit) {
+        println!("   Mis-ordered pipeline: {}", e);
+    }
+
+    // A failing stage stops the pipeline before later stages run
+    let mut starved = Pipeline::empty()
+        .with_stage(Preprocessor::new(0.1))
+        .with_stage(FeatureExtractor::new())
+        .with_stage(ModelTrainer::new(0.1));
+    if let Err(e) = starved.execute(PipelineValue::Rows(Vec::new())) {
+        println!("   Empty dataset: {}", e);
+        println!(
+            "   Stopped at {} after {:?}",
+            e.stage,
+            starved.stages_executed()
+        );
+    }
+    println!();
+
+    Ok(())
 }
 
 /// Demonstrate determinism
-fn determinism_demo() {
+fn determinism_demo() -> Result<()> {
     println!("🔁 Pipeline Determinism");
     println!();
 
@@ -235,7 +1000,7 @@ fn determinism_demo() {
 
     for run in 1..=5 {
         let mut pipeline = Pipeline::new();
-        let model = pipeline.run();
+        let (model, _) = pipeline.run()?;
         println!("   Run {}: bias = {:.6}", run, model.bias);
         results.push(model.bias);
     }
@@ -250,6 +1015,164 @@ fn determinism_demo() {
         println!("   ❌ Non-deterministic: Results varied!");
     }
     println!();
+
+    Ok(())
+}
+
+/// Demonstrate the lineage record of a run
+fn lineage_demo() -> Result<()> {
+    println!("🧾 Pipeline Lineage");
+    println!();
+
+    let mut pipeline = Pipeline::new();
+    pipeline.run()?;
+    for entry in &pipeline.lineage().stages {
+        println!(
+            "   {:<18} {:>12} → {:<12} {:?}",
+            entry.stage,
+            entry.input.to_string(),
+            entry.output.to_string(),
+            entry.params
+        );
+    }
+    println!();
+
+    println!("   As JSON:");
+    for line in pipeline.lineage().to_json()?.lines().take(12) {
+        println!("   {}", line);
+    }
+    println!("   ...");
+    println!();
+
+    Ok(())
+}
+
+/// Demonstrate caching of pure stages
+fn caching_demo() -> Result<()> {
+    println!("💾 Stage Caching");
+    println!();
+
+    let mut pipeline = Pipeline::new().with_cache();
+    let (first, _) = pipeline.run()?;
+    println!("   Run 1: {} cache hits", pipeline.cache_hits());
+
+    let (second, _) = pipeline.run()?;
+    println!(
+        "   Run 2: {} cache hits (DataLoader is not pure, so it always runs)",
+        pipeline.cache_hits()
+    );
+    println!("   Same model: {}", first == second);
+    println!();
+
+    Ok(())
+}
+
+/// Demonstrate rebuilding a pipeline from a saved configuration
+fn config_demo() -> Result<()> {
+    println!("📄 Pipeline Configuration");
+    println!();
+
+    let config = PipelineConfig {
+        scale: 0.05,
+        learning_rate: 0.2,
+    };
+    let path = std::env::temp_dir().join("ch20-pipeline-config.json");
+    config.save(&path)?;
+    println!("   Saved {:?} to {}", config, path.display());
+
+    let loaded = PipelineConfig::load(&path)?;
+    let (original, _) = Pipeline::from_config(&config).run()?;
+    let (rebuilt, _) = Pipeline::from_config(&loaded).run()?;
+    println!("   Original bias: {:.6}", original.bias);
+    println!("   Rebuilt bias:  {:.6}", rebuilt.bias);
+    println!("   Reproduced: {}", original == rebuilt);
+    println!();
+
+    Ok(())
+}
+
+/// Demonstrate a branching pipeline
+fn branching_demo() -> Result<()> {
+    println!("🌿 Branching Pipeline (fan-out/fan-in)");
+    println!();
+
+    let mut pipeline = Pipeline::empty()
+        .with_stage(DataLoader::new())
+        .with_stage(Preprocessor::new(0.01))
+        .with_stage(
+            FanOut::new("FeaturesAndStats")
+                .branch(FeatureExtractor::new())
+                .branch(StatsCollector::new()),
+        )
+        .with_stage(ModelTrainer::new(0.1));
+
+    println!("   DataLoader → Preprocessor ─┬─ FeatureExtractor ─┬─ ModelTrainer");
+    println!("                              └─ StatsCollector ───┘");
+    if let PipelineValue::Model(model) = pipeline.execute(PipelineValue::Unit)? {
+        println!(
+            "   Trained on {} merged features (2 means + 4 min/max)",
+            model.weights.len()
+        );
+        println!("   Weights: {}", format_values(&model.weights));
+        println!("   Bias: {:.4}", model.bias);
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Demonstrate z-score standardization
+fn standardization_demo() -> Result<()> {
+    println!("📏 Feature Standardization");
+    println!();
+
+    let training = DataLoader::new().process(())?;
+    let standardizer = Standardizer::fit(&training)?;
+    println!("   Fitted means: {}", format_values(&standardizer.means));
+    println!("   Fitted stds:  {}", format_values(&standardizer.stds));
+
+    let mut pipeline = Pipeline::empty()
+        .with_stage(DataLoader::new())
+        .with_stage(standardizer.clone())
+        .with_stage(FeatureExtractor::new());
+    if let PipelineValue::Features(means) = pipeline.execute(PipelineValue::Unit)? {
+        println!("   Standardized means: {}", format_values(&means));
+    }
+
+    // New data goes through the same fitted transform
+    let fresh = standardizer.transform(vec![vec![49.5, 99.0], vec![120.0, 240.0]])?;
+    for row in &fresh {
+        println!("   New row → {}", format_values(row));
+    }
+    println!();
+
+    Ok(())
+}
+
+fn format_values(values: &[f64]) -> String {
+    let parts: Vec<String> = values.iter().map(|v| format!("{:.4}", v)).collect();
+    format!("[{}]", parts.join(", "))
+}
+
+/// Demonstrate schema checks on incoming data
+fn validation_demo() {
+    println!("🛡️  Data Validation");
+    println!();
+
+    let validator = Validator::new(2);
+    let datasets = [
+        ("clean", vec![vec![1.0, 2.0], vec![3.0, 4.0]]),
+        ("wrong width", vec![vec![1.0, 2.0], vec![3.0]]),
+        ("NaN value", vec![vec![1.0, 2.0], vec![f64::NAN, 4.0]]),
+        ("infinite value", vec![vec![f64::INFINITY, 2.0]]),
+    ];
+    for (label, rows) in datasets {
+        match validator.process(rows) {
+            Ok(rows) => println!("   {:<15} ✅ {} rows accepted", label, rows.len()),
+            Err(e) => println!("   {:<15} ❌ {}", label, e),
+        }
+    }
+    println!();
 }
 
 /// Demonstrate data flow
@@ -282,13 +1205,14 @@ fn eu_compliance() {
 
     println!("   Article 10 (Data Governance):");
     println!("   ├─ Data generation deterministic");
+    println!("   ├─ Rows validated for width and finite values");
     println!("   ├─ Preprocessing transparent");
     println!("   └─ All transformations logged");
     println!();
 
     println!("   Article 13 (Transparency):");
     println!("   ├─ Pipeline stages visible");
-    println!("   ├─ Data flow explicit");
+    println!("   ├─ Data flow explicit (lineage record per run)");
     println!("   └─ Model outputs reproducible");
     println!();
 
@@ -307,15 +1231,39 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
-    pipeline_demo();
+    pipeline_demo()?;
+    println!("{}", "─".repeat(70));
+    println!();
+
+    stage_demo()?;
+    println!("{}", "─".repeat(70));
+    println!();
+
+    determinism_demo()?;
+    println!("{}", "─".repeat(70));
+    println!();
+
+    lineage_demo()?;
+    println!("{}", "─".repeat(70));
+    println!();
+
+    caching_demo()?;
+    println!("{}", "─".repeat(70));
+    println!();
+
+    config_demo()?;
+    println!("{}", "─".repeat(70));
+    println!();
+
+    branching_demo()?;
     println!("{}", "─".repeat(70));
     println!();
 
-    stage_demo();
+    standardization_demo()?;
     println!("{}", "─".repeat(70));
     println!();
 
-    determinism_demo();
+    validation_demo();
     println!("{}", "─".repeat(70));
     println!();
 
@@ -340,11 +1288,13 @@ fn main() -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
 
     #[test]
     fn test_data_loader() {
         let loader = DataLoader::new();
-        let data = loader.process(());
+        let data = loader.process(()).expect("loader always succeeds");
         assert_eq!(data.len(), 100);
         assert_eq!(data[0].len(), 2);
     }
@@ -353,17 +1303,128 @@ mod tests {
     fn test_preprocessor() {
         let preprocessor = Preprocessor::new(2.0);
         let input = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
-        let output = preprocessor.process(input);
+        let output = preprocessor.process(input).expect("rows scale");
 
         assert_eq!(output[0], vec![2.0, 4.0]);
         assert_eq!(output[1], vec![6.0, 8.0]);
     }
 
+    #[test]
+    fn test_fan_out_merges_branches_in_order() {
+        let rows = vec![vec![1.0, 4.0], vec![3.0, 2.0]];
+        let fan_out = FanOut::new("Both")
+            .branch(FeatureExtractor::new())
+            .branch(StatsCollector::new());
+
+        let merged = fan_out.process(rows.clone()).expect("branches agree");
+        // Means first, then [min, max] per feature
+        assert_eq!(merged, vec![2.0, 3.0, 1.0, 3.0, 2.0, 4.0]);
+        assert_eq!(fan_out.process(rows.clone()), Ok(merged));
+
+        let swapped = FanOut::new("Swapped")
+            .branch(StatsCollector::new())
+            .branch(FeatureExtractor::new());
+        assert_eq!(
+            swapped.process(rows),
+            Ok(vec![1.0, 3.0, 2.0, 4.0, 2.0, 3.0])
+        );
+    }
+
+    #[test]
+    fn test_stats_collector_rejects_ragged_rows() {
+        let ragged = vec![vec![1.0, 4.0], vec![3.0]];
+        let fan_out = FanOut::new("Stats").branch(StatsCollector::new());
+        let err = fan_out.process(ragged.clone()).expect_err("ragged rows");
+        assert!(err.message.contains("row 1 has 1 features, expected 2"));
+
+        // Every branch rejects them, whichever runs first
+        let mixed = FanOut::new("Mixed")
+            .branch(FeatureExtractor::new())
+            .branch(StatsCollector::new());
+        let err = mixed.process(ragged).expect_err("ragged rows");
+        assert!(err.message.contains("row 1 has 1 features, expected 2"));
+
+        // Wider rows are rejected, not truncated
+        let wide = vec![vec![1.0], vec![3.0, 9.0]];
+        let err = FeatureExtractor::new()
+            .process(wide)
+            .expect_err("wider row");
+        assert!(err.message.contains("row 1 has 2 features, expected 1"));
+    }
+
+    #[test]
+    fn test_fan_out_rejects_non_feature_branch() {
+        let fan_out = FanOut::new("Bad").branch(Preprocessor::new(1.0));
+        let err = fan_out
+            .process(vec![vec![1.0]])
+            .expect_err("rows cannot be merged");
+        assert_eq!(err.to_string(), "Bad: branch Preprocessor produced rows");
+    }
+
+    #[test]
+    fn test_standardizer_zero_mean_unit_std() {
+        let rows = vec![
+            vec![1.0, 10.0, 5.0],
+            vec![2.0, 20.0, 5.0],
+            vec![3.0, 40.0, 5.0],
+            vec![6.0, 30.0, 5.0],
+        ];
+        let standardizer = Standardizer::fit(&rows).expect("non-empty rows");
+        let out = standardizer.transform(rows).expect("same width");
+
+        let n = out.len() as f64;
+        for f in 0..2 {
+            let mean = out.iter().map(|row| row[f]).sum::<f64>() / n;
+            let var = out.iter().map(|row| (row[f] - mean).powi(2)).sum::<f64>() / n;
+            assert!(mean.abs() < 1e-12, "feature {} mean {}", f, mean);
+            assert!((var.sqrt() - 1.0).abs() < 1e-12, "feature {} std", f);
+        }
+        // A constant feature is centred, not divided by zero
+        assert!(out.iter().all(|row| row[2] == 0.0));
+
+        // New data reuses the fitted statistics
+        let fresh = standardizer
+            .transform(vec![vec![4.0, 25.0, 5.0]])
+            .expect("same width");
+        assert!(fresh[0][0] > 0.0 && fresh[0][1] == 0.0);
+        assert!(standardizer.transform(vec![vec![1.0]]).is_err());
+        assert!(Standardizer::fit(&[]).is_err());
+    }
+
+    #[test]
+    fn test_validator_rejects_bad_rows() {
+        let validator = Validator::new(2);
+        let clean = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+        assert_eq!(validator.process(clean.clone()), Ok(clean));
+
+        let err = validator
+            .process(vec![vec![1.0, 2.0], vec![3.0]])
+            .expect_err("short row");
+        assert_eq!(
+            err.to_string(),
+            "Validator: row 1 has 1 features, expected 2"
+        );
+
+        let err = validator
+            .process(vec![vec![1.0, 2.0], vec![3.0, f64::NAN]])
+            .expect_err("NaN value");
+        assert_eq!(err.to_string(), "Validator: row 1 feature 1 is NaN");
+
+        // The error stops a pipeline before training
+        let mut pipeline = Pipeline::empty()
+            .with_stage(Validator::new(2))
+            .with_stage(FeatureExtractor::new());
+        let err = pipeline
+            .execute(PipelineValue::Rows(vec![vec![f64::INFINITY, 0.0]]))
+            .expect_err("infinite value");
+        assert_eq!(err.stage, "Validator");
+    }
+
     #[test]
     fn test_feature_extractor() {
         let extractor = FeatureExtractor::new();
         let input = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
-        let features = extractor.process(input);
+        let features = extractor.process(input).expect("non-empty rows");
 
         assert!((features[0] - 2.0).abs() < 1e-10);
         assert!((features[1] - 3.0).abs() < 1e-10);
@@ -373,7 +1434,7 @@ mod tests {
     fn test_model_trainer() {
         let trainer = ModelTrainer::new(0.1);
         let features = vec![10.0, 20.0];
-        let model = trainer.process(features);
+        let model = trainer.process(features).expect("features train");
 
         assert!((model.weights[0] - 1.0).abs() < 1e-10);
         assert!((model.weights[1] - 2.0).abs() < 1e-10);
@@ -382,19 +1443,253 @@ mod tests {
     #[test]
     fn test_pipeline_execution() {
         let mut pipeline = Pipeline::new();
-        let model = pipeline.run();
+        let (model, _) = pipeline.run().expect("standard pipeline runs");
 
         assert_eq!(pipeline.stages_executed().len(), 4);
         assert!(!model.weights.is_empty());
     }
 
+    #[test]
+    fn test_profile_has_one_event_per_stage() {
+        let mut pipeline = Pipeline::new();
+        let (_, profile) = pipeline.run().expect("standard pipeline runs");
+
+        assert_eq!(profile.events.len(), pipeline.stages_executed().len());
+        let names: Vec<&str> = profile.events.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, pipeline.stages_executed());
+    }
+
+    #[test]
+    fn test_profile_covers_only_the_latest_run() {
+        let mut pipeline = Pipeline::new();
+        pipeline.run().expect("first run");
+        let (_, profile) = pipeline.run().expect("second run");
+
+        let names: Vec<&str> = profile.events.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "DataLoader",
+                "Preprocessor",
+                "FeatureExtractor",
+                "ModelTrainer"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dynamic_pipeline_matches_direct_stages() {
+        let mut pipeline = Pipeline::empty()
+            .with_stage(DataLoader::new())
+            .with_stage(Preprocessor::new(0.01))
+            .with_stage(FeatureExtractor::new());
+        let value = pipeline
+            .execute(PipelineValue::Unit)
+            .expect("stages line up");
+
+        let rows = DataLoader::new().process(()).expect("loader");
+        let scaled = Preprocessor::new(0.01).process(rows).expect("preprocess");
+        let direct = FeatureExtractor::new().process(scaled).expect("extract");
+        assert_eq!(value, PipelineValue::Features(direct.clone()));
+        assert_eq!(
+            pipeline.stages_executed(),
+            &["DataLoader", "Preprocessor", "FeatureExtractor"]
+        );
+
+        // Finishing the runtime pipeline reproduces the standard run
+        let model = ModelTrainer::new(0.1).process(direct).expect("train");
+        let (standard, _) = Pipeline::new().run().expect("standard run");
+        assert_eq!(model, standard);
+    }
+
+    #[test]
+    fn test_dynamic_pipeline_rejects_mismatched_stages() {
+        let mut pipeline = Pipeline::empty()
+            .with_stage(DataLoader::new())
+            .with_stage(ModelTrainer::new(0.1));
+
+        let err = pipeline
+            .execute(PipelineValue::Unit)
+            .expect_err("trainer cannot take rows");
+        assert_eq!(err.stage, "ModelTrainer");
+        assert_eq!(err.to_string(), "ModelTrainer: expected features, got rows");
+    }
+
+    #[test]
+    fn test_failing_stage_stops_pipeline() {
+        let mut pipeline = Pipeline::empty()
+            .with_stage(Preprocessor::new(0.1))
+            .with_stage(FeatureExtractor::new())
+            .with_stage(ModelTrainer::new(0.1));
+
+        let err = pipeline
+            .execute(PipelineValue::Rows(Vec::new()))
+            .expect_err("no rows means no features");
+        assert_eq!(err.stage, "FeatureExtractor");
+        assert_eq!(
+            pipeline.stages_executed(),
+            &["Preprocessor", "FeatureExtractor"],
+            "ModelTrainer must not run after a failure"
+        );
+    }
+
+    /// Pure pass-through stage that counts how often it really runs
+    struct CountingStage {
+        calls: Rc<Cell<usize>>,
+    }
+
+    impl Stage for CountingStage {
+        type Input = Vec<Vec<f64>>;
+        type Output = Vec<Vec<f64>>;
+
+        fn process(&self, input: Self::Input) -> Result<Self::Output, PipelineError> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(input)
+        }
+
+        fn name(&self) -> &str {
+            "CountingStage"
+        }
+
+        fn is_pure(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_cache_skips_pure_stage_on_identical_input() {
+        let calls = Rc::new(Cell::new(0));
+        let mut pipeline = Pipeline::empty()
+            .with_stage(DataLoader::new())
+            .with_stage(CountingStage {
+                calls: Rc::clone(&calls),
+            })
+            .with_stage(FeatureExtractor::new())
+            .with_cache();
+
+        let first = pipeline.execute(PipelineValue::Unit).expect("first run");
+        assert_eq!(calls.get(), 1);
+        let second = pipeline.execute(PipelineValue::Unit).expect("second run");
+        assert_eq!(calls.get(), 1, "expensive stage must not rerun");
+        assert_eq!(first, second);
+        // CountingStage and FeatureExtractor hit; DataLoader is not pure
+        assert_eq!(pipeline.cache_hits(), 2);
+
+        // A different input is a cache miss
+        let rows = PipelineValue::Rows(vec![vec![1.0, 2.0]]);
+        let mut tail = Pipeline::empty()
+            .with_stage(CountingStage {
+                calls: Rc::clone(&calls),
+            })
+            .with_cache();
+        tail.execute(rows.clone()).expect("rows pass through");
+        tail.execute(PipelineValue::Rows(vec![vec![3.0, 4.0]]))
+            .expect("rows pass through");
+        assert_eq!(calls.get(), 3);
+        tail.execute(rows).expect("rows pass through");
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn test_cache_hash_collision_is_a_miss() {
+        let calls = Rc::new(Cell::new(0));
+        let mut pipeline = Pipeline::empty()
+            .with_stage(CountingStage {
+                calls: Rc::clone(&calls),
+            })
+            .with_cache();
+
+        // Plant another input's output under this input's hash
+        let rows = PipelineValue::Rows(vec![vec![1.0, 2.0]]);
+        let key = (0, rows.content_hash());
+        pipeline.cache.as_mut().expect("caching on").insert(
+            key,
+            vec![CacheEntry {
+                input: PipelineValue::Rows(vec![vec![9.0]]),
+                output: PipelineValue::Rows(vec![vec![9.0]]),
+            }],
+        );
+
+        let output = pipeline.execute(rows.clone()).expect("rows pass through");
+        assert_eq!(output, rows);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(pipeline.cache_hits(), 0);
+    }
+
+    #[test]
+    fn test_lineage_records_stage_shapes() {
+        let mut pipeline = Pipeline::new();
+        pipeline.run().expect("standard pipeline runs");
+        let lineage = pipeline.lineage();
+
+        let names: Vec<&str> = lineage.stages.iter().map(|s| s.stage.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "DataLoader",
+                "Preprocessor",
+                "FeatureExtractor",
+                "ModelTrainer"
+            ]
+        );
+        let shapes: Vec<(Shape, Shape)> =
+            lineage.stages.iter().map(|s| (s.input, s.output)).collect();
+        assert_eq!(
+            shapes,
+            [
+                (Shape::Unit, Shape::Rows { rows: 100, cols: 2 }),
+                (
+                    Shape::Rows { rows: 100, cols: 2 },
+                    Shape::Rows { rows: 100, cols: 2 }
+                ),
+                (
+                    Shape::Rows { rows: 100, cols: 2 },
+                    Shape::Features { len: 2 }
+                ),
+                (Shape::Features { len: 2 }, Shape::Model { weights: 2 }),
+            ]
+        );
+        assert_eq!(lineage.stages[1].params.get("scale"), Some(&0.01));
+        assert_eq!(lineage.stages[3].params.get("learning_rate"), Some(&0.1));
+
+        let json = lineage.to_json().expect("lineage serializes");
+        assert!(json.contains("\"stage\": \"ModelTrainer\""));
+        assert!(json.contains("\"learning_rate\": 0.1"));
+
+        // A second run replaces, rather than extends, the record
+        pipeline.run().expect("second run");
+        assert_eq!(pipeline.lineage().stages.len(), 4);
+    }
+
+    #[test]
+    fn test_config_round_trip_rebuilds_pipeline() {
+        let config = PipelineConfig {
+            scale: 0.05,
+            learning_rate: 0.2,
+        };
+        let json = serde_json::to_string(&config).expect("config serializes");
+        let restored: PipelineConfig = serde_json::from_str(&json).expect("config parses");
+        assert_eq!(restored, config);
+
+        let (original, _) = Pipeline::from_config(&config).run().expect("original run");
+        let (rebuilt, _) = Pipeline::from_config(&restored).run().expect("rebuilt run");
+        assert_eq!(original.bias.to_bits(), rebuilt.bias.to_bits());
+
+        // The default config is the standard pipeline
+        let (standard, _) = Pipeline::new().run().expect("standard run");
+        let (default, _) = Pipeline::from_config(&PipelineConfig::default())
+            .run()
+            .expect("default run");
+        assert_eq!(standard, default);
+    }
+
     #[test]
     fn test_pipeline_determinism() {
         let mut results = Vec::new();
 
         for _ in 0..5 {
             let mut pipeline = Pipeline::new();
-            let model = pipeline.run();
+            let (model, _) = pipeline.run().expect("standard pipeline runs");
             results.push(model.bias);
         }
 
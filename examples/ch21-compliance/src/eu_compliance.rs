@@ -7,25 +7,40 @@
 ///
 /// **VALIDATION:** `make run-ch21`
 use anyhow::Result;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Compliance check result
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct ComplianceCheck {
     article: String,
     requirement: String,
     status: ComplianceStatus,
     evidence: String,
+    weight: f64,
+    remediation: Option<String>,
+    severity: Severity,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 enum ComplianceStatus {
     Compliant,
     NonCompliant,
     Partial,
 }
 
+/// How serious a finding is if the check does not pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+enum Severity {
+    Info,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
 impl ComplianceCheck {
     fn new(article: &str, requirement: &str, status: ComplianceStatus, evidence: &str) -> Self {
         Self {
@@ -33,18 +48,100 @@ impl ComplianceCheck {
             requirement: requirement.to_string(),
             status,
             evidence: evidence.to_string(),
+            weight: 1.0,
+            remediation: None,
+            severity: Severity::Medium,
+        }
+    }
+
+    fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    fn with_remediation(mut self, remediation: &str) -> Self {
+        self.remediation = Some(remediation.to_string());
+        self
+    }
+}
+
+impl ComplianceStatus {
+    fn symbol(self) -> &'static str {
+        match self {
+            ComplianceStatus::Compliant => "✅",
+            ComplianceStatus::NonCompliant => "❌",
+            ComplianceStatus::Partial => "⚠️",
+        }
+    }
+
+    /// Fraction of a check's weight credited towards the score
+    fn credit(self) -> f64 {
+        match self {
+            ComplianceStatus::Compliant => 1.0,
+            ComplianceStatus::Partial => 0.5,
+            ComplianceStatus::NonCompliant => 0.0,
         }
     }
 }
 
+/// Status counts for a compliance report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ComplianceSummary {
+    compliant: usize,
+    non_compliant: usize,
+    partial: usize,
+}
+
+/// Serializable compliance evidence: every check plus the summary counts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ComplianceReport {
+    checks: Vec<ComplianceCheck>,
+    summary: ComplianceSummary,
+    compliant: bool,
+}
+
+/// A requirement whose status changed between two audits
+#[derive(Debug, Clone, PartialEq)]
+struct ComplianceChange {
+    article: String,
+    requirement: String,
+    before: Option<ComplianceStatus>,
+    after: ComplianceStatus,
+}
+
+/// Changes between two audits, each list ordered by article then requirement
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ComplianceDiff {
+    regressions: Vec<ComplianceChange>,
+    improvements: Vec<ComplianceChange>,
+    added: Vec<ComplianceChange>,
+}
+
+/// Downstream check producer, run as part of `run_all_checks`
+type CheckFn = Box<dyn Fn() -> ComplianceCheck>;
+
 /// Compliance auditor
 struct ComplianceAuditor {
     checks: Vec<ComplianceCheck>,
+    custom_checks: Vec<(String, CheckFn)>,
 }
 
 impl ComplianceAuditor {
     fn new() -> Self {
-        Self { checks: Vec::new() }
+        Self {
+            checks: Vec::new(),
+            custom_checks: Vec::new(),
+        }
+    }
+
+    /// Register a custom check under `article`, evaluated by `run_all_checks`
+    fn register(&mut self, article: &str, check_fn: CheckFn) {
+        self.custom_checks.push((article.to_string(), check_fn));
     }
 
     fn add_check(&mut self, check: ComplianceCheck) {
@@ -98,12 +195,15 @@ impl ComplianceAuditor {
     }
 
     fn run_article_15_checks(&mut self) {
-        self.add_check(ComplianceCheck::new(
-            "Article 15",
-            "No undefined behavior",
-            ComplianceStatus::Compliant,
-            "Rust memory safety guarantees",
-        ));
+        self.add_check(
+            ComplianceCheck::new(
+                "Article 15",
+                "No undefined behavior",
+                ComplianceStatus::Compliant,
+                "Rust memory safety guarantees",
+            )
+            .with_weight(2.0),
+        );
 
         self.add_check(ComplianceCheck::new(
             "Article 15",
@@ -124,6 +224,33 @@ impl ComplianceAuditor {
         self.run_article_10_checks();
         self.run_article_13_checks();
         self.run_article_15_checks();
+        self.run_custom_checks();
+    }
+
+    fn run_custom_checks(&mut self) {
+        let checks: Vec<_> = self
+            .custom_checks
+            .iter()
+            .map(|(article, check_fn)| ComplianceCheck {
+                article: article.clone(),
+                ..check_fn()
+            })
+            .collect();
+        for check in checks {
+            self.add_check(check);
+        }
+    }
+
+    /// Checks grouped by article, in the order they were run
+    fn checks_by_article(&self) -> HashMap<String, Vec<&ComplianceCheck>> {
+        let mut grouped: HashMap<String, Vec<&ComplianceCheck>> = HashMap::new();
+        for check in &self.checks {
+            grouped
+                .entry(check.article.clone())
+                .or_default()
+                .push(check);
+        }
+        grouped
     }
 
     fn summary(&self) -> HashMap<ComplianceStatus, usize> {
@@ -139,9 +266,141 @@ impl ComplianceAuditor {
             .iter()
             .all(|c| c.status == ComplianceStatus::Compliant)
     }
+
+    /// Weighted compliance score from 0 to 100 (an empty audit scores 100)
+    fn score(&self) -> f64 {
+        let total: f64 = self.checks.iter().map(|c| c.weight).sum();
+        if total <= 0.0 {
+            return 100.0;
+        }
+        let earned: f64 = self
+            .checks
+            .iter()
+            .map(|c| c.weight * c.status.credit())
+            .sum();
+        100.0 * earned / total
+    }
+
+    /// Most severe finding among checks that are not fully compliant
+    fn highest_severity_failure(&self) -> Option<Severity> {
+        self.checks
+            .iter()
+            .filter(|c| c.status != ComplianceStatus::Compliant)
+            .map(|c| c.severity)
+            .max()
+    }
+
+    /// Deployment gate: true when no finding reaches `blocking` severity
+    fn passes_severity_gate(&self, blocking: Severity) -> bool {
+        self.highest_severity_failure()
+            .is_none_or(|severity| severity < blocking)
+    }
+
+    /// Compare against an earlier audit: regressions were Compliant and no longer
+    /// are, improvements became Compliant, added requirements did not exist before
+    fn diff(&self, previous: &ComplianceAuditor) -> ComplianceDiff {
+        let index = |auditor: &ComplianceAuditor| -> BTreeMap<(String, String), ComplianceStatus> {
+            auditor
+                .checks
+                .iter()
+                .map(|c| ((c.article.clone(), c.requirement.clone()), c.status))
+                .collect()
+        };
+        let before = index(previous);
+
+        let mut diff = ComplianceDiff::default();
+        for ((article, requirement), after) in index(self) {
+            let prior = before.get(&(article.clone(), requirement.clone())).copied();
+            let change = ComplianceChange {
+                article,
+                requirement,
+                before: prior,
+                after,
+            };
+            match prior {
+                None => diff.added.push(change),
+                Some(ComplianceStatus::Compliant) if after != ComplianceStatus::Compliant => {
+                    diff.regressions.push(change);
+                }
+                Some(status)
+                    if status != ComplianceStatus::Compliant
+                        && after == ComplianceStatus::Compliant =>
+                {
+                    diff.improvements.push(change);
+                }
+                Some(_) => {}
+            }
+        }
+        diff
+    }
+
+    /// (requirement, suggested fix) for every check that is not fully compliant
+    fn remediation_report(&self) -> Vec<(String, String)> {
+        self.checks
+            .iter()
+            .filter(|c| c.status != ComplianceStatus::Compliant)
+            .map(|c| {
+                let fix = c
+                    .remediation
+                    .clone()
+                    .unwrap_or_else(|| "No remediation documented".to_string());
+                (c.requirement.clone(), fix)
+            })
+            .collect()
+    }
+
+    fn report(&self) -> ComplianceReport {
+        let summary = self.summary();
+        let count = |status| summary.get(&status).copied().unwrap_or(0);
+        ComplianceReport {
+            checks: self.checks.clone(),
+            summary: ComplianceSummary {
+                compliant: count(ComplianceStatus::Compliant),
+                non_compliant: count(ComplianceStatus::NonCompliant),
+                partial: count(ComplianceStatus::Partial),
+            },
+            compliant: self.is_compliant(),
+        }
+    }
+
+    /// Markdown table of every check plus a summary and overall verdict
+    fn to_markdown(&self) -> String {
+        let cell = |text: &str| text.replace('|', "\\|");
+        let mut md = String::from("| Article | Requirement | Status | Evidence |\n");
+        md.push_str("|---------|-------------|--------|----------|\n");
+        for check in &self.checks {
+            md.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                cell(&check.article),
+                cell(&check.requirement),
+                check.status.symbol(),
+                cell(&check.evidence)
+            ));
+        }
+
+        let summary = self.report().summary;
+        md.push_str(&format!(
+            "\n**Summary:** {} compliant, {} partial, {} non-compliant (score {:.1}/100)\n",
+            summary.compliant,
+            summary.partial,
+            summary.non_compliant,
+            self.score()
+        ));
+        let verdict = if self.is_compliant() { "PASS" } else { "FAIL" };
+        md.push_str(&format!("\n**Overall:** {}\n", verdict));
+        md
+    }
+
+    /// Full report as pretty-printed JSON, suitable for storing as an artifact
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.report()).expect("compliance report serializes")
+    }
 }
 
-/// Audit trail entry
+/// Hash that the first entry of an audit chain links to
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Audit trail entry, linked to its predecessor by a SHA-256 hash
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 struct AuditEntry {
@@ -149,35 +408,127 @@ struct AuditEntry {
     action: String,
     actor: String,
     details: String,
+    prev_hash: String,
+    hash: String,
 }
 
 impl AuditEntry {
-    fn new(timestamp: u64, action: &str, actor: &str, details: &str) -> Self {
-        Self {
+    fn new(timestamp: u64, action: &str, actor: &str, details: &str, prev_hash: &str) -> Self {
+        let mut entry = Self {
             timestamp,
             action: action.to_string(),
             actor: actor.to_string(),
             details: details.to_string(),
+            prev_hash: prev_hash.to_string(),
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+        entry
+    }
+
+    /// SHA-256 over the previous hash and every field (length-prefixed)
+    fn compute_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.prev_hash.as_bytes());
+        hasher.update(self.timestamp.to_le_bytes());
+        for field in [&self.action, &self.actor, &self.details] {
+            hasher.update((field.len() as u64).to_le_bytes());
+            hasher.update(field.as_bytes());
         }
+        hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
     }
 }
 
+/// Wall-clock time in milliseconds since the Unix epoch
+fn system_clock_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
 /// Audit log
 struct AuditLog {
     entries: Vec<AuditEntry>,
+    clock: Box<dyn Fn() -> u64>,
 }
 
 impl AuditLog {
     fn new() -> Self {
+        Self::with_clock(system_clock_ms)
+    }
+
+    /// Use a custom timestamp source, e.g. a fixed clock for deterministic tests
+    fn with_clock(clock: impl Fn() -> u64 + 'static) -> Self {
         Self {
             entries: Vec::new(),
+            clock: Box::new(clock),
         }
     }
 
     fn log(&mut self, action: &str, actor: &str, details: &str) {
-        let timestamp = self.entries.len() as u64; // Simplified timestamp
+        let timestamp = (self.clock)();
+        let prev_hash = self
+            .entries
+            .last()
+            .map_or(GENESIS_HASH, |e| e.hash.as_str())
+            .to_string();
+        self.entries.push(AuditEntry::new(
+            timestamp, action, actor, details, &prev_hash,
+        ));
+    }
+
+    /// Entries matching every given constraint; `time_range` is inclusive
+    fn filter(
+        &self,
+        actor: Option<&str>,
+        action: Option<&str>,
+        time_range: Option<(u64, u64)>,
+    ) -> Vec<&AuditEntry> {
         self.entries
-            .push(AuditEntry::new(timestamp, action, actor, details));
+            .iter()
+            .filter(|e| actor.is_none_or(|a| e.actor == a))
+            .filter(|e| action.is_none_or(|a| e.action == a))
+            .filter(|e| time_range.is_none_or(|(from, to)| (from..=to).contains(&e.timestamp)))
+            .collect()
+    }
+
+    /// Entries as `timestamp,actor,action,details` CSV (RFC 4180 quoting)
+    fn to_csv(&self) -> String {
+        let escape = |field: &str| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        };
+        let mut csv = String::from("timestamp,actor,action,details\n");
+        for e in &self.entries {
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                e.timestamp,
+                escape(&e.actor),
+                escape(&e.action),
+                escape(&e.details)
+            ));
+        }
+        csv
+    }
+
+    /// Recompute the hash chain; false if any entry was modified, removed or reordered
+    fn verify(&self) -> bool {
+        let mut prev_hash = GENESIS_HASH;
+        for entry in &self.entries {
+            if entry.prev_hash != prev_hash || entry.hash != entry.compute_hash() {
+                return false;
+            }
+            prev_hash = &entry.hash;
+        }
+        true
     }
 
     fn len(&self) -> usize {
@@ -191,6 +542,17 @@ fn audit_demo() {
     println!();
 
     let mut auditor = ComplianceAuditor::new();
+    auditor.register(
+        "Article 12",
+        Box::new(|| {
+            ComplianceCheck::new(
+                "Article 12",
+                "Events automatically logged",
+                ComplianceStatus::Compliant,
+                "AuditLog records every pipeline action",
+            )
+        }),
+    );
     auditor.run_all_checks();
 
     println!(
@@ -200,14 +562,11 @@ fn audit_demo() {
     println!("   ─────────────┼────────────────────────────────┼───────────");
 
     for check in &auditor.checks {
-        let status = match check.status {
-            ComplianceStatus::Compliant => "✅",
-            ComplianceStatus::NonCompliant => "❌",
-            ComplianceStatus::Partial => "⚠️",
-        };
         println!(
             "   {:>12} │ {:>30} │ {:>10}",
-            check.article, check.requirement, status
+            check.article,
+            check.requirement,
+            check.status.symbol()
         );
     }
 
@@ -226,12 +585,125 @@ fn audit_demo() {
         "   - Partial: {}",
         summary.get(&ComplianceStatus::Partial).unwrap_or(&0)
     );
+    println!("   - Score: {:.1}/100", auditor.score());
+    let grouped = auditor.checks_by_article();
+    let mut articles: Vec<_> = grouped.keys().collect();
+    articles.sort();
+    for article in articles {
+        println!("   - {}: {} checks", article, grouped[article].len());
+    }
     println!();
 
     if auditor.is_compliant() {
         println!("   ✅ FULLY COMPLIANT with EU AI Act");
     } else {
         println!("   ❌ NOT COMPLIANT - remediation required");
+        for (requirement, fix) in auditor.remediation_report() {
+            println!("   - {}: {}", requirement, fix);
+        }
+    }
+    println!();
+    println!(
+        "   JSON evidence artifact: {} bytes",
+        auditor.to_json().len()
+    );
+    println!(
+        "   Markdown report: {} lines",
+        auditor.to_markdown().lines().count()
+    );
+    println!();
+}
+
+/// Demonstrate remediation guidance for a failing audit
+fn remediation_demo() {
+    println!("🛠️  Remediation Guidance");
+    println!();
+
+    let mut auditor = ComplianceAuditor::new();
+    auditor.add_check(
+        ComplianceCheck::new(
+            "Article 12",
+            "Logs retained for 180 days",
+            ComplianceStatus::NonCompliant,
+            "Logs rotated after 30 days",
+        )
+        .with_remediation("Increase log retention to 180 days")
+        .with_severity(Severity::High),
+    );
+    auditor.add_check(
+        ComplianceCheck::new(
+            "Article 14",
+            "Human override available",
+            ComplianceStatus::Partial,
+            "Override exists for batch jobs only",
+        )
+        .with_remediation("Expose override in the online inference API")
+        .with_severity(Severity::Low),
+    );
+
+    for (requirement, fix) in auditor.remediation_report() {
+        println!("   {} → {}", requirement, fix);
+    }
+    println!();
+    println!(
+        "   Highest severity finding: {:?}",
+        auditor.highest_severity_failure()
+    );
+    println!(
+        "   Deployment gate (no High/Critical): {}",
+        if auditor.passes_severity_gate(Severity::High) {
+            "✅ PASS"
+        } else {
+            "❌ BLOCKED"
+        }
+    );
+    println!();
+}
+
+/// Demonstrate comparing two audits over time
+fn audit_diff_demo() {
+    println!("📈 Compliance Over Time");
+    println!();
+
+    let mut previous = ComplianceAuditor::new();
+    previous.run_all_checks();
+    previous.add_check(ComplianceCheck::new(
+        "Article 12",
+        "Logs retained for 180 days",
+        ComplianceStatus::NonCompliant,
+        "Logs rotated after 30 days",
+    ));
+
+    let mut current = ComplianceAuditor::new();
+    current.run_all_checks();
+    current.add_check(ComplianceCheck::new(
+        "Article 12",
+        "Logs retained for 180 days",
+        ComplianceStatus::Compliant,
+        "Retention raised to 180 days",
+    ));
+    current.add_check(ComplianceCheck::new(
+        "Article 14",
+        "Human override available",
+        ComplianceStatus::Partial,
+        "Override exists for batch jobs only",
+    ));
+
+    let diff = current.diff(&previous);
+    for (label, changes) in [
+        ("Regressed", &diff.regressions),
+        ("Improved", &diff.improvements),
+        ("New", &diff.added),
+    ] {
+        for change in changes {
+            println!(
+                "   {:>9}: {} - {} ({})",
+                label,
+                change.article,
+                change.requirement,
+                change.after.symbol()
+            );
+        }
     }
     println!();
 }
@@ -254,6 +726,14 @@ fn audit_trail_demo() {
     log.log("model_deploy", "admin", "Model deployed to production");
 
     println!("   Audit entries: {}", log.len());
+    println!(
+        "   Hash chain: {}",
+        if log.verify() {
+            "✅ intact"
+        } else {
+            "❌ tampered"
+        }
+    );
     println!();
 
     for entry in &log.entries {
@@ -263,6 +743,12 @@ fn audit_trail_demo() {
         );
     }
     println!();
+    println!(
+        "   Actions by admin: {}",
+        log.filter(Some("admin"), None, None).len()
+    );
+    println!("   CSV export: {} rows", log.to_csv().lines().count() - 1);
+    println!();
 }
 
 /// Demonstrate article requirements
@@ -289,18 +775,61 @@ fn requirements_demo() {
     println!();
 }
 
+/// A stack component's contribution to an EU AI Act article
+#[derive(Debug, Clone, PartialEq)]
+struct StackMapping {
+    component: String,
+    article: String,
+    feature: String,
+}
+
+/// Which stack components cover which articles
+struct StackComplianceMap {
+    mappings: Vec<StackMapping>,
+}
+
+impl StackComplianceMap {
+    fn new() -> Self {
+        Self {
+            mappings: Vec::new(),
+        }
+    }
+
+    /// Mapping for the components of the Sovereign AI Stack
+    fn sovereign_stack() -> Self {
+        let mut map = Self::new();
+        map.add("trueno", "Article 15", "Deterministic tensor ops");
+        map.add("aprender", "Article 10", "Reproducible training");
+        map.add("realizar", "Article 13", "Explainable inference");
+        map.add("batuta", "Article 10", "Auditable workflows");
+        map.add("renacer", "Article 15", "Performance monitoring");
+        map
+    }
+
+    fn add(&mut self, component: &str, article: &str, feature: &str) {
+        self.mappings.push(StackMapping {
+            component: component.to_string(),
+            article: article.to_string(),
+            feature: feature.to_string(),
+        });
+    }
+
+    /// Required articles that no component covers, in the order given
+    fn coverage_gaps<'a>(&self, required_articles: &[&'a str]) -> Vec<&'a str> {
+        required_articles
+            .iter()
+            .copied()
+            .filter(|article| !self.mappings.iter().any(|m| m.article == *article))
+            .collect()
+    }
+}
+
 /// Demonstrate sovereign stack mapping
 fn stack_mapping_demo() {
     println!("🗺️  Sovereign Stack Compliance Mapping");
     println!();
 
-    let mappings = vec![
-        ("trueno", "Article 15", "Deterministic tensor ops"),
-        ("aprender", "Article 10", "Reproducible training"),
-        ("realizar", "Article 13", "Explainable inference"),
-        ("batuta", "Article 10", "Auditable workflows"),
-        ("renacer", "Article 15", "Performance monitoring"),
-    ];
+    let map = StackComplianceMap::sovereign_stack();
 
     println!(
         "   {:>12} │ {:>12} │ {:>30}",
@@ -308,8 +837,19 @@ fn stack_mapping_demo() {
     );
     println!("   ─────────────┼──────────────┼───────────────────────────────");
 
-    for (component, article, feature) in mappings {
-        println!("   {:>12} │ {:>12} │ {:>30}", component, article, feature);
+    for m in &map.mappings {
+        println!(
+            "   {:>12} │ {:>12} │ {:>30}",
+            m.component, m.article, m.feature
+        );
+    }
+    println!();
+
+    let gaps = map.coverage_gaps(&["Article 10", "Article 13", "Article 15"]);
+    if gaps.is_empty() {
+        println!("   ✅ Every required article is covered by a component");
+    } else {
+        println!("   ❌ Uncovered articles: {}", gaps.join(", "));
     }
     println!();
 }
@@ -345,6 +885,14 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
+    remediation_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
+    audit_diff_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
     audit_trail_demo();
     println!("{}", "─".repeat(70));
     println!();
@@ -400,6 +948,139 @@ mod tests {
         assert_eq!(log.len(), 2);
     }
 
+    #[test]
+    fn test_audit_log_uses_injected_clock() {
+        let now = std::cell::Cell::new(1_000);
+        let mut log = AuditLog::with_clock(move || {
+            let t = now.get();
+            now.set(t + 250);
+            t
+        });
+        log.log("a", "system", "first");
+        log.log("b", "system", "second");
+        log.log("c", "admin", "third");
+
+        let timestamps: Vec<_> = log.entries.iter().map(|e| e.timestamp).collect();
+        assert_eq!(timestamps, vec![1_000, 1_250, 1_500]);
+    }
+
+    #[test]
+    fn test_audit_log_default_clock_is_wall_time() {
+        let mut log = AuditLog::new();
+        log.log("action", "actor", "details");
+        // Any time after 2020-01-01 in milliseconds
+        assert!(log.entries[0].timestamp > 1_577_836_800_000);
+    }
+
+    #[test]
+    fn test_audit_log_hash_chain_verifies() {
+        let mut log = AuditLog::with_clock(|| 42);
+        log.log("model_train", "system", "Started training");
+        log.log("model_deploy", "admin", "Deployed v1");
+
+        assert!(log.verify());
+        assert_eq!(log.entries[0].prev_hash, GENESIS_HASH);
+        assert_eq!(log.entries[1].prev_hash, log.entries[0].hash);
+    }
+
+    #[test]
+    fn test_audit_log_detects_tampering() {
+        let mut log = AuditLog::with_clock(|| 42);
+        log.log("model_train", "system", "Started training");
+        log.log("model_deploy", "admin", "Deployed v1");
+        log.log("model_deploy", "admin", "Deployed v2");
+
+        log.entries[1].details = "Deployed v1 (approved)".to_string();
+        assert!(!log.verify());
+    }
+
+    #[test]
+    fn test_audit_log_detects_removed_entry() {
+        let mut log = AuditLog::with_clock(|| 42);
+        log.log("a", "system", "one");
+        log.log("b", "system", "two");
+        log.log("c", "system", "three");
+
+        log.entries.remove(1);
+        assert!(!log.verify());
+    }
+
+    fn seeded_log() -> AuditLog {
+        let now = std::cell::Cell::new(0);
+        let mut log = AuditLog::with_clock(move || {
+            let t = now.get();
+            now.set(t + 10);
+            t
+        });
+        log.log("model_train", "system", "t=0");
+        log.log("model_deploy", "admin", "t=10");
+        log.log("config_change", "admin", "t=20");
+        log.log("model_deploy", "system", "t=30");
+        log.log("model_deploy", "admin", "t=40");
+        log
+    }
+
+    fn details(entries: &[&AuditEntry]) -> Vec<String> {
+        entries.iter().map(|e| e.details.clone()).collect()
+    }
+
+    #[test]
+    fn test_filter_by_actor() {
+        let log = seeded_log();
+        let admin = log.filter(Some("admin"), None, None);
+        assert_eq!(details(&admin), vec!["t=10", "t=20", "t=40"]);
+    }
+
+    #[test]
+    fn test_filter_by_action() {
+        let log = seeded_log();
+        let deploys = log.filter(None, Some("model_deploy"), None);
+        assert_eq!(details(&deploys), vec!["t=10", "t=30", "t=40"]);
+    }
+
+    #[test]
+    fn test_filter_combined() {
+        let log = seeded_log();
+        let entries = log.filter(Some("admin"), Some("model_deploy"), Some((5, 30)));
+        assert_eq!(details(&entries), vec!["t=10"]);
+        assert_eq!(log.filter(None, None, None).len(), log.len());
+    }
+
+    /// Minimal RFC 4180 field splitter for a single CSV line
+    fn parse_csv_line(line: &str) -> Vec<String> {
+        let mut fields = vec![String::new()];
+        let mut in_quotes = false;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, in_quotes) {
+                ('"', true) if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    fields.last_mut().expect("field").push('"');
+                }
+                ('"', _) => in_quotes = !in_quotes,
+                (',', false) => fields.push(String::new()),
+                _ => fields.last_mut().expect("field").push(c),
+            }
+        }
+        fields
+    }
+
+    #[test]
+    fn test_audit_log_csv_escaping() {
+        let mut log = AuditLog::with_clock(|| 7);
+        let details = "Deployed v1, tagged \"stable\"";
+        log.log("model_deploy", "admin", details);
+
+        let csv = log.to_csv();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "timestamp,actor,action,details");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            parse_csv_line(lines[1]),
+            vec!["7", "admin", "model_deploy", details]
+        );
+    }
+
     #[test]
     fn test_summary() {
         let mut auditor = ComplianceAuditor::new();
@@ -439,6 +1120,227 @@ mod tests {
         assert!(!auditor.is_compliant());
     }
 
+    #[test]
+    fn test_report_json_roundtrip() {
+        let mut auditor = ComplianceAuditor::new();
+        auditor.run_all_checks();
+        auditor.add_check(ComplianceCheck::new(
+            "Article 12",
+            "Logs retained",
+            ComplianceStatus::Partial,
+            "30 of 180 days",
+        ));
+
+        let json = auditor.to_json();
+        let parsed: ComplianceReport = serde_json::from_str(&json).expect("valid JSON");
+        assert_eq!(parsed, auditor.report());
+        assert_eq!(parsed.summary.compliant, 9);
+        assert_eq!(parsed.summary.partial, 1);
+        assert!(!parsed.compliant);
+    }
+
+    #[test]
+    fn test_status_serializes_to_stable_strings() {
+        let to_json = |s| serde_json::to_string(&s).expect("serializable");
+        assert_eq!(to_json(ComplianceStatus::Compliant), "\"Compliant\"");
+        assert_eq!(to_json(ComplianceStatus::NonCompliant), "\"NonCompliant\"");
+        assert_eq!(to_json(ComplianceStatus::Partial), "\"Partial\"");
+    }
+
+    #[test]
+    fn test_weighted_score() {
+        let mut auditor = ComplianceAuditor::new();
+        auditor.add_check(
+            ComplianceCheck::new("A", "R1", ComplianceStatus::Compliant, "E").with_weight(2.0),
+        );
+        auditor.add_check(
+            ComplianceCheck::new("A", "R2", ComplianceStatus::Partial, "E").with_weight(1.0),
+        );
+        auditor.add_check(
+            ComplianceCheck::new("A", "R3", ComplianceStatus::NonCompliant, "E").with_weight(1.0),
+        );
+
+        // (2.0 * 1.0 + 1.0 * 0.5 + 1.0 * 0.0) / 4.0 = 62.5%
+        assert!((auditor.score() - 62.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_full_compliance_scores_100() {
+        let mut auditor = ComplianceAuditor::new();
+        auditor.run_all_checks();
+        assert!((auditor.score() - 100.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_custom_check_grouped_by_article() {
+        let mut auditor = ComplianceAuditor::new();
+        auditor.register(
+            "Article 12",
+            Box::new(|| {
+                ComplianceCheck::new(
+                    "ignored",
+                    "Record-keeping enabled",
+                    ComplianceStatus::Compliant,
+                    "AuditLog",
+                )
+            }),
+        );
+        auditor.run_all_checks();
+
+        let grouped = auditor.checks_by_article();
+        assert_eq!(grouped.len(), 4);
+        assert_eq!(grouped["Article 10"].len(), 3);
+        let article_12 = &grouped["Article 12"];
+        assert_eq!(article_12.len(), 1);
+        assert_eq!(article_12[0].requirement, "Record-keeping enabled");
+        assert_eq!(article_12[0].article, "Article 12");
+    }
+
+    #[test]
+    fn test_remediation_report_lists_only_failures() {
+        let mut auditor = ComplianceAuditor::new();
+        auditor.add_check(ComplianceCheck::new(
+            "Article 10",
+            "Data lineage tracked",
+            ComplianceStatus::Compliant,
+            "Pipeline logs",
+        ));
+        auditor.add_check(
+            ComplianceCheck::new(
+                "Article 12",
+                "Logs retained for 180 days",
+                ComplianceStatus::NonCompliant,
+                "Logs rotated after 30 days",
+            )
+            .with_remediation("Increase log retention to 180 days"),
+        );
+
+        let report = auditor.remediation_report();
+        assert_eq!(
+            report,
+            vec![(
+                "Logs retained for 180 days".to_string(),
+                "Increase log retention to 180 days".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_markdown_report() {
+        let mut auditor = ComplianceAuditor::new();
+        auditor.run_all_checks();
+        auditor.add_check(ComplianceCheck::new(
+            "Article 12",
+            "Logs retained",
+            ComplianceStatus::Partial,
+            "30 | 180 days",
+        ));
+
+        let md = auditor.to_markdown();
+        assert!(md.contains("| Article | Requirement | Status | Evidence |"));
+        let rows = md.lines().filter(|l| l.starts_with("| Article ")).count();
+        assert_eq!(rows, auditor.checks.len() + 1); // Header plus one row per check
+        assert!(md.contains("| Article 12 | Logs retained | ⚠️ | 30 \\| 180 days |"));
+        assert!(md.contains("**Overall:** FAIL"));
+    }
+
+    #[test]
+    fn test_severity_gate() {
+        let finding = |requirement: &str, severity| {
+            ComplianceCheck::new("A", requirement, ComplianceStatus::NonCompliant, "E")
+                .with_severity(severity)
+        };
+
+        let mut auditor = ComplianceAuditor::new();
+        auditor.run_all_checks();
+        assert_eq!(auditor.highest_severity_failure(), None);
+
+        auditor.add_check(finding("R1", Severity::Medium));
+        auditor.add_check(finding("R2", Severity::Medium));
+        assert_eq!(auditor.highest_severity_failure(), Some(Severity::Medium));
+        assert!(auditor.passes_severity_gate(Severity::High));
+
+        auditor.add_check(finding("R3", Severity::Critical));
+        assert_eq!(auditor.highest_severity_failure(), Some(Severity::Critical));
+        assert!(!auditor.passes_severity_gate(Severity::High));
+    }
+
+    #[test]
+    fn test_diff_detects_regression_and_improvement() {
+        let check = |requirement: &str, status| {
+            ComplianceCheck::new("Article 10", requirement, status, "E")
+        };
+
+        let mut before = ComplianceAuditor::new();
+        before.add_check(check("Lineage tracked", ComplianceStatus::Compliant));
+        before.add_check(check("Bias examined", ComplianceStatus::NonCompliant));
+        before.add_check(check("Data local", ComplianceStatus::Compliant));
+
+        let mut after = ComplianceAuditor::new();
+        after.add_check(check("Lineage tracked", ComplianceStatus::Partial));
+        after.add_check(check("Bias examined", ComplianceStatus::Compliant));
+        after.add_check(check("Data local", ComplianceStatus::Compliant));
+
+        let diff = after.diff(&before);
+        assert_eq!(diff.regressions.len(), 1);
+        assert_eq!(diff.regressions[0].requirement, "Lineage tracked");
+        assert_eq!(
+            diff.regressions[0].before,
+            Some(ComplianceStatus::Compliant)
+        );
+        assert_eq!(diff.improvements.len(), 1);
+        assert_eq!(diff.improvements[0].requirement, "Bias examined");
+        assert!(diff.added.is_empty());
+    }
+
+    #[test]
+    fn test_diff_orders_by_article_then_requirement() {
+        let mut current = ComplianceAuditor::new();
+        for (article, requirement) in [
+            ("Article 15", "B"),
+            ("Article 10", "Z"),
+            ("Article 10", "A"),
+        ] {
+            current.add_check(ComplianceCheck::new(
+                article,
+                requirement,
+                ComplianceStatus::Compliant,
+                "E",
+            ));
+        }
+
+        let added: Vec<_> = current
+            .diff(&ComplianceAuditor::new())
+            .added
+            .into_iter()
+            .map(|c| (c.article, c.requirement))
+            .collect();
+        assert_eq!(
+            added,
+            vec![
+                ("Article 10".to_string(), "A".to_string()),
+                ("Article 10".to_string(), "Z".to_string()),
+                ("Article 15".to_string(), "B".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stack_map_has_no_gaps() {
+        let map = StackComplianceMap::sovereign_stack();
+        let gaps = map.coverage_gaps(&["Article 10", "Article 13", "Article 15"]);
+        assert!(gaps.is_empty());
+    }
+
+    #[test]
+    fn test_stack_map_reports_article_gap() {
+        let mut map = StackComplianceMap::sovereign_stack();
+        map.mappings.retain(|m| m.article != "Article 10");
+
+        let gaps = map.coverage_gaps(&["Article 10", "Article 13", "Article 15"]);
+        assert_eq!(gaps, vec!["Article 10"]);
+    }
+
     #[test]
     fn test_full_compliance() {
         let mut auditor = ComplianceAuditor::new();
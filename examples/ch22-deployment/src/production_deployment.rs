@@ -6,28 +6,63 @@
 /// - EU AI Act compliant operations
 ///
 /// **VALIDATION:** `make run-ch22`
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Deployment environment
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 enum Environment {
     Development,
     Staging,
     Production,
 }
 
+/// How a new version replaces the running one
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum DeploymentStrategy {
+    /// Stop every old replica, then start the new version
+    #[default]
+    Recreate,
+    /// Bring up a full new set and switch all traffic at once
+    BlueGreen,
+    /// Move only `percent` of replicas to the new version
+    Canary { percent: u8 },
+}
+
+impl DeploymentStrategy {
+    fn zero_downtime(self) -> bool {
+        !matches!(self, DeploymentStrategy::Recreate)
+    }
+
+    /// Replicas that should run the new version when replacing a release
+    fn new_replicas(self, replicas: usize) -> usize {
+        match self {
+            DeploymentStrategy::Recreate | DeploymentStrategy::BlueGreen => replicas,
+            DeploymentStrategy::Canary { percent } => {
+                let percent = usize::from(percent.min(100));
+                (replicas * percent).div_ceil(100)
+            }
+        }
+    }
+}
+
 /// Deployment configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 struct DeploymentConfig {
     environment: Environment,
     version: String,
     replicas: usize,
+    #[serde(default)]
     resources: ResourceConfig,
+    #[serde(default)]
+    strategy: DeploymentStrategy,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(dead_code)]
 struct ResourceConfig {
     cpu_cores: usize,
@@ -45,6 +80,26 @@ impl Default for ResourceConfig {
     }
 }
 
+impl ResourceConfig {
+    /// Per-replica resources sized for the environment: development runs
+    /// lean, production gets headroom and a GPU for model serving
+    fn for_environment(environment: Environment) -> Self {
+        match environment {
+            Environment::Development => Self {
+                cpu_cores: 2,
+                memory_mb: 4096,
+                gpu_enabled: false,
+            },
+            Environment::Staging => Self::default(),
+            Environment::Production => Self {
+                cpu_cores: 8,
+                memory_mb: 16384,
+                gpu_enabled: true,
+            },
+        }
+    }
+}
+
 impl DeploymentConfig {
     fn new(environment: Environment, version: &str) -> Self {
         Self {
@@ -55,8 +110,92 @@ impl DeploymentConfig {
                 Environment::Staging => 2,
                 Environment::Production => 3,
             },
-            resources: ResourceConfig::default(),
+            resources: ResourceConfig::for_environment(environment),
+            strategy: DeploymentStrategy::Recreate,
+        }
+    }
+
+    fn with_strategy(mut self, strategy: DeploymentStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Parse a config file such as `deploy.toml`; a missing `[resources]`
+    /// table falls back to the environment's defaults
+    fn from_toml(s: &str) -> Result<Self> {
+        let table: toml::Table = s.parse().context("parsing deployment config")?;
+        let has_resources = table.contains_key("resources");
+        let mut config: Self = table.try_into().context("parsing deployment config")?;
+        if !has_resources {
+            config.resources = ResourceConfig::for_environment(config.environment);
         }
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Build a config from `DEPLOY_*` environment variables
+    ///
+    /// `DEPLOY_ENVIRONMENT` and `DEPLOY_VERSION` are required; replicas and
+    /// resources fall back to the environment's defaults.
+    fn from_env() -> Result<Self> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// `from_env` with an injectable variable source, so tests need not
+    /// touch the process environment
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        fn parsed<T: std::str::FromStr>(
+            lookup: &impl Fn(&str) -> Option<String>,
+            key: &str,
+        ) -> Result<Option<T>>
+        where
+            T::Err: std::fmt::Display,
+        {
+            lookup(key)
+                .map(|v| {
+                    v.parse()
+                        .map_err(|e| anyhow::anyhow!("{}={}: {}", key, v, e))
+                })
+                .transpose()
+        }
+
+        let environment = match lookup("DEPLOY_ENVIRONMENT").as_deref() {
+            Some("development") => Environment::Development,
+            Some("staging") => Environment::Staging,
+            Some("production") => Environment::Production,
+            Some(other) => bail!("DEPLOY_ENVIRONMENT={}: unknown environment", other),
+            None => bail!("DEPLOY_ENVIRONMENT is not set"),
+        };
+        let version = lookup("DEPLOY_VERSION").context("DEPLOY_VERSION is not set")?;
+
+        let mut config = Self::new(environment, &version);
+        if let Some(replicas) = parsed(&lookup, "DEPLOY_REPLICAS")? {
+            config.replicas = replicas;
+        }
+        if let Some(cpu_cores) = parsed(&lookup, "DEPLOY_CPU_CORES")? {
+            config.resources.cpu_cores = cpu_cores;
+        }
+        if let Some(memory_mb) = parsed(&lookup, "DEPLOY_MEMORY_MB")? {
+            config.resources.memory_mb = memory_mb;
+        }
+        if let Some(gpu_enabled) = parsed(&lookup, "DEPLOY_GPU_ENABLED")? {
+            config.resources.gpu_enabled = gpu_enabled;
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.version.is_empty() {
+            bail!("version must not be empty");
+        }
+        if self.replicas == 0 {
+            bail!("replicas must be at least 1");
+        }
+        if self.resources.cpu_cores == 0 || self.resources.memory_mb == 0 {
+            bail!("resources must request at least 1 core and 1 MB");
+        }
+        Ok(())
     }
 }
 
@@ -69,11 +208,15 @@ enum HealthStatus {
 }
 
 /// Service health
+///
+/// `status` answers "is it running?" (liveness); `ready` answers "should
+/// it receive traffic?" (readiness), e.g. false while a model is warming up.
 #[derive(Debug)]
 struct ServiceHealth {
     name: String,
     status: HealthStatus,
     latency_ms: u64,
+    ready: bool,
 }
 
 impl ServiceHealth {
@@ -82,8 +225,48 @@ impl ServiceHealth {
             name: name.to_string(),
             status,
             latency_ms,
+            ready: true,
         }
     }
+
+    fn is_alive(&self) -> bool {
+        self.status != HealthStatus::Unhealthy
+    }
+
+    fn is_ready(&self) -> bool {
+        self.ready && self.is_alive()
+    }
+}
+
+/// Service level objectives checked against collected metrics
+#[derive(Debug, Clone)]
+struct Slo {
+    min_uptime: f64,
+    max_avg_latency_ms: f64,
+}
+
+/// Outcome of one objective; a missing metric counts as a failure
+#[derive(Debug, Clone, PartialEq)]
+struct ObjectiveResult {
+    metric: &'static str,
+    target: f64,
+    actual: Option<f64>,
+    passed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SloResult {
+    objectives: Vec<ObjectiveResult>,
+    /// True only if every objective passed
+    met: bool,
+}
+
+/// Services and metrics of a superseded release, kept for rollback
+#[derive(Debug)]
+struct Snapshot {
+    services: Vec<ServiceHealth>,
+    metrics: HashMap<String, f64>,
+    replica_versions: Vec<String>,
 }
 
 /// Deployment manager
@@ -92,6 +275,18 @@ struct DeploymentManager {
     config: DeploymentConfig,
     services: Vec<ServiceHealth>,
     metrics: HashMap<String, f64>,
+    /// Every recorded sample per metric, stamped with a logical clock so
+    /// series are reproducible
+    metric_series: HashMap<String, Vec<(u64, f64)>>,
+    metric_clock: u64,
+    /// Version running on each replica
+    replica_versions: Vec<String>,
+    /// Every deployed version in order; the last one is active
+    deployed_versions: Vec<String>,
+    /// One snapshot per superseded version in `deployed_versions`
+    history: Vec<Snapshot>,
+    /// Cluster capacity checked before each deploy, when known
+    capacity: Option<ResourceConfig>,
 }
 
 impl DeploymentManager {
@@ -100,10 +295,73 @@ impl DeploymentManager {
             config,
             services: Vec::new(),
             metrics: HashMap::new(),
+            metric_series: HashMap::new(),
+            metric_clock: 0,
+            replica_versions: Vec::new(),
+            deployed_versions: Vec::new(),
+            history: Vec::new(),
+            capacity: None,
         }
     }
 
+    fn with_capacity(mut self, capacity: ResourceConfig) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Check that all replicas fit in `available`, explaining any shortfall
+    fn check_capacity(&self, available: &ResourceConfig) -> Result<(), String> {
+        let replicas = self.config.replicas;
+        let per_replica = &self.config.resources;
+        let cpu_needed = replicas.checked_mul(per_replica.cpu_cores).ok_or_else(|| {
+            format!(
+                "{} replicas × {} cores overflows",
+                replicas, per_replica.cpu_cores
+            )
+        })?;
+        let memory_needed = replicas.checked_mul(per_replica.memory_mb).ok_or_else(|| {
+            format!(
+                "{} replicas × {}MB overflows",
+                replicas, per_replica.memory_mb
+            )
+        })?;
+
+        if cpu_needed > available.cpu_cores {
+            return Err(format!(
+                "{} replicas × {} cores = {} cores, cluster has {}",
+                replicas, per_replica.cpu_cores, cpu_needed, available.cpu_cores
+            ));
+        }
+        if memory_needed > available.memory_mb {
+            return Err(format!(
+                "{} replicas × {}MB = {}MB, cluster has {}MB",
+                replicas, per_replica.memory_mb, memory_needed, available.memory_mb
+            ));
+        }
+        if per_replica.gpu_enabled && !available.gpu_enabled {
+            return Err("replicas need a GPU, cluster has none".to_string());
+        }
+        Ok(())
+    }
+
+    fn can_schedule(&self, available: &ResourceConfig) -> bool {
+        self.check_capacity(available).is_ok()
+    }
+
     fn deploy(&mut self) -> Result<(), String> {
+        if let Some(capacity) = &self.capacity {
+            self.check_capacity(capacity)
+                .map_err(|e| format!("cannot schedule: {}", e))?;
+        }
+        if !self.deployed_versions.is_empty() {
+            self.history.push(Snapshot {
+                services: std::mem::take(&mut self.services),
+                metrics: std::mem::take(&mut self.metrics),
+                replica_versions: self.replica_versions.clone(),
+            });
+        }
+        self.roll_out_replicas();
+
         // Simulate deployment steps
         self.services
             .push(ServiceHealth::new("api", HealthStatus::Healthy, 15));
@@ -112,13 +370,138 @@ impl DeploymentManager {
         self.services
             .push(ServiceHealth::new("database", HealthStatus::Healthy, 5));
 
-        self.metrics.insert("uptime".to_string(), 99.9);
-        self.metrics.insert("requests_per_sec".to_string(), 1000.0);
-        self.metrics.insert("avg_latency_ms".to_string(), 23.0);
+        self.record_metric("uptime", 99.9);
+        self.record_metric("requests_per_sec", 1000.0);
+        self.record_metric("avg_latency_ms", 23.0);
+
+        self.deployed_versions.push(self.config.version.clone());
+        Ok(())
+    }
+
+    /// Assign replicas to the new version according to the strategy;
+    /// a first deployment has nothing to keep, so every replica is new
+    fn roll_out_replicas(&mut self) {
+        let replicas = self.config.replicas;
+        let new_count = if self.replica_versions.is_empty() {
+            replicas
+        } else {
+            self.config.strategy.new_replicas(replicas)
+        };
+        let previous = std::mem::take(&mut self.replica_versions);
+        self.replica_versions = (0..replicas)
+            .map(|i| match previous.get(i) {
+                Some(old) if i >= new_count => old.clone(),
+                _ => self.config.version.clone(),
+            })
+            .collect();
+    }
+
+    /// Number of replicas running `version`
+    fn replicas_on(&self, version: &str) -> usize {
+        self.replica_versions
+            .iter()
+            .filter(|v| v.as_str() == version)
+            .count()
+    }
+
+    /// Move every replica to the canary version if it is `Healthy`;
+    /// otherwise roll back to the previous release and report why
+    fn promote_canary(&mut self) -> Result<(), String> {
+        let version = self
+            .active_version()
+            .ok_or_else(|| "nothing deployed".to_string())?
+            .to_string();
+        if self.replicas_on(&version) == self.replica_versions.len() {
+            return Err(format!("no canary in progress for {}", version));
+        }
+
+        let health = self.health_check();
+        if health != HealthStatus::Healthy {
+            let restored = self.rollback()?;
+            return Err(format!(
+                "canary {} is {:?}; rolled back to {}",
+                version, health, restored
+            ));
+        }
 
+        for replica in &mut self.replica_versions {
+            replica.clone_from(&version);
+        }
+        Ok(())
+    }
+
+    /// Deploy a new version over the active one
+    ///
+    /// If the deployment is refused, the configured version is put back so
+    /// it keeps matching [`Self::active_version`].
+    fn deploy_version(&mut self, version: &str) -> Result<(), String> {
+        let previous = std::mem::replace(&mut self.config.version, version.to_string());
+        if let Err(e) = self.deploy() {
+            self.config.version = previous;
+            return Err(e);
+        }
         Ok(())
     }
 
+    fn active_version(&self) -> Option<&str> {
+        self.deployed_versions.last().map(|v| v.as_str())
+    }
+
+    /// Restore the previous release, returning the version now active
+    fn rollback(&mut self) -> Result<String, String> {
+        let snapshot = self
+            .history
+            .pop()
+            .ok_or_else(|| match self.active_version() {
+                Some(v) => format!("no version before {} to roll back to", v),
+                None => "nothing deployed".to_string(),
+            })?;
+        self.deployed_versions.pop();
+        self.services = snapshot.services;
+        self.metrics = snapshot.metrics;
+        self.replica_versions = snapshot.replica_versions;
+
+        let version = self
+            .deployed_versions
+            .last()
+            .cloned()
+            .expect("a snapshot implies an earlier version");
+        self.config.version = version.clone();
+        Ok(version)
+    }
+
+    /// Force a service into the given status, returning whether it exists
+    fn inject_fault(&mut self, service: &str, status: HealthStatus) -> bool {
+        match self.services.iter_mut().find(|s| s.name == service) {
+            Some(s) => {
+                s.status = status;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Mark a service as (not) accepting traffic, returning whether it exists
+    fn set_ready(&mut self, service: &str, ready: bool) -> bool {
+        match self.services.iter_mut().find(|s| s.name == service) {
+            Some(s) => {
+                s.ready = ready;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Readiness probe: every service is up and accepting traffic
+    fn readiness(&self) -> bool {
+        !self.services.is_empty() && self.services.iter().all(|s| s.is_ready())
+    }
+
+    /// Liveness probe: no service has crashed
+    fn liveness(&self) -> bool {
+        self.services.iter().all(|s| s.is_alive())
+    }
+
     fn health_check(&self) -> HealthStatus {
         if self
             .services
@@ -140,6 +523,76 @@ impl DeploymentManager {
     fn get_metric(&self, name: &str) -> Option<f64> {
         self.metrics.get(name).copied()
     }
+
+    /// Services ordered by name, for reproducible reports
+    fn services_sorted(&self) -> Vec<&ServiceHealth> {
+        let mut services: Vec<&ServiceHealth> = self.services.iter().collect();
+        services.sort_by(|a, b| a.name.cmp(&b.name));
+        services
+    }
+
+    /// Latest metric values ordered by name, independent of `HashMap` order
+    fn metrics_sorted(&self) -> Vec<(&str, f64)> {
+        let mut metrics: Vec<(&str, f64)> = self
+            .metrics
+            .iter()
+            .map(|(name, &value)| (name.as_str(), value))
+            .collect();
+        metrics.sort_by(|a, b| a.0.cmp(b.0));
+        metrics
+    }
+
+    /// Compare the latest metrics against each objective
+    fn evaluate_slo(&self, slo: &Slo) -> SloResult {
+        let objective = |metric: &'static str, target: f64, ok: fn(f64, f64) -> bool| {
+            let actual = self.get_metric(metric);
+            ObjectiveResult {
+                metric,
+                target,
+                actual,
+                passed: actual.is_some_and(|a| ok(a, target)),
+            }
+        };
+        let objectives = vec![
+            objective("uptime", slo.min_uptime, |a, t| a >= t),
+            objective("avg_latency_ms", slo.max_avg_latency_ms, |a, t| a <= t),
+        ];
+        let met = objectives.iter().all(|o| o.passed);
+        SloResult { objectives, met }
+    }
+
+    /// Append a sample to the metric's series and make it the latest value
+    fn record_metric(&mut self, name: &str, value: f64) {
+        self.metric_clock += 1;
+        self.metric_series
+            .entry(name.to_string())
+            .or_default()
+            .push((self.metric_clock, value));
+        self.metrics.insert(name.to_string(), value);
+    }
+
+    /// Samples for a metric in recording order
+    fn metric_series(&self, name: &str) -> Option<&[(u64, f64)]> {
+        self.metric_series.get(name).map(|v| v.as_slice())
+    }
+
+    /// Least-squares slope of a metric per clock tick (`None` for < 2 samples)
+    fn metric_trend(&self, name: &str) -> Option<f64> {
+        let series = self.metric_series(name)?;
+        if series.len() < 2 {
+            return None;
+        }
+        let n = series.len() as f64;
+        let mean_t = series.iter().map(|&(t, _)| t as f64).sum::<f64>() / n;
+        let mean_v = series.iter().map(|&(_, v)| v).sum::<f64>() / n;
+        let (mut cov, mut var) = (0.0, 0.0);
+        for &(t, v) in series {
+            let dt = t as f64 - mean_t;
+            cov += dt * (v - mean_v);
+            var += dt * dt;
+        }
+        Some(cov / var)
+    }
 }
 
 /// Demonstrate deployment configuration
@@ -154,23 +607,111 @@ fn config_demo() {
     ];
 
     println!(
-        "   {:>12} │ {:>8} │ {:>8} │ {:>6}",
-        "Environment", "Replicas", "CPU", "Memory"
+        "   {:>12} │ {:>8} │ {:>8} │ {:>8} │ {:>3}",
+        "Environment", "Replicas", "CPU", "Memory", "GPU"
     );
-    println!("   ─────────────┼──────────┼──────────┼────────");
+    println!("   ─────────────┼──────────┼──────────┼──────────┼─────");
 
     for config in configs {
         println!(
-            "   {:>12?} │ {:>8} │ {:>8} │ {:>6}MB",
-            config.environment,
+            "   {:>12} │ {:>8} │ {:>8} │ {:>6}MB │ {:>3}",
+            format!("{:?}", config.environment),
             config.replicas,
             config.resources.cpu_cores,
-            config.resources.memory_mb
+            config.resources.memory_mb,
+            if config.resources.gpu_enabled {
+                "yes"
+            } else {
+                "no"
+            }
         );
     }
     println!();
 }
 
+/// Demonstrate loading configuration from a file and the environment
+fn config_file_demo() -> Result<()> {
+    println!("📄 Configuration as Code");
+    println!();
+
+    let file = r#"
+environment = "production"
+version = "2.0.0"
+replicas = 4
+
+[resources]
+cpu_cores = 8
+memory_mb = 32768
+gpu_enabled = true
+
+[strategy]
+type = "canary"
+percent = 25
+"#;
+    let config = DeploymentConfig::from_toml(file)?;
+    println!("   deploy.toml:");
+    println!(
+        "   {:?} v{} × {} replicas, {} cores / {}MB / gpu={}, {:?}",
+        config.environment,
+        config.version,
+        config.replicas,
+        config.resources.cpu_cores,
+        config.resources.memory_mb,
+        config.resources.gpu_enabled,
+        config.strategy
+    );
+
+    match DeploymentConfig::from_env() {
+        Ok(config) => println!(
+            "   DEPLOY_* environment: {:?} v{}",
+            config.environment, config.version
+        ),
+        Err(e) => println!("   DEPLOY_* environment: not configured ({})", e),
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Demonstrate capacity admission checks
+fn capacity_demo() {
+    println!("📏 Capacity Admission");
+    println!();
+
+    let config = DeploymentConfig::new(Environment::Production, "1.0.0");
+    println!(
+        "   Need: {} replicas × {} cores / {}MB / gpu={}",
+        config.replicas,
+        config.resources.cpu_cores,
+        config.resources.memory_mb,
+        config.resources.gpu_enabled
+    );
+
+    let clusters = [
+        ("small", 16, 32768, true),
+        ("cpu-only", 64, 131072, false),
+        ("large", 64, 131072, true),
+    ];
+    for (name, cpu_cores, memory_mb, gpu_enabled) in clusters {
+        let capacity = ResourceConfig {
+            cpu_cores,
+            memory_mb,
+            gpu_enabled,
+        };
+        let mut manager = DeploymentManager::new(config.clone()).with_capacity(capacity.clone());
+        let fits = if manager.can_schedule(&capacity) {
+            "✅"
+        } else {
+            "❌"
+        };
+        match manager.deploy() {
+            Ok(()) => println!("   {} {:<8} deployed", fits, name),
+            Err(e) => println!("   {} {:<8} {}", fits, name, e),
+        }
+    }
+    println!();
+}
+
 /// Demonstrate deployment
 fn deployment_demo() {
     println!("🚀 Deployment Execution");
@@ -223,6 +764,191 @@ fn monitoring_demo() {
         println!("   - Avg latency: {:.1}ms", latency);
     }
     println!();
+
+    println!("   Report (name-sorted):");
+    for service in manager.services_sorted() {
+        println!("   - service {:<16} {:?}", service.name, service.status);
+    }
+    for (name, value) in manager.metrics_sorted() {
+        println!("   - metric  {:<16} {:.1}", name, value);
+    }
+    println!();
+
+    for uptime in [99.7, 99.4, 99.1] {
+        manager.record_metric("uptime", uptime);
+    }
+    if let (Some(series), Some(trend)) = (
+        manager.metric_series("uptime"),
+        manager.metric_trend("uptime"),
+    ) {
+        let samples: Vec<String> = series.iter().map(|(_, v)| format!("{:.1}", v)).collect();
+        println!("   Uptime series: [{}]", samples.join(", "));
+        println!("   Uptime trend:  {:+.3} per clock tick", trend);
+    }
+    println!();
+
+    let slo = Slo {
+        min_uptime: 99.0,
+        max_avg_latency_ms: 20.0,
+    };
+    let result = manager.evaluate_slo(&slo);
+    println!("   SLO evaluation:");
+    for o in &result.objectives {
+        let actual = o
+            .actual
+            .map_or_else(|| "missing".to_string(), |a| format!("{:.1}", a));
+        println!(
+            "   {} {:<15} target {:>5.1}, actual {:>7}",
+            if o.passed { "✅" } else { "❌" },
+            o.metric,
+            o.target,
+            actual
+        );
+    }
+    println!(
+        "   Verdict: {}",
+        if result.met {
+            "SLO met"
+        } else {
+            "SLO violated"
+        }
+    );
+    println!();
+
+    println!("   Probes:");
+    manager.set_ready("model", false);
+    println!(
+        "   - model warming up   → ready={}, live={}",
+        manager.readiness(),
+        manager.liveness()
+    );
+    manager.set_ready("model", true);
+    println!(
+        "   - model warmed up    → ready={}, live={}",
+        manager.readiness(),
+        manager.liveness()
+    );
+    println!();
+
+    println!("   Fault injection:");
+    manager.inject_fault("model", HealthStatus::Degraded);
+    println!(
+        "   - model Degraded     → overall {:?}",
+        manager.health_check()
+    );
+    manager.inject_fault("database", HealthStatus::Unhealthy);
+    println!(
+        "   - database Unhealthy → overall {:?}",
+        manager.health_check()
+    );
+    println!();
+}
+
+/// Demonstrate rollout strategies
+fn strategy_demo() -> Result<()> {
+    println!("🐤 Rollout Strategies");
+    println!();
+
+    let strategies = [
+        DeploymentStrategy::Recreate,
+        DeploymentStrategy::BlueGreen,
+        DeploymentStrategy::Canary { percent: 20 },
+    ];
+
+    println!(
+        "   {:>22} │ {:>13} │ {:>5} │ {:>5}",
+        "Strategy", "Zero-downtime", "1.1.0", "1.0.0"
+    );
+    println!("   ───────────────────────┼───────────────┼───────┼───────");
+
+    for strategy in strategies {
+        let mut config = DeploymentConfig::new(Environment::Production, "1.0.0");
+        config.replicas = 5;
+        let mut manager = DeploymentManager::new(config.with_strategy(strategy));
+        manager.deploy().map_err(anyhow::Error::msg)?;
+        manager
+            .deploy_version("1.1.0")
+            .map_err(anyhow::Error::msg)?;
+
+        println!(
+            "   {:>22} │ {:>13} │ {:>5} │ {:>5}",
+            format!("{:?}", strategy),
+            if strategy.zero_downtime() {
+                "yes"
+            } else {
+                "no"
+            },
+            manager.replicas_on("1.1.0"),
+            manager.replicas_on("1.0.0")
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Demonstrate health-gated canary promotion
+fn canary_promotion_demo() -> Result<()> {
+    println!("🚦 Progressive Delivery");
+    println!();
+
+    for faulty in [false, true] {
+        let mut config = DeploymentConfig::new(Environment::Production, "1.0.0")
+            .with_strategy(DeploymentStrategy::Canary { percent: 20 });
+        config.replicas = 5;
+        let mut manager = DeploymentManager::new(config);
+        manager.deploy().map_err(anyhow::Error::msg)?;
+        manager
+            .deploy_version("1.1.0")
+            .map_err(anyhow::Error::msg)?;
+        if faulty {
+            manager.inject_fault("model", HealthStatus::Unhealthy);
+        }
+
+        println!(
+            "   Canary 1.1.0 on {}/5 replicas{}",
+            manager.replicas_on("1.1.0"),
+            if faulty { " (model unhealthy)" } else { "" }
+        );
+        match manager.promote_canary() {
+            Ok(()) => println!(
+                "   ✅ Promoted: 1.1.0 on {}/5",
+                manager.replicas_on("1.1.0")
+            ),
+            Err(e) => println!("   ❌ Blocked: {}", e),
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Demonstrate rollback
+fn rollback_demo() -> Result<()> {
+    println!("⏪ Rollback");
+    println!();
+
+    let config = DeploymentConfig::new(Environment::Production, "1.0.0");
+    let mut manager = DeploymentManager::new(config);
+    manager.deploy().map_err(anyhow::Error::msg)?;
+    manager
+        .deploy_version("1.1.0")
+        .map_err(anyhow::Error::msg)?;
+    println!("   Deployed: {}", manager.deployed_versions.join(" → "));
+    println!(
+        "   Active:   {}",
+        manager.active_version().unwrap_or("none")
+    );
+
+    let restored = manager.rollback().map_err(anyhow::Error::msg)?;
+    println!("   Rolled back to {}", restored);
+    match manager.rollback() {
+        Ok(v) => println!("   ❌ Unexpected second rollback to {}", v),
+        Err(e) => println!("   Second rollback refused: {}", e),
+    }
+    println!();
+
+    Ok(())
 }
 
 /// Demonstrate determinism
@@ -318,6 +1044,14 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
+    config_file_demo()?;
+    println!("{}", "─".repeat(70));
+    println!();
+
+    capacity_demo();
+    println!("{}", "─".repeat(70));
+    println!();
+
     deployment_demo();
     println!("{}", "─".repeat(70));
     println!();
@@ -326,6 +1060,18 @@ fn main() -> Result<()> {
     println!("{}", "─".repeat(70));
     println!();
 
+    strategy_demo()?;
+    println!("{}", "─".repeat(70));
+    println!();
+
+    canary_promotion_demo()?;
+    println!("{}", "─".repeat(70));
+    println!();
+
+    rollback_demo()?;
+    println!("{}", "─".repeat(70));
+    println!();
+
     determinism_demo();
     println!("{}", "─".repeat(70));
     println!();
@@ -371,6 +1117,160 @@ mod tests {
         assert_eq!(prd.replicas, 3);
     }
 
+    #[test]
+    fn test_environment_resources() {
+        let dev = ResourceConfig::for_environment(Environment::Development);
+        let stg = ResourceConfig::for_environment(Environment::Staging);
+        let prd = ResourceConfig::for_environment(Environment::Production);
+
+        assert!(prd.cpu_cores > stg.cpu_cores && stg.cpu_cores > dev.cpu_cores);
+        assert!(prd.memory_mb > stg.memory_mb && stg.memory_mb > dev.memory_mb);
+        assert!(prd.gpu_enabled);
+        assert!(!dev.gpu_enabled);
+
+        let config = DeploymentConfig::new(Environment::Production, "1.0.0");
+        assert_eq!(config.resources, prd);
+
+        let from_file = DeploymentConfig::from_toml(
+            "environment = \"development\"\nversion = \"0.1.0\"\nreplicas = 1",
+        )
+        .expect("valid TOML");
+        assert_eq!(from_file.resources, dev);
+    }
+
+    #[test]
+    fn test_config_from_toml() {
+        let config = DeploymentConfig::from_toml(
+            r#"
+environment = "staging"
+version = "1.4.2"
+replicas = 5
+
+[resources]
+cpu_cores = 6
+memory_mb = 12288
+gpu_enabled = true
+"#,
+        )
+        .expect("valid TOML");
+
+        assert_eq!(config.environment, Environment::Staging);
+        assert_eq!(config.version, "1.4.2");
+        assert_eq!(config.replicas, 5);
+        assert_eq!(
+            config.resources,
+            ResourceConfig {
+                cpu_cores: 6,
+                memory_mb: 12288,
+                gpu_enabled: true,
+            }
+        );
+        assert_eq!(config.strategy, DeploymentStrategy::Recreate);
+
+        let canary = DeploymentConfig::from_toml(
+            r#"
+environment = "production"
+version = "1.0.0"
+replicas = 3
+
+[strategy]
+type = "canary"
+percent = 20
+"#,
+        )
+        .expect("valid TOML");
+        assert_eq!(canary.strategy, DeploymentStrategy::Canary { percent: 20 });
+
+        assert!(DeploymentConfig::from_toml("environment = \"qa\"").is_err());
+        assert!(DeploymentConfig::from_toml(
+            "environment = \"staging\"\nversion = \"1.0.0\"\nreplicas = 0"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_config_from_env_lookup() {
+        let vars: HashMap<&str, &str> = [
+            ("DEPLOY_ENVIRONMENT", "production"),
+            ("DEPLOY_VERSION", "3.1.0"),
+            ("DEPLOY_CPU_CORES", "16"),
+        ]
+        .into_iter()
+        .collect();
+        let config = DeploymentConfig::from_lookup(|k| vars.get(k).map(|v| v.to_string()))
+            .expect("valid environment");
+        assert_eq!(config.environment, Environment::Production);
+        assert_eq!(config.version, "3.1.0");
+        assert_eq!(config.replicas, 3);
+        assert_eq!(config.resources.cpu_cores, 16);
+
+        let bad = DeploymentConfig::from_lookup(|k| match k {
+            "DEPLOY_ENVIRONMENT" => Some("staging".to_string()),
+            "DEPLOY_VERSION" => Some("1.0.0".to_string()),
+            "DEPLOY_REPLICAS" => Some("many".to_string()),
+            _ => None,
+        });
+        assert!(bad
+            .expect_err("non-numeric replicas")
+            .to_string()
+            .contains("DEPLOY_REPLICAS"));
+        assert!(DeploymentConfig::from_lookup(|_| None).is_err());
+    }
+
+    #[test]
+    fn test_capacity_admission() {
+        let mut config = DeploymentConfig::new(Environment::Staging, "1.0.0");
+        config.replicas = 3;
+        config.resources = ResourceConfig {
+            cpu_cores: 4,
+            memory_mb: 1024,
+            gpu_enabled: false,
+        };
+        let cluster = |cpu_cores| ResourceConfig {
+            cpu_cores,
+            memory_mb: 65536,
+            gpu_enabled: false,
+        };
+        let manager = DeploymentManager::new(config.clone());
+
+        assert!(!manager.can_schedule(&cluster(10)));
+        let err = manager
+            .check_capacity(&cluster(10))
+            .expect_err("12 > 10 cores");
+        assert!(err.contains("12 cores"));
+        assert!(manager.can_schedule(&cluster(16)));
+
+        let mut blocked = DeploymentManager::new(config.clone()).with_capacity(cluster(10));
+        assert!(blocked.deploy().is_err());
+        assert!(blocked.services.is_empty());
+        let mut admitted = DeploymentManager::new(config).with_capacity(cluster(16));
+        assert!(admitted.deploy().is_ok());
+    }
+
+    #[test]
+    fn test_capacity_overflow_is_an_error() {
+        let mut config = DeploymentConfig::new(Environment::Staging, "1.0.0");
+        config.replicas = usize::MAX;
+        let manager = DeploymentManager::new(config);
+        let err = manager
+            .check_capacity(&ResourceConfig::default())
+            .expect_err("usize::MAX replicas cannot fit");
+        assert!(err.contains("overflows"));
+    }
+
+    #[test]
+    fn test_refused_deploy_keeps_configured_version() {
+        let mut config = DeploymentConfig::new(Environment::Staging, "1.0.0");
+        config.replicas = 2;
+        let per_replica = config.resources.clone();
+        let mut manager = DeploymentManager::new(config).with_capacity(per_replica);
+
+        // The cluster fits one replica, so even the first deploy is refused
+        assert!(manager.deploy_version("2.0.0").is_err());
+        assert_eq!(manager.config.version, "1.0.0");
+        assert_eq!(manager.active_version(), None);
+    }
+
     #[test]
     fn test_deployment() {
         let config = DeploymentConfig::new(Environment::Production, "1.0.0");
@@ -391,6 +1291,49 @@ mod tests {
         assert_eq!(health, HealthStatus::Healthy);
     }
 
+    #[test]
+    fn test_degraded_service_degrades_overall_health() {
+        let config = DeploymentConfig::new(Environment::Production, "1.0.0");
+        let mut manager = DeploymentManager::new(config);
+        manager.deploy().expect("deployment succeeds");
+
+        assert!(manager.inject_fault("model", HealthStatus::Degraded));
+        assert_eq!(manager.health_check(), HealthStatus::Degraded);
+        assert!(!manager.inject_fault("missing", HealthStatus::Unhealthy));
+        assert_eq!(manager.health_check(), HealthStatus::Degraded);
+    }
+
+    #[test]
+    fn test_unhealthy_service_makes_overall_unhealthy() {
+        let config = DeploymentConfig::new(Environment::Production, "1.0.0");
+        let mut manager = DeploymentManager::new(config);
+        manager.deploy().expect("deployment succeeds");
+
+        manager.inject_fault("api", HealthStatus::Degraded);
+        manager.inject_fault("database", HealthStatus::Unhealthy);
+        assert_eq!(manager.health_check(), HealthStatus::Unhealthy);
+    }
+
+    #[test]
+    fn test_alive_but_not_ready() {
+        let config = DeploymentConfig::new(Environment::Production, "1.0.0");
+        let mut manager = DeploymentManager::new(config);
+        assert!(!manager.readiness());
+
+        manager.deploy().expect("deployment succeeds");
+        assert!(manager.readiness());
+        assert!(manager.liveness());
+
+        assert!(manager.set_ready("model", false));
+        assert!(!manager.readiness());
+        assert!(manager.liveness());
+
+        manager.set_ready("model", true);
+        manager.inject_fault("api", HealthStatus::Unhealthy);
+        assert!(!manager.readiness());
+        assert!(!manager.liveness());
+    }
+
     #[test]
     fn test_metrics() {
         let config = DeploymentConfig::new(Environment::Production, "1.0.0");
@@ -401,6 +1344,182 @@ mod tests {
         assert!(manager.get_metric("requests_per_sec").is_some());
     }
 
+    #[test]
+    fn test_rollback_restores_previous_version() {
+        let config = DeploymentConfig::new(Environment::Production, "1.0.0");
+        let mut manager = DeploymentManager::new(config);
+        assert!(manager.rollback().is_err());
+
+        manager.deploy().expect("deploy 1.0.0");
+        manager.metrics.insert("uptime".to_string(), 99.5);
+        manager.deploy_version("1.1.0").expect("deploy 1.1.0");
+        assert_eq!(manager.active_version(), Some("1.1.0"));
+        assert_eq!(manager.services.len(), 3);
+
+        assert_eq!(manager.rollback(), Ok("1.0.0".to_string()));
+        assert_eq!(manager.active_version(), Some("1.0.0"));
+        assert_eq!(manager.config.version, "1.0.0");
+        assert_eq!(manager.services.len(), 3);
+        assert_eq!(manager.get_metric("uptime"), Some(99.5));
+
+        let err = manager.rollback().expect_err("no version before 1.0.0");
+        assert!(err.contains("1.0.0"));
+        assert_eq!(manager.active_version(), Some("1.0.0"));
+    }
+
+    #[test]
+    fn test_canary_moves_fraction_of_replicas() {
+        let mut config = DeploymentConfig::new(Environment::Production, "1.0.0")
+            .with_strategy(DeploymentStrategy::Canary { percent: 20 });
+        config.replicas = 5;
+        let mut manager = DeploymentManager::new(config);
+
+        manager.deploy().expect("initial deploy");
+        assert_eq!(manager.replicas_on("1.0.0"), 5);
+
+        manager.deploy_version("1.1.0").expect("canary deploy");
+        assert_eq!(manager.replicas_on("1.1.0"), 1);
+        assert_eq!(manager.replicas_on("1.0.0"), 4);
+        assert_eq!(manager.replica_versions[0], "1.1.0");
+
+        manager.rollback().expect("rollback");
+        assert_eq!(manager.replicas_on("1.0.0"), 5);
+    }
+
+    fn canary_manager() -> DeploymentManager {
+        let mut config = DeploymentConfig::new(Environment::Production, "1.0.0")
+            .with_strategy(DeploymentStrategy::Canary { percent: 20 });
+        config.replicas = 5;
+        let mut manager = DeploymentManager::new(config);
+        manager.deploy().expect("initial deploy");
+        manager.deploy_version("1.1.0").expect("canary deploy");
+        manager
+    }
+
+    #[test]
+    fn test_healthy_canary_promotes() {
+        let mut manager = canary_manager();
+        assert_eq!(manager.promote_canary(), Ok(()));
+        assert_eq!(manager.replicas_on("1.1.0"), 5);
+        assert_eq!(manager.active_version(), Some("1.1.0"));
+        assert!(manager.promote_canary().is_err(), "nothing left to promote");
+    }
+
+    #[test]
+    fn test_unhealthy_canary_rolls_back() {
+        let mut manager = canary_manager();
+        manager.inject_fault("model", HealthStatus::Unhealthy);
+
+        let err = manager.promote_canary().expect_err("unhealthy canary");
+        assert!(err.contains("rolled back to 1.0.0"));
+        assert_eq!(manager.active_version(), Some("1.0.0"));
+        assert_eq!(manager.replicas_on("1.0.0"), 5);
+        assert_eq!(manager.health_check(), HealthStatus::Healthy);
+    }
+
+    #[test]
+    fn test_recreate_and_blue_green_replace_all_replicas() {
+        for strategy in [DeploymentStrategy::Recreate, DeploymentStrategy::BlueGreen] {
+            let config =
+                DeploymentConfig::new(Environment::Production, "1.0.0").with_strategy(strategy);
+            let mut manager = DeploymentManager::new(config);
+            manager.deploy().expect("initial deploy");
+            manager.deploy_version("2.0.0").expect("upgrade");
+            assert_eq!(manager.replicas_on("2.0.0"), 3);
+        }
+        assert_eq!(DeploymentStrategy::Canary { percent: 0 }.new_replicas(5), 0);
+        assert_eq!(
+            DeploymentStrategy::Canary { percent: 100 }.new_replicas(5),
+            5
+        );
+    }
+
+    #[test]
+    fn test_metric_series() {
+        let config = DeploymentConfig::new(Environment::Production, "1.0.0");
+        let mut manager = DeploymentManager::new(config);
+        assert!(manager.metric_series("uptime").is_none());
+
+        manager.record_metric("uptime", 99.9);
+        manager.record_metric("requests_per_sec", 500.0);
+        manager.record_metric("uptime", 99.5);
+        manager.record_metric("uptime", 99.1);
+
+        let series = manager.metric_series("uptime").expect("recorded");
+        assert_eq!(series.len(), 3);
+        let values: Vec<f64> = series.iter().map(|&(_, v)| v).collect();
+        assert_eq!(values, vec![99.9, 99.5, 99.1]);
+        assert!(series.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(manager.get_metric("uptime"), Some(99.1));
+
+        let trend = manager.metric_trend("uptime").expect("3 samples");
+        assert!(trend < 0.0);
+        assert!(manager.metric_trend("requests_per_sec").is_none());
+    }
+
+    #[test]
+    fn test_slo_mixed_verdict() {
+        let config = DeploymentConfig::new(Environment::Production, "1.0.0");
+        let mut manager = DeploymentManager::new(config);
+        manager.record_metric("uptime", 99.95);
+        manager.record_metric("avg_latency_ms", 45.0);
+
+        let slo = Slo {
+            min_uptime: 99.9,
+            max_avg_latency_ms: 30.0,
+        };
+        let result = manager.evaluate_slo(&slo);
+        assert!(!result.met);
+        assert_eq!(result.objectives.len(), 2);
+        assert_eq!(result.objectives[0].metric, "uptime");
+        assert!(result.objectives[0].passed);
+        assert_eq!(result.objectives[1].metric, "avg_latency_ms");
+        assert!(!result.objectives[1].passed);
+        assert_eq!(result.objectives[1].actual, Some(45.0));
+
+        manager.record_metric("avg_latency_ms", 25.0);
+        assert!(manager.evaluate_slo(&slo).met);
+
+        let empty = DeploymentManager::new(DeploymentConfig::new(Environment::Staging, "1.0.0"));
+        assert!(empty
+            .evaluate_slo(&slo)
+            .objectives
+            .iter()
+            .all(|o| !o.passed));
+    }
+
+    #[test]
+    fn test_sorted_reports_are_stable() {
+        let config = DeploymentConfig::new(Environment::Production, "1.0.0");
+        let mut manager = DeploymentManager::new(config);
+        manager.deploy().expect("deployment succeeds");
+        manager.record_metric("error_rate", 0.01);
+
+        let names: Vec<&str> = manager
+            .services_sorted()
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["api", "database", "model"]);
+
+        let metrics = manager.metrics_sorted();
+        let metric_names: Vec<&str> = metrics.iter().map(|(n, _)| *n).collect();
+        assert_eq!(
+            metric_names,
+            vec!["avg_latency_ms", "error_rate", "requests_per_sec", "uptime"]
+        );
+
+        for _ in 0..10 {
+            assert_eq!(manager.metrics_sorted(), metrics);
+            let again: Vec<&str> = manager
+                .services_sorted()
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect();
+            assert_eq!(again, names);
+        }
+    }
+
     #[test]
     fn test_deployment_determinism() {
         let mut results = Vec::new();